@@ -3,13 +3,62 @@ use dioxus::prelude::*;
 
 mod models;
 mod pages;
+#[cfg(feature = "server")]
+mod recommendations;
+#[cfg(feature = "server")]
+mod roadmap_io;
 mod server_functions;
+#[cfg(feature = "server")]
+mod taxonomy;
 mod utils;
 
 use pages::*;
 
 pub const LOAD_AND_EMBED_JSON: bool = false;
 pub const SESSION_DURATION_DAYS: i64 = 2;
+// Proficiency decay: no decay for the grace period, then the level halves every half-life
+// until it reaches the floor ratio of the recorded level.
+pub const SKILL_DECAY_GRACE_DAYS: i64 = 14;
+pub const SKILL_DECAY_HALF_LIFE_DAYS: f64 = 180.0;
+pub const SKILL_DECAY_FLOOR_RATIO: f64 = 0.3;
+pub const STALE_SKILL_RATIO: f64 = 0.75;
+pub const NODE_COMPLETION_SKILL_LEVEL: u8 = 60;
+pub const MASTERY_QUIZ_QUESTIONS: usize = 5;
+pub const MASTERY_PASS_SCORE: u8 = 70;
+// Spaced repetition (SM-2): easiness starts at 2.5 and never drops below 1.3.
+pub const REVIEW_INITIAL_EASINESS: f64 = 2.5;
+pub const REVIEW_MIN_EASINESS: f64 = 1.3;
+pub const REVIEW_FIRST_INTERVAL_DAYS: u32 = 1;
+pub const REVIEW_SECOND_INTERVAL_DAYS: u32 = 6;
+pub const REVIEW_QUEUE_LIMIT: usize = 10;
+// Schedule planning: study time per node is the watch time of its resources times the
+// practice multiplier; nodes with nothing to go on get the default. No step is planned
+// to take longer than the horizon, however large its estimate.
+pub const DEFAULT_WEEKLY_HOURS: f32 = 4.0;
+pub const DEFAULT_NODE_HOURS: f32 = 3.0;
+pub const PRACTICE_TIME_MULTIPLIER: f32 = 1.5;
+pub const PLAN_SLACK_DAYS: i64 = 3;
+pub const PLAN_HORIZON_DAYS: i64 = 36_500;
+pub const ROADMAP_EXPORT_VERSION: u32 = 1;
+pub const ADMIN_PAGE_SIZE: usize = 50;
+pub const CATALOG_SEARCH_CANDIDATES: usize = 200;
+pub const CATALOG_PAGE_SIZE: usize = 30;
+// Recommendations blend prerequisite readiness, similarity to the learner's skills (over
+// the nearest courses) and team demand, which counts in full at the saturation point.
+// Topics already on an unfinished roadmap keep a share of their score.
+pub const RECOMMENDATION_LIMIT: usize = 6;
+pub const RECOMMENDATION_NEIGHBOURS: usize = 50;
+pub const RECOMMENDATION_READINESS_WEIGHT: f32 = 0.5;
+pub const RECOMMENDATION_SIMILARITY_WEIGHT: f32 = 0.3;
+pub const RECOMMENDATION_TEAM_WEIGHT: f32 = 0.2;
+pub const RECOMMENDATION_TEAM_SATURATION: usize = 3;
+pub const RECOMMENDATION_IN_PROGRESS_FACTOR: f32 = 0.5;
+// Retrieval pulls extra candidates per query and keeps the best after nudging each
+// course's similarity by its feedback quality score.
+pub const RETRIEVAL_CANDIDATES: usize = 10;
+pub const RETRIEVAL_RESULTS_PER_QUERY: usize = 5;
+pub const RETRIEVAL_QUALITY_WEIGHT: f32 = 0.1;
+pub const FEEDBACK_PRIOR_VOTES: f32 = 5.0;
 /*
 TODO:
 1. Show Users how much they currently know, before learning, and what they will learn upon the completion of the given roadmap
@@ -29,8 +78,16 @@ enum Route {
     Profile {},
     #[route("/roadmap/:id")]
     RoadmapView { id: String },
-    #[route("/create-roadmap")]
-    CreateRoadmap {},
+    #[route("/share/:token")]
+    SharedRoadmapView { token: String },
+    #[route("/create-roadmap?:skill")]
+    CreateRoadmap { skill: String },
+    #[route("/organizations")]
+    Organizations {},
+    #[route("/courses")]
+    Courses {},
+    #[route("/admin")]
+    Admin {},
 }
 
 const MAIN_CSS: Asset = asset!("/assets/main.css");
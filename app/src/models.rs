@@ -1,8 +1,17 @@
 use chrono::{DateTime, Utc};
+#[cfg(feature = "server")]
+use course_schema::Course;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "server")]
 use surrealdb::RecordId;
 
+use crate::{
+    DEFAULT_NODE_HOURS, DEFAULT_WEEKLY_HOURS, FEEDBACK_PRIOR_VOTES, PLAN_HORIZON_DAYS,
+    PLAN_SLACK_DAYS, PRACTICE_TIME_MULTIPLIER, REVIEW_FIRST_INTERVAL_DAYS, REVIEW_INITIAL_EASINESS,
+    REVIEW_MIN_EASINESS, REVIEW_SECOND_INTERVAL_DAYS, SKILL_DECAY_FLOOR_RATIO,
+    SKILL_DECAY_GRACE_DAYS, SKILL_DECAY_HALF_LIFE_DAYS, STALE_SKILL_RATIO,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct User {
     pub id: Option<String>,
@@ -13,6 +22,11 @@ pub struct User {
     pub skills_learned: Vec<UserSkills>,
     #[serde(default)]
     pub preferences: UserPreferences,
+    #[serde(default)]
+    pub role: UserRole,
+    /// Locked accounts can't sign in and lose their sessions.
+    #[serde(default)]
+    pub locked: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -25,6 +39,10 @@ pub struct UserDB {
     pub name: String,
     pub skills_learned: Vec<UserSkills>,
     pub preferences: UserPreferences,
+    #[serde(default)]
+    pub role: UserRole,
+    #[serde(default)]
+    pub locked: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -38,6 +56,8 @@ impl From<User> for UserDB {
             name: value.name,
             skills_learned: value.skills_learned,
             preferences: value.preferences,
+            role: value.role,
+            locked: value.locked,
             created_at: value.created_at,
         }
     }
@@ -53,11 +73,30 @@ impl From<UserDB> for User {
             name: value.name,
             skills_learned: value.skills_learned,
             preferences: value.preferences,
+            role: value.role,
+            locked: value.locked,
             created_at: value.created_at,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum UserRole {
+    #[default]
+    Learner,
+    /// Can manage the course corpus and other users from `/admin`.
+    Admin,
+}
+
+impl UserRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            UserRole::Learner => "Learner",
+            UserRole::Admin => "Admin",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct UserPreferences {
     pub learning_style: String,
@@ -66,6 +105,25 @@ pub struct UserPreferences {
     pub difficulty_preference: String,
 }
 
+impl UserPreferences {
+    /// Weekly study hours implied by `time_commitment` ("1-2", "3-5", "6-10", "10+" or a
+    /// plain number). Ranges use their midpoint.
+    pub fn weekly_hours(&self) -> f32 {
+        let raw = self.time_commitment.trim();
+        let parsed = if let Some(min) = raw.strip_suffix('+') {
+            min.trim().parse::<f32>().ok().map(|h| h * 1.2)
+        } else if let Some((lo, hi)) = raw.split_once('-') {
+            match (lo.trim().parse::<f32>(), hi.trim().parse::<f32>()) {
+                (Ok(lo), Ok(hi)) => Some((lo + hi) / 2.0),
+                _ => None,
+            }
+        } else {
+            raw.parse::<f32>().ok()
+        };
+        parsed.filter(|h| *h > 0.0).unwrap_or(DEFAULT_WEEKLY_HOURS)
+    }
+}
+
 #[cfg(feature = "server")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -76,6 +134,17 @@ pub struct Session {
     pub expires_at: DateTime<Utc>,
 }
 
+/// Secret token behind a user's subscribable calendar feed. Rotating or revoking the feed
+/// deletes the record, which invalidates any URL built from it.
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarFeedDB {
+    pub id: Option<RecordId>,
+    pub user_id: RecordId,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Roadmap {
     pub id: Option<String>,
@@ -84,6 +153,10 @@ pub struct Roadmap {
     pub user_id: String,
     pub skill_name: String,
     pub nodes: Vec<RoadmapNode>,
+    #[serde(default)]
+    pub require_mastery_quiz: bool,
+    #[serde(default)]
+    pub origin: Option<RoadmapOrigin>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -97,6 +170,14 @@ pub struct RoadmapDB {
     pub user_id: RecordId,
     pub skill_name: String,
     pub nodes: Vec<RoadmapNode>,
+    #[serde(default)]
+    pub require_mastery_quiz: bool,
+    /// Never copied into the client's `Roadmap`; the owner lists them through
+    /// `list_share_links`.
+    #[serde(default)]
+    pub share_links: Vec<ShareLink>,
+    #[serde(default)]
+    pub origin: Option<RoadmapOriginDB>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -111,29 +192,96 @@ impl From<RoadmapDB> for Roadmap {
             learning_outcomes: value.learning_outcomes,
             skill_name: value.skill_name,
             nodes: value.nodes,
+            require_mastery_quiz: value.require_mastery_quiz,
+            origin: value.origin.map(RoadmapOrigin::from),
             created_at: value.created_at,
             updated_at: value.updated_at,
         }
     }
 }
 
-// TODO: Use tryfrom - idiomatic approach
+/// Public, read-only link to a roadmap. Anyone holding the token can view the roadmap
+/// until the link expires or the owner revokes it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShareLink {
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Show completion state and mastery scores to viewers.
+    #[serde(default)]
+    pub include_progress: bool,
+    /// Show the owner's display name to viewers.
+    #[serde(default)]
+    pub include_owner: bool,
+}
+
+impl ShareLink {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// A roadmap as seen through a share link, stripped of anything the owner didn't opt in to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SharedRoadmap {
+    pub roadmap: Roadmap,
+    pub owner_name: Option<String>,
+    pub include_progress: bool,
+}
+
+/// Where a cloned roadmap was copied from, as its owner sees it. The original's id stays on
+/// the server, so holding a copy never leads back to the original.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoadmapOrigin {
+    pub skill_name: String,
+    /// Handed out by a team assignment rather than cloned by hand.
+    #[serde(default)]
+    pub from_assignment: bool,
+    /// `updated_at` of the original when it was copied.
+    pub source_updated_at: DateTime<Utc>,
+    pub cloned_at: DateTime<Utc>,
+}
+
+/// Where a cloned roadmap was copied from, kept so later changes to the original can be
+/// offered to the copy.
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoadmapOriginDB {
+    /// Only set when the copy's owner also owns the original. A copy made through a share
+    /// link reaches it through `share_token`, and an assigned copy through the assignment,
+    /// so revoking either also cuts the copy off.
+    #[serde(default)]
+    pub roadmap_id: Option<RecordId>,
+    #[serde(default)]
+    pub share_token: Option<String>,
+    pub skill_name: String,
+    #[serde(default)]
+    pub assignment_id: Option<RecordId>,
+    pub source_updated_at: DateTime<Utc>,
+    pub cloned_at: DateTime<Utc>,
+}
+
 #[cfg(feature = "server")]
-impl From<Roadmap> for RoadmapDB {
-    fn from(value: Roadmap) -> Self {
+impl From<RoadmapOriginDB> for RoadmapOrigin {
+    fn from(value: RoadmapOriginDB) -> Self {
         Self {
-            id: value.id.and_then(|s| s.parse().ok()),
-            skills_prev_known: value.skills_prev_known,
-            learning_outcomes: value.learning_outcomes,
-            user_id: value.user_id.parse().unwrap(),
             skill_name: value.skill_name,
-            nodes: value.nodes,
-            created_at: value.created_at,
-            updated_at: value.updated_at,
+            from_assignment: value.assignment_id.is_some(),
+            source_updated_at: value.source_updated_at,
+            cloned_at: value.cloned_at,
         }
     }
 }
 
+/// The roadmap to copy in `clone_roadmap`: one of the caller's own, or one reached through
+/// a share link.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CloneSource {
+    Owned { roadmap_id: String },
+    Shared { token: String },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RoadmapNode {
     #[serde(default)]
@@ -146,6 +294,12 @@ pub struct RoadmapNode {
     pub prerequisites: Vec<String>,
     #[serde(default)]
     pub is_completed: bool,
+    /// Best passing score on the node's mastery quiz, if it was completed that way.
+    #[serde(default)]
+    pub mastery_score: Option<u8>,
+    /// Study time for the whole step, including practice.
+    #[serde(default)]
+    pub estimated_hours: Option<f32>,
     pub prev_node_id: Option<String>,
     pub next_node_id: Option<String>,
 }
@@ -156,6 +310,261 @@ pub struct LearningResource {
     pub platform: String,
     pub url: Option<String>,
     pub resource_type: String,
+    /// Watch/read time, from course metadata when the resource is in the corpus.
+    #[serde(default)]
+    pub estimated_minutes: Option<u32>,
+}
+
+impl RoadmapNode {
+    /// Hours to budget for this node: the explicit estimate if there is one, otherwise the
+    /// resources' running time scaled for practice.
+    pub fn effort_hours(&self) -> f32 {
+        if let Some(hours) = self.estimated_hours.filter(|h| *h > 0.0) {
+            return hours;
+        }
+        let minutes = self
+            .resources
+            .iter()
+            .filter_map(|r| r.estimated_minutes)
+            .fold(0u32, u32::saturating_add);
+        if minutes == 0 {
+            DEFAULT_NODE_HOURS
+        } else {
+            minutes as f32 / 60.0 * PRACTICE_TIME_MULTIPLIER
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledNode {
+    pub node_id: String,
+    pub skill_name: String,
+    pub hours: f32,
+    pub starts_on: DateTime<Utc>,
+    pub ends_on: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PlanStatus {
+    Ahead { days: i64 },
+    OnTrack,
+    Behind { days: i64 },
+}
+
+impl PlanStatus {
+    pub fn label(self) -> String {
+        match self {
+            PlanStatus::Ahead { days } => format!("{days} days ahead"),
+            PlanStatus::OnTrack => "On track".to_string(),
+            PlanStatus::Behind { days } => format!("{days} days behind"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoadmapSchedule {
+    pub weekly_hours: f32,
+    pub total_hours: f32,
+    pub remaining_hours: f32,
+    /// Finish date if the user had kept to the weekly hours since the roadmap was created.
+    pub planned_finish: DateTime<Utc>,
+    /// Finish date if the user keeps to the weekly hours from now on.
+    pub projected_finish: DateTime<Utc>,
+    pub status: PlanStatus,
+    /// Dated plan for the nodes that are still open, in dependency order.
+    pub upcoming: Vec<ScheduledNode>,
+}
+
+impl Roadmap {
+    /// Nodes ordered so every node comes after its prerequisites, keeping the stored order
+    /// among nodes that are free to go next. Nodes caught in a cycle are appended in stored
+    /// order rather than dropped.
+    pub fn topological_nodes(&self) -> Vec<&RoadmapNode> {
+        use std::collections::HashSet;
+
+        let ids: HashSet<&str> = self.nodes.iter().map(|n| n.id.as_str()).collect();
+        let mut placed: HashSet<&str> = HashSet::new();
+        let mut ordered = Vec::with_capacity(self.nodes.len());
+
+        while ordered.len() < self.nodes.len() {
+            let next = self.nodes.iter().find(|n| {
+                !placed.contains(n.id.as_str())
+                    && n.prerequisites
+                        .iter()
+                        .filter(|p| ids.contains(p.as_str()))
+                        .all(|p| placed.contains(p.as_str()))
+            });
+            match next {
+                Some(node) => {
+                    placed.insert(node.id.as_str());
+                    ordered.push(node);
+                }
+                None => {
+                    ordered.extend(
+                        self.nodes
+                            .iter()
+                            .filter(|n| !placed.contains(n.id.as_str())),
+                    );
+                    break;
+                }
+            }
+        }
+        ordered
+    }
+
+    /// Lays the open nodes out back to back at `weekly_hours`, starting `now`, and compares
+    /// the hours completed so far against what the same pace would have covered since the
+    /// roadmap was created.
+    pub fn plan_schedule(&self, weekly_hours: f32, now: DateTime<Utc>) -> RoadmapSchedule {
+        let weekly_hours = if weekly_hours > 0.0 {
+            weekly_hours
+        } else {
+            DEFAULT_WEEKLY_HOURS
+        };
+        let hours_per_day = weekly_hours / 7.0;
+        let days_for = |hours: f32| {
+            let minutes = (hours / hours_per_day * 24.0 * 60.0).round() as i64;
+            chrono::Duration::minutes(minutes.clamp(0, PLAN_HORIZON_DAYS * 24 * 60))
+        };
+        let after = |start: DateTime<Utc>, hours: f32| {
+            start
+                .checked_add_signed(days_for(hours))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC)
+        };
+
+        let total_hours: f32 = self.nodes.iter().map(RoadmapNode::effort_hours).sum();
+        let done_hours: f32 = self
+            .nodes
+            .iter()
+            .filter(|n| n.is_completed)
+            .map(RoadmapNode::effort_hours)
+            .sum();
+
+        let mut cursor = now;
+        let upcoming = self
+            .topological_nodes()
+            .into_iter()
+            .filter(|n| !n.is_completed)
+            .map(|n| {
+                let hours = n.effort_hours();
+                let starts_on = cursor;
+                cursor = after(cursor, hours);
+                ScheduledNode {
+                    node_id: n.id.clone(),
+                    skill_name: n.skill_name.clone(),
+                    hours,
+                    starts_on,
+                    ends_on: cursor,
+                }
+            })
+            .collect();
+
+        let elapsed_days = (now - self.created_at).num_minutes().max(0) as f32 / (24.0 * 60.0);
+        let expected_hours = (elapsed_days * hours_per_day).min(total_hours);
+        let lead_days = ((done_hours - expected_hours) / hours_per_day).round() as i64;
+        let status = if lead_days > PLAN_SLACK_DAYS {
+            PlanStatus::Ahead { days: lead_days }
+        } else if lead_days < -PLAN_SLACK_DAYS {
+            PlanStatus::Behind {
+                days: lead_days.saturating_neg(),
+            }
+        } else {
+            PlanStatus::OnTrack
+        };
+
+        RoadmapSchedule {
+            weekly_hours,
+            total_hours,
+            remaining_hours: total_hours - done_hours,
+            planned_finish: after(self.created_at, total_hours),
+            projected_finish: cursor,
+            status,
+            upcoming,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Markdown,
+        ExportFormat::Json,
+        ExportFormat::Html,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "text/markdown",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Html => "text/html",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Html => "Printable HTML",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportedFile {
+    pub filename: String,
+    pub mime: String,
+    pub contents: String,
+}
+
+/// Versioned interchange format for a roadmap. Node references use `key`s local to the
+/// file, so an import can assign fresh IDs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoadmapExport {
+    pub format: String,
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub skill_name: String,
+    #[serde(default)]
+    pub learning_outcomes: Vec<String>,
+    #[serde(default)]
+    pub skills_prev_known: Vec<String>,
+    #[serde(default)]
+    pub require_mastery_quiz: bool,
+    pub nodes: Vec<ExportedNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportedNode {
+    pub key: String,
+    pub skill_name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    #[serde(default)]
+    pub prev: Option<String>,
+    #[serde(default)]
+    pub next: Option<String>,
+    #[serde(default)]
+    pub estimated_hours: Option<f32>,
+    #[serde(default)]
+    pub is_completed: bool,
+    #[serde(default)]
+    pub resources: Vec<LearningResource>,
 }
 
 #[cfg(feature = "server")]
@@ -172,8 +581,17 @@ pub struct CoursesDataWithEmbeddings {
     pub content: String,
     pub topic: String,
     pub prerequisite_topics: Vec<String>,
+    /// Left out of admin queries, which select the record without it.
+    #[serde(default)]
     pub embedding: Vec<f32>,
     pub url: String,
+    #[serde(default)]
+    pub duration_minutes: Option<u32>,
+    /// Hidden from search and generation without losing the record.
+    #[serde(default)]
+    pub deactivated: bool,
+    #[serde(default)]
+    pub feedback: CourseFeedbackSummary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -190,6 +608,30 @@ pub struct CoursesDataClean {
     pub topic: String,
     pub prerequisite_topics: Vec<String>,
     pub url: String,
+    #[serde(default)]
+    pub duration_minutes: Option<u32>,
+}
+
+/// The schema view of a stored course, for code shared with the pipeline such as the
+/// embedding text.
+#[cfg(feature = "server")]
+impl From<&CoursesDataWithEmbeddings> for Course {
+    fn from(value: &CoursesDataWithEmbeddings) -> Self {
+        Course {
+            title: value.title.clone(),
+            description: value.description.clone(),
+            channel_name: value.channel_name.clone(),
+            published_date: value.published_date.clone(),
+            skill_path: value.skill_path.clone(),
+            level: value.level.clone(),
+            ctype: value.ctype.clone(),
+            content: value.content.clone(),
+            topic: value.topic.clone(),
+            prerequisite_topics: value.prerequisite_topics.clone(),
+            url: Some(value.url.clone()).filter(|u| !u.is_empty()),
+            ..Course::default()
+        }
+    }
 }
 
 #[cfg(feature = "server")]
@@ -207,55 +649,936 @@ impl From<CoursesDataWithEmbeddings> for CoursesDataClean {
             topic: value.topic,
             prerequisite_topics: value.prerequisite_topics,
             url: value.url,
+            duration_minutes: value.duration_minutes,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)] // Added PartialEq
-pub struct Question {
-    pub id: String,
-    pub question_text: String,
-    pub question_type: QuestionType,
-    pub options: Vec<String>,
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ResourceFeedbackKind {
+    ThumbsUp,
+    ThumbsDown,
+    BrokenLink,
+    TooAdvanced,
+    TooBasic,
+}
+
+impl ResourceFeedbackKind {
+    pub const ALL: [ResourceFeedbackKind; 5] = [
+        ResourceFeedbackKind::ThumbsUp,
+        ResourceFeedbackKind::ThumbsDown,
+        ResourceFeedbackKind::BrokenLink,
+        ResourceFeedbackKind::TooAdvanced,
+        ResourceFeedbackKind::TooBasic,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResourceFeedbackKind::ThumbsUp => "👍",
+            ResourceFeedbackKind::ThumbsDown => "👎",
+            ResourceFeedbackKind::BrokenLink => "Broken link",
+            ResourceFeedbackKind::TooAdvanced => "Too advanced",
+            ResourceFeedbackKind::TooBasic => "Too basic",
+        }
+    }
+
+    /// The feedback a learner can't give at the same time as this one.
+    pub fn opposite(&self) -> Option<ResourceFeedbackKind> {
+        match self {
+            ResourceFeedbackKind::ThumbsUp => Some(ResourceFeedbackKind::ThumbsDown),
+            ResourceFeedbackKind::ThumbsDown => Some(ResourceFeedbackKind::ThumbsUp),
+            ResourceFeedbackKind::TooAdvanced => Some(ResourceFeedbackKind::TooBasic),
+            ResourceFeedbackKind::TooBasic => Some(ResourceFeedbackKind::TooAdvanced),
+            ResourceFeedbackKind::BrokenLink => None,
+        }
+    }
 }
 
+/// Feedback counts for a course, kept on the course record so retrieval can rank by them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CourseFeedbackSummary {
+    pub thumbs_up: u32,
+    pub thumbs_down: u32,
+    pub broken_link: u32,
+    pub too_advanced: u32,
+    pub too_basic: u32,
+}
+
+impl CourseFeedbackSummary {
+    pub fn count(&self, kind: ResourceFeedbackKind) -> u32 {
+        match kind {
+            ResourceFeedbackKind::ThumbsUp => self.thumbs_up,
+            ResourceFeedbackKind::ThumbsDown => self.thumbs_down,
+            ResourceFeedbackKind::BrokenLink => self.broken_link,
+            ResourceFeedbackKind::TooAdvanced => self.too_advanced,
+            ResourceFeedbackKind::TooBasic => self.too_basic,
+        }
+    }
+
+    pub fn add(&mut self, kind: ResourceFeedbackKind) {
+        match kind {
+            ResourceFeedbackKind::ThumbsUp => self.thumbs_up += 1,
+            ResourceFeedbackKind::ThumbsDown => self.thumbs_down += 1,
+            ResourceFeedbackKind::BrokenLink => self.broken_link += 1,
+            ResourceFeedbackKind::TooAdvanced => self.too_advanced += 1,
+            ResourceFeedbackKind::TooBasic => self.too_basic += 1,
+        }
+    }
+
+    /// Net opinion of the course from -1.0 to 1.0. Broken links weigh double and level
+    /// mismatches half, and the prior keeps a handful of votes from swinging it far.
+    pub fn quality_score(&self) -> f32 {
+        let level_mismatch = (self.too_advanced + self.too_basic) as f32;
+        let net = self.thumbs_up as f32
+            - self.thumbs_down as f32
+            - 2.0 * self.broken_link as f32
+            - 0.5 * level_mismatch;
+        let votes = (self.thumbs_up + self.thumbs_down + self.broken_link) as f32 + level_mismatch;
+        (net / (votes + FEEDBACK_PRIOR_VOTES)).clamp(-1.0, 1.0)
+    }
+}
+
+/// Feedback on one resource of a roadmap step, by position in the step's resource list.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum QuestionType {
-    Mcq,
-    Msq,
-    TrueFalse,
-    OneWord,
+pub struct ResourceFeedback {
+    pub resource_index: usize,
+    /// Feedback is kept on course records, so resources outside the catalog can't take any.
+    pub in_catalog: bool,
+    pub mine: Vec<ResourceFeedbackKind>,
+    pub summary: CourseFeedbackSummary,
 }
 
+#[cfg(feature = "server")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QuestionResponse {
-    pub question_id: String,
-    pub answer: Vec<String>,
+pub struct ResourceFeedbackDB {
+    pub id: Option<RecordId>,
+    pub user_id: RecordId,
+    pub course_id: RecordId,
+    pub kind: ResourceFeedbackKind,
+    pub created_at: DateTime<Utc>,
+}
+
+/// How the course catalog matches a search query.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum CourseSearchMode {
+    /// Nearest courses by embedding, so related wording still matches.
+    #[default]
+    Semantic,
+    /// Courses whose text contains the query.
+    Keyword,
+}
+
+impl CourseSearchMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CourseSearchMode::Semantic => "Semantic",
+            CourseSearchMode::Keyword => "Keyword",
+        }
+    }
+}
+
+/// Splits a skill path such as `Cloud Computing > Google Cloud` into its segments. The
+/// corpus uses both `>` and `->` as separators.
+pub fn skill_path_segments(path: &str) -> Vec<String> {
+    path.replace("->", ">")
+        .split('>')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct JsonData {
+/// A course as listed in the public catalog.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogCourse {
+    pub id: String,
     pub title: String,
     pub description: String,
     pub channel_name: String,
-    pub published_date: String,
     pub skill_path: String,
     pub level: String,
-    #[serde(rename = "type")]
     pub ctype: String,
-    pub content: String,
     pub topic: String,
-    pub prerequisite_topics: Vec<String>,
+    pub url: String,
+    pub duration_minutes: Option<u32>,
+}
+
+#[cfg(feature = "server")]
+impl From<CoursesDataWithEmbeddings> for CatalogCourse {
+    fn from(value: CoursesDataWithEmbeddings) -> Self {
+        Self {
+            id: value.id.map(|r| r.to_string()).unwrap_or_default(),
+            title: value.title,
+            description: value.description,
+            channel_name: value.channel_name,
+            skill_path: value.skill_path,
+            level: value.level,
+            ctype: value.ctype,
+            topic: value.topic,
+            url: value.url,
+            duration_minutes: value.duration_minutes,
+        }
+    }
+}
+
+impl From<CatalogCourse> for LearningResource {
+    fn from(value: CatalogCourse) -> Self {
+        Self {
+            title: value.title,
+            platform: crate::utils::platform_for_url(&value.url),
+            url: (!value.url.is_empty()).then_some(value.url),
+            resource_type: value.ctype,
+            estimated_minutes: value.duration_minutes,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct JsonDataCollection {
-    pub data: Vec<JsonData>,
+/// Facet values picked in the catalog; `None` leaves that facet open.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CourseFilters {
+    pub level: Option<String>,
+    pub ctype: Option<String>,
+    pub channel_name: Option<String>,
+    /// Matches any segment of the course's skill path.
+    pub skill_path: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct UserSkills {
-    pub skillname: String,
-    // pub skill_level: u8,
-    pub date_learnt: DateTime<Utc>,
+impl CourseFilters {
+    pub fn is_empty(&self) -> bool {
+        self.level.is_none()
+            && self.ctype.is_none()
+            && self.channel_name.is_none()
+            && self.skill_path.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Values available for each catalog facet, with how many matching courses carry them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CourseFacets {
+    pub levels: Vec<FacetCount>,
+    pub ctypes: Vec<FacetCount>,
+    pub channels: Vec<FacetCount>,
+    pub skill_paths: Vec<FacetCount>,
+}
+
+/// One level of the skill-path hierarchy, e.g. `Google Cloud` under `Cloud Computing`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkillPathNode {
+    pub name: String,
+    /// Segment names from the root down to and including this one.
+    pub path: Vec<String>,
+    /// Courses filed at or below this level.
+    pub course_count: usize,
+    pub children: Vec<SkillPathNode>,
+}
+
+/// A snake_case corpus topic and the topics its courses build on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopicNode {
+    pub topic: String,
+    /// Courses on this topic. Zero for topics only ever named as a prerequisite.
+    pub course_count: usize,
+    pub prerequisites: Vec<String>,
+}
+
+/// Skill paths and topic prerequisites derived from the course corpus. Rebuilt on ingestion
+/// and whenever an admin changes a course.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Taxonomy {
+    pub skill_paths: Vec<SkillPathNode>,
+    /// Sorted by topic name.
+    pub topics: Vec<TopicNode>,
+    pub built_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopicDetail {
+    pub topic: TopicNode,
+    pub prerequisites: Vec<TopicNode>,
+    /// Topics that list this one as a prerequisite.
+    pub dependents: Vec<TopicNode>,
+}
+
+/// A roadmap step that should come after another step according to the taxonomy, but
+/// doesn't depend on it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrerequisiteIssue {
+    pub node_id: String,
+    pub skill_name: String,
+    pub requires_node_id: String,
+    pub requires_skill_name: String,
+    pub topic: String,
+    pub prerequisite_topic: String,
+}
+
+/// A skill the learner looks ready to start, with the signals behind the suggestion.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkillRecommendation {
+    pub topic: String,
+    /// Readable name, used to pre-fill roadmap creation.
+    pub skill_name: String,
+    pub score: f32,
+    /// Share of the topic's prerequisites the learner already has, 0.0-1.0.
+    pub readiness: f32,
+    /// How close the topic's courses are to the learner's skills, 0.0-1.0.
+    pub similarity: f32,
+    /// Team assignments in the learner's organizations that cover the topic.
+    pub team_demand: usize,
+    /// Already on one of the learner's unfinished roadmaps.
+    pub in_progress: bool,
+    pub known_prerequisites: Vec<String>,
+}
+
+/// One page of catalog results. Each facet is counted over everything the query and the
+/// other facets' picks match, so picking a value doesn't hide that facet's other options.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CourseSearchResults {
+    pub courses: Vec<CatalogCourse>,
+    pub facets: CourseFacets,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)] // Added PartialEq
+pub struct Question {
+    pub id: String,
+    pub question_text: String,
+    pub question_type: QuestionType,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum QuestionType {
+    Mcq,
+    Msq,
+    TrueFalse,
+    OneWord,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionResponse {
+    pub question_id: String,
+    pub answer: Vec<String>,
+}
+
+/// A mastery quiz as sent to the client. Answer keys never leave the server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NodeQuiz {
+    pub quiz_id: String,
+    pub node_id: String,
+    pub questions: Vec<Question>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuizResult {
+    pub score: u8,
+    pub correct: usize,
+    pub total: usize,
+    pub passed: bool,
+    pub incorrect_question_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizAnswerKey {
+    pub question_id: String,
+    pub question_type: QuestionType,
+    pub correct_answers: Vec<String>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeQuizDB {
+    pub id: Option<RecordId>,
+    pub user_id: RecordId,
+    pub roadmap_id: RecordId,
+    pub node_id: String,
+    pub questions: Vec<Question>,
+    pub answer_keys: Vec<QuizAnswerKey>,
+    pub created_at: DateTime<Utc>,
+    /// Set by the first submission. A quiz is graded only once, so resubmitting it can't be
+    /// used to work out the answer key.
+    #[serde(default)]
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "server")]
+impl From<NodeQuizDB> for NodeQuiz {
+    fn from(value: NodeQuizDB) -> Self {
+        NodeQuiz {
+            quiz_id: value.id.map(|r| r.to_string()).unwrap_or_default(),
+            node_id: value.node_id,
+            questions: value.questions,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizAttempt {
+    pub id: Option<RecordId>,
+    pub quiz_id: RecordId,
+    pub user_id: RecordId,
+    pub roadmap_id: RecordId,
+    pub node_id: String,
+    pub score: u8,
+    pub passed: bool,
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// SM-2 scheduling state for one completed node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewSchedule {
+    pub easiness: f64,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    pub due_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_reviewed: Option<DateTime<Utc>>,
+}
+
+impl ReviewSchedule {
+    /// A fresh schedule whose first review falls one interval after the node was completed.
+    pub fn starting_from(completed_at: DateTime<Utc>) -> Self {
+        ReviewSchedule {
+            easiness: REVIEW_INITIAL_EASINESS,
+            interval_days: REVIEW_FIRST_INTERVAL_DAYS,
+            repetitions: 0,
+            due_at: completed_at + chrono::Duration::days(REVIEW_FIRST_INTERVAL_DAYS as i64),
+            last_reviewed: None,
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due_at <= now
+    }
+
+    /// Maps a quiz score onto SM-2's 0-5 recall quality; anything under 60% is a lapse.
+    pub fn quality_from_score(score: u8) -> u8 {
+        match score {
+            95.. => 5,
+            80..=94 => 4,
+            60..=79 => 3,
+            40..=59 => 2,
+            20..=39 => 1,
+            _ => 0,
+        }
+    }
+
+    /// Applies one graded review. A lapse restarts the repetition count; otherwise the
+    /// interval grows by the easiness factor, which itself drifts with recall quality.
+    pub fn record_review(&mut self, quality: u8, now: DateTime<Utc>) {
+        let quality = quality.min(5);
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval_days = REVIEW_FIRST_INTERVAL_DAYS;
+        } else {
+            self.interval_days = match self.repetitions {
+                0 => REVIEW_FIRST_INTERVAL_DAYS,
+                1 => REVIEW_SECOND_INTERVAL_DAYS,
+                _ => (self.interval_days as f64 * self.easiness).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+
+        let q = quality as f64;
+        self.easiness =
+            (self.easiness + 0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02)).max(REVIEW_MIN_EASINESS);
+        self.last_reviewed = Some(now);
+        self.due_at = now + chrono::Duration::days(self.interval_days as i64);
+    }
+}
+
+/// A review card as shown in the dashboard queue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewItem {
+    pub review_id: String,
+    pub roadmap_id: String,
+    pub roadmap_title: String,
+    pub node_id: String,
+    pub skill_name: String,
+    pub schedule: ReviewSchedule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewOutcome {
+    pub result: QuizResult,
+    pub next_due: DateTime<Utc>,
+    pub interval_days: u32,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCardDB {
+    pub id: Option<RecordId>,
+    pub user_id: RecordId,
+    pub roadmap_id: RecordId,
+    pub node_id: String,
+    pub skill_name: String,
+    pub schedule: ReviewSchedule,
+    /// Quiz used at the last review, so the next one gets fresh questions.
+    #[serde(default)]
+    pub last_quiz_id: Option<RecordId>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserSkills {
+    pub skillname: String,
+    /// Proficiency on a 0-100 scale, as of `last_practiced`. Use `current_level` for the decayed value.
+    #[serde(default = "default_skill_level")]
+    pub skill_level: u8,
+    #[serde(default)]
+    pub level_source: ProficiencySource,
+    /// When the skill was learnt on SkillForge; `None` for knowledge the user already had,
+    /// which doesn't count towards their activity.
+    pub date_learnt: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_practiced: Option<DateTime<Utc>>,
+}
+
+fn default_skill_level() -> u8 {
+    50
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ProficiencySource {
+    #[default]
+    SelfAssessment,
+    NodeCompletion,
+    Quiz,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProficiencyLevel {
+    Novice,
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+}
+
+impl ProficiencyLevel {
+    pub const ALL: [ProficiencyLevel; 5] = [
+        ProficiencyLevel::Novice,
+        ProficiencyLevel::Beginner,
+        ProficiencyLevel::Intermediate,
+        ProficiencyLevel::Advanced,
+        ProficiencyLevel::Expert,
+    ];
+
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            0..=19 => ProficiencyLevel::Novice,
+            20..=39 => ProficiencyLevel::Beginner,
+            40..=59 => ProficiencyLevel::Intermediate,
+            60..=79 => ProficiencyLevel::Advanced,
+            _ => ProficiencyLevel::Expert,
+        }
+    }
+
+    /// Level stored when a user picks this band in a self-assessment.
+    pub fn representative_level(self) -> u8 {
+        match self {
+            ProficiencyLevel::Novice => 10,
+            ProficiencyLevel::Beginner => 30,
+            ProficiencyLevel::Intermediate => 50,
+            ProficiencyLevel::Advanced => 70,
+            ProficiencyLevel::Expert => 90,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProficiencyLevel::Novice => "Novice",
+            ProficiencyLevel::Beginner => "Beginner",
+            ProficiencyLevel::Intermediate => "Intermediate",
+            ProficiencyLevel::Advanced => "Advanced",
+            ProficiencyLevel::Expert => "Expert",
+        }
+    }
+}
+
+impl UserSkills {
+    pub fn new(skillname: &str, level: u8, source: ProficiencySource, now: DateTime<Utc>) -> Self {
+        UserSkills {
+            skillname: skillname.trim().to_string(),
+            skill_level: level.min(100),
+            level_source: source,
+            date_learnt: Some(now),
+            last_practiced: Some(now),
+        }
+    }
+
+    /// A skill the user had before they started, e.g. as assessed by a roadmap's onboarding
+    /// quiz, so it has no learnt date.
+    pub fn prior_knowledge(
+        skillname: &str,
+        level: u8,
+        source: ProficiencySource,
+        now: DateTime<Utc>,
+    ) -> Self {
+        UserSkills {
+            date_learnt: None,
+            ..UserSkills::new(skillname, level, source, now)
+        }
+    }
+
+    /// Whether this is the skill called `name`, ignoring case and surrounding whitespace.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.skillname.trim().eq_ignore_ascii_case(name.trim())
+    }
+
+    pub fn last_practiced_at(&self) -> DateTime<Utc> {
+        self.last_practiced
+            .or(self.date_learnt)
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+
+    pub fn days_since_practice(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.last_practiced_at()).num_days().max(0)
+    }
+
+    /// Recorded level after exponential decay since the skill was last practiced.
+    /// Nothing decays during the grace period, and a skill never drops below a
+    /// fixed fraction of what was recorded.
+    pub fn current_level(&self, now: DateTime<Utc>) -> u8 {
+        let idle_days = self.days_since_practice(now) - SKILL_DECAY_GRACE_DAYS;
+        if idle_days <= 0 {
+            return self.skill_level;
+        }
+        let recorded = self.skill_level as f64;
+        let decayed = recorded * 0.5_f64.powf(idle_days as f64 / SKILL_DECAY_HALF_LIFE_DAYS);
+        decayed.max(recorded * SKILL_DECAY_FLOOR_RATIO).round() as u8
+    }
+
+    pub fn proficiency(&self, now: DateTime<Utc>) -> ProficiencyLevel {
+        ProficiencyLevel::from_level(self.current_level(now))
+    }
+
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        (self.current_level(now) as f64) < self.skill_level as f64 * STALE_SKILL_RATIO
+    }
+
+    /// Folds a new piece of evidence into the recorded level and resets the decay clock.
+    /// Quizzes and self-assessments replace the level; completing a node can only raise it.
+    pub fn record_evidence(&mut self, level: u8, source: ProficiencySource, now: DateTime<Utc>) {
+        let level = level.min(100);
+        self.skill_level = match source {
+            ProficiencySource::NodeCompletion => self.current_level(now).max(level),
+            ProficiencySource::Quiz | ProficiencySource::SelfAssessment => level,
+        };
+        self.level_source = source;
+        self.last_practiced = Some(now);
+    }
+
+    /// One-line description used when handing the user's skills to the LLM.
+    pub fn prompt_summary(&self, now: DateTime<Utc>) -> String {
+        let level = self.current_level(now);
+        format!(
+            "{}: {} ({}/100, last practiced {} days ago{})",
+            self.skillname,
+            ProficiencyLevel::from_level(level).label(),
+            level,
+            self.days_since_practice(now),
+            if self.is_stale(now) {
+                ", getting rusty"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// A member's standing in an organization, ordered by authority.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OrgRole {
+    Member,
+    Manager,
+    Owner,
+}
+
+impl OrgRole {
+    pub const ALL: [OrgRole; 3] = [OrgRole::Owner, OrgRole::Manager, OrgRole::Member];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OrgRole::Member => "Member",
+            OrgRole::Manager => "Manager",
+            OrgRole::Owner => "Owner",
+        }
+    }
+
+    /// Managers and owners run teams: they add members, create teams and assign roadmaps.
+    pub fn can_manage(self) -> bool {
+        self >= OrgRole::Manager
+    }
+}
+
+/// An organization as seen by one of its members.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Organization {
+    pub id: String,
+    pub name: String,
+    pub my_role: OrgRole,
+    pub members: Vec<OrgMember>,
+    pub teams: Vec<Team>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgMember {
+    pub user_id: String,
+    pub username: String,
+    pub name: String,
+    pub role: OrgRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Team {
+    pub id: String,
+    pub name: String,
+    pub member_ids: Vec<String>,
+    pub assignments: Vec<TeamAssignment>,
+}
+
+/// A template roadmap handed to every member of a team. Each member works through their
+/// own copy, so progress is never shared.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamAssignment {
+    pub id: String,
+    /// Only sent to the template's owner; other members never learn where it lives.
+    pub template_roadmap_id: Option<String>,
+    pub skill_name: String,
+    pub assigned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamProgress {
+    pub team_id: String,
+    pub team_name: String,
+    pub assignments: Vec<AssignmentProgress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AssignmentProgress {
+    pub assignment_id: String,
+    pub skill_name: String,
+    pub members: Vec<MemberProgress>,
+}
+
+impl AssignmentProgress {
+    /// Mean completion across members, counting anyone without a copy as 0%.
+    pub fn average_percent(&self) -> u8 {
+        if self.members.is_empty() {
+            return 0;
+        }
+        let total: u32 = self.members.iter().map(|m| m.percent() as u32).sum();
+        (total / self.members.len() as u32) as u8
+    }
+
+    pub fn finished_members(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|m| m.total_steps > 0 && m.completed_steps == m.total_steps)
+            .count()
+    }
+}
+
+/// One member's progress on their copy of an assigned roadmap. `roadmap_id` is `None` if
+/// they deleted their copy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberProgress {
+    pub user_id: String,
+    pub name: String,
+    /// Whether the member still has their copy of the assigned roadmap.
+    pub has_copy: bool,
+    pub completed_steps: usize,
+    pub total_steps: usize,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+impl MemberProgress {
+    pub fn percent(&self) -> u8 {
+        if self.total_steps == 0 {
+            return 0;
+        }
+        (self.completed_steps * 100 / self.total_steps) as u8
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationDB {
+    pub id: Option<RecordId>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgMembershipDB {
+    pub id: Option<RecordId>,
+    pub org_id: RecordId,
+    pub user_id: RecordId,
+    pub role: OrgRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamDB {
+    pub id: Option<RecordId>,
+    pub org_id: RecordId,
+    pub name: String,
+    #[serde(default)]
+    pub member_ids: Vec<RecordId>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamAssignmentDB {
+    pub id: Option<RecordId>,
+    pub team_id: RecordId,
+    pub template_roadmap_id: RecordId,
+    pub skill_name: String,
+    pub assigned_by: RecordId,
+    pub assigned_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl TeamAssignment {
+    /// The assignment as `viewer` may see it. Templates are the assigner's own roadmaps.
+    pub fn for_viewer(value: TeamAssignmentDB, viewer: &RecordId) -> Self {
+        Self {
+            id: value.id.map(|r| r.to_string()).unwrap_or_default(),
+            template_roadmap_id: (&value.assigned_by == viewer)
+                .then(|| value.template_roadmap_id.to_string()),
+            skill_name: value.skill_name,
+            assigned_at: value.assigned_at,
+        }
+    }
+}
+
+/// Editable view of a corpus course for the admin console.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdminCourse {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub channel_name: String,
+    pub skill_path: String,
+    pub level: String,
+    pub ctype: String,
+    pub topic: String,
+    pub url: String,
+    pub duration_minutes: Option<u32>,
+    pub deactivated: bool,
+}
+
+#[cfg(feature = "server")]
+impl From<CoursesDataWithEmbeddings> for AdminCourse {
+    fn from(value: CoursesDataWithEmbeddings) -> Self {
+        Self {
+            id: value.id.map(|r| r.to_string()).unwrap_or_default(),
+            title: value.title,
+            description: value.description,
+            channel_name: value.channel_name,
+            skill_path: value.skill_path,
+            level: value.level,
+            ctype: value.ctype,
+            topic: value.topic,
+            url: value.url,
+            duration_minutes: value.duration_minutes,
+            deactivated: value.deactivated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdminUser {
+    pub id: String,
+    pub username: String,
+    pub name: String,
+    pub role: UserRole,
+    pub locked: bool,
+    pub roadmap_count: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A roadmap, question or quiz generation that failed, kept for the admin console.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GenerationFailure {
+    pub id: String,
+    pub user_id: String,
+    pub job: String,
+    pub subject: String,
+    pub error: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationFailureDB {
+    pub id: Option<RecordId>,
+    pub user_id: Option<RecordId>,
+    pub job: String,
+    pub subject: String,
+    pub error: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<GenerationFailureDB> for GenerationFailure {
+    fn from(value: GenerationFailureDB) -> Self {
+        Self {
+            id: value.id.map(|r| r.to_string()).unwrap_or_default(),
+            user_id: value.user_id.map(|r| r.to_string()).unwrap_or_default(),
+            job: value.job,
+            subject: value.subject,
+            error: value.error,
+            occurred_at: value.occurred_at,
+        }
+    }
+}
+
+/// One admin action, as recorded in the audit log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub id: String,
+    pub admin_username: String,
+    pub action: String,
+    pub target: String,
+    pub details: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntryDB {
+    pub id: Option<RecordId>,
+    pub admin_id: RecordId,
+    pub admin_username: String,
+    pub action: String,
+    pub target: String,
+    pub details: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<AuditEntryDB> for AuditEntry {
+    fn from(value: AuditEntryDB) -> Self {
+        Self {
+            id: value.id.map(|r| r.to_string()).unwrap_or_default(),
+            admin_username: value.admin_username,
+            action: value.action,
+            target: value.target,
+            details: value.details,
+            created_at: value.created_at,
+        }
+    }
 }
@@ -0,0 +1,724 @@
+use crate::{
+    Route,
+    models::{AdminCourse, AdminUser, UserRole},
+    server_functions::{
+        admin_audit_log, admin_delete_user, admin_generation_failures, admin_list_users,
+        admin_merge_courses, admin_search_courses, admin_set_course_active, admin_set_user_locked,
+        admin_set_user_role, admin_update_course, get_user_data,
+    },
+    utils::get_session_token,
+};
+use dioxus::prelude::*;
+
+const INPUT_CLASS: &str = "w-full px-3 py-2 bg-[#050505] text-gray-100 text-sm border border-white/10 rounded-lg focus:ring-2 focus:ring-teal-500/30 focus:border-transparent outline-none transition placeholder:text-gray-600";
+const ROW_BUTTON_CLASS: &str = "px-3 py-1.5 rounded-md text-xs font-medium border border-white/10 bg-[#1a1b1e] text-gray-300 hover:text-white hover:border-teal-500/30 transition disabled:opacity-50";
+
+#[derive(Clone, PartialEq)]
+enum AdminTab {
+    Courses,
+    Users,
+    Failures,
+    AuditLog,
+}
+
+#[component]
+pub fn Admin() -> Element {
+    let mut active_tab = use_signal(|| AdminTab::Courses);
+    let nav = navigator();
+    let token = get_session_token();
+
+    if token.is_none() {
+        nav.push(Route::Login {});
+        return rsx! { "Redirecting..." };
+    }
+
+    let session_token = token.unwrap();
+    let session_token_for_user = session_token.clone();
+    let user = use_resource(move || {
+        let session_token = session_token_for_user.clone();
+        async move { get_user_data(session_token).await }
+    });
+
+    rsx! {
+        div { class: "min-h-screen bg-[#050505] text-gray-100 font-sans selection:bg-teal-500/30 selection:text-teal-200",
+            nav { class: "bg-[#050505]/80 backdrop-blur-md border-b border-white/5",
+                div { class: "container mx-auto px-6 py-4 flex justify-between items-center",
+                    Link {
+                        to: Route::Dashboard {},
+                        class: "text-teal-400 hover:text-teal-300 font-medium",
+                        "← Back to Dashboard"
+                    }
+                    h1 { class: "text-xl font-bold text-gray-100", "Admin Console" }
+                }
+            }
+
+            main { class: "container mx-auto px-6 py-8 max-w-6xl",
+                match user.read_unchecked().as_ref() {
+                    Some(Ok(u)) if u.role == UserRole::Admin => rsx! {
+                        div { class: "bg-[#0f1012]/60 rounded-2xl overflow-hidden backdrop-blur-md border border-white/5",
+                            div { class: "border-b border-white/10 flex",
+                                TabButton {
+                                    active: active_tab() == AdminTab::Courses,
+                                    onclick: move |_| active_tab.set(AdminTab::Courses),
+                                    label: "Courses",
+                                }
+                                TabButton {
+                                    active: active_tab() == AdminTab::Users,
+                                    onclick: move |_| active_tab.set(AdminTab::Users),
+                                    label: "Users",
+                                }
+                                TabButton {
+                                    active: active_tab() == AdminTab::Failures,
+                                    onclick: move |_| active_tab.set(AdminTab::Failures),
+                                    label: "Generation Failures",
+                                }
+                                TabButton {
+                                    active: active_tab() == AdminTab::AuditLog,
+                                    onclick: move |_| active_tab.set(AdminTab::AuditLog),
+                                    label: "Audit Log",
+                                }
+                            }
+                            div { class: "p-8",
+                                match active_tab() {
+                                    AdminTab::Courses => rsx! {
+                                        CoursesTab { session_token: session_token.clone() }
+                                    },
+                                    AdminTab::Users => rsx! {
+                                        UsersTab { session_token: session_token.clone() }
+                                    },
+                                    AdminTab::Failures => rsx! {
+                                        FailuresTab { session_token: session_token.clone() }
+                                    },
+                                    AdminTab::AuditLog => rsx! {
+                                        AuditLogTab { session_token: session_token.clone() }
+                                    },
+                                }
+                            }
+                        }
+                    },
+                    Some(Ok(_)) => rsx! {
+                        div { class: "bg-red-500/10 text-red-300 p-6 rounded-lg border border-red-500/20",
+                            "This page is only available to admins."
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "bg-red-500/10 text-red-300 p-6 rounded-lg border border-red-500/20",
+                            "Error loading your account: {e}"
+                        }
+                    },
+                    None => rsx! {
+                        div { class: "text-center text-gray-500", "Loading..." }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TabButton(active: bool, onclick: EventHandler<()>, label: &'static str) -> Element {
+    rsx! {
+        button {
+            onclick: move |_| onclick.call(()),
+            class: format!(
+                "px-6 py-4 font-medium transition border-b-2 {}",
+                if active {
+                    "border-teal-400 text-teal-400"
+                } else {
+                    "border-transparent text-gray-400 hover:text-gray-100"
+                },
+            ),
+            {label}
+        }
+    }
+}
+
+#[component]
+fn CoursesTab(session_token: String) -> Element {
+    let mut query = use_signal(String::new);
+    let mut missing_url_only = use_signal(|| false);
+    let mut editing = use_signal(|| None::<AdminCourse>);
+    let mut merge_keep = use_signal(|| None::<AdminCourse>);
+    let mut merge_duplicate = use_signal(|| None::<AdminCourse>);
+    let mut message = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    let token_for_search = session_token.clone();
+    let mut courses = use_resource(move || {
+        let session_token = token_for_search.clone();
+        let query = query();
+        let missing_url_only = missing_url_only();
+        async move { admin_search_courses(session_token, query, missing_url_only).await }
+    });
+
+    let token_for_merge = session_token.clone();
+    let missing_class = if missing_url_only() {
+        "bg-teal-500/20 border-teal-500/40 text-teal-300"
+    } else {
+        "bg-[#1a1b1e] border-white/10 text-gray-400 hover:text-gray-200"
+    };
+
+    rsx! {
+        div { class: "space-y-6",
+            div { class: "flex gap-3",
+                input {
+                    r#type: "text",
+                    class: INPUT_CLASS,
+                    placeholder: "Search by title, topic or channel",
+                    value: "{query}",
+                    oninput: move |e| query.set(e.value()),
+                }
+                button {
+                    class: "px-4 py-2 rounded-lg border text-xs font-medium whitespace-nowrap transition {missing_class}",
+                    onclick: move |_| missing_url_only.set(!missing_url_only()),
+                    "Missing URL only"
+                }
+            }
+
+            if let Some(msg) = message() {
+                div { class: "p-3 bg-green-500/10 text-green-300 rounded-lg text-sm border border-green-500/20",
+                    {msg}
+                }
+            }
+            if let Some(err) = error() {
+                div { class: "p-3 bg-red-500/10 text-red-300 rounded-lg text-sm border border-red-500/20",
+                    {err}
+                }
+            }
+
+            if merge_keep().is_some() || merge_duplicate().is_some() {
+                div { class: "p-4 bg-white/5 border border-white/10 rounded-lg flex items-center justify-between gap-4",
+                    div { class: "text-sm text-gray-300",
+                        "Merge "
+                        span { class: "font-semibold text-white",
+                            {merge_duplicate().map(|c| c.title).unwrap_or_else(|| "…".to_string())}
+                        }
+                        " into "
+                        span { class: "font-semibold text-white",
+                            {merge_keep().map(|c| c.title).unwrap_or_else(|| "…".to_string())}
+                        }
+                    }
+                    div { class: "flex gap-2",
+                        button {
+                            class: ROW_BUTTON_CLASS,
+                            disabled: merge_keep().is_none() || merge_duplicate().is_none(),
+                            onclick: move |_| {
+                                let session_token = token_for_merge.clone();
+                                let (Some(keep), Some(duplicate)) = (merge_keep(), merge_duplicate()) else {
+                                    return;
+                                };
+                                spawn(async move {
+                                    match admin_merge_courses(session_token, keep.id, duplicate.id).await {
+                                        Ok(updated) => {
+                                            message.set(Some(format!("Merged; {updated} roadmaps updated.")));
+                                            error.set(None);
+                                            merge_keep.set(None);
+                                            merge_duplicate.set(None);
+                                            courses.restart();
+                                        }
+                                        Err(e) => error.set(Some(format!("Merge failed: {e}"))),
+                                    }
+                                });
+                            },
+                            "Merge"
+                        }
+                        button {
+                            class: ROW_BUTTON_CLASS,
+                            onclick: move |_| {
+                                merge_keep.set(None);
+                                merge_duplicate.set(None);
+                            },
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+
+            if let Some(course) = editing() {
+                CourseEditor {
+                    key: "{course.id}",
+                    course,
+                    session_token: session_token.clone(),
+                    on_done: move |msg: Option<String>| {
+                        editing.set(None);
+                        if let Some(msg) = msg {
+                            message.set(Some(msg));
+                            error.set(None);
+                            courses.restart();
+                        }
+                    },
+                }
+            }
+
+            match courses.read_unchecked().as_ref() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    p { class: "text-sm text-gray-500", "No courses match." }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-2",
+                        for course in list.iter().cloned() {
+                            {
+                                let session_token = session_token.clone();
+                                let for_edit = course.clone();
+                                let for_keep = course.clone();
+                                let for_duplicate = course.clone();
+                                let course_id = course.id.clone();
+                                let deactivated = course.deactivated;
+                                let url = if course.url.is_empty() {
+                                    "no URL".to_string()
+                                } else {
+                                    course.url.clone()
+                                };
+                                rsx! {
+                                    div {
+                                        key: "{course.id}",
+                                        class: format!(
+                                            "p-3 border rounded-lg flex items-center justify-between gap-4 {}",
+                                            if deactivated {
+                                                "bg-white/[0.02] border-white/5 opacity-60"
+                                            } else {
+                                                "bg-white/5 border-white/10"
+                                            },
+                                        ),
+                                        div { class: "min-w-0",
+                                            div { class: "text-sm text-gray-200 font-medium truncate", "{course.title}" }
+                                            div { class: "text-xs text-gray-500 truncate",
+                                                "{course.channel_name} · {course.topic} · {url}"
+                                            }
+                                        }
+                                        div { class: "flex gap-2 shrink-0",
+                                            button {
+                                                class: ROW_BUTTON_CLASS,
+                                                onclick: move |_| editing.set(Some(for_edit.clone())),
+                                                "Edit"
+                                            }
+                                            button {
+                                                class: ROW_BUTTON_CLASS,
+                                                onclick: move |_| {
+                                                    let session_token = session_token.clone();
+                                                    let course_id = course_id.clone();
+                                                    spawn(async move {
+                                                        match admin_set_course_active(session_token, course_id, deactivated)
+                                                            .await
+                                                        {
+                                                            Ok(()) => courses.restart(),
+                                                            Err(e) => error.set(Some(format!("Update failed: {e}"))),
+                                                        }
+                                                    });
+                                                },
+                                                if deactivated {
+                                                    "Reactivate"
+                                                } else {
+                                                    "Deactivate"
+                                                }
+                                            }
+                                            button {
+                                                class: ROW_BUTTON_CLASS,
+                                                title: "Keep this course when merging",
+                                                onclick: move |_| merge_keep.set(Some(for_keep.clone())),
+                                                "Keep"
+                                            }
+                                            button {
+                                                class: ROW_BUTTON_CLASS,
+                                                title: "Merge this course into the one kept",
+                                                onclick: move |_| merge_duplicate.set(Some(for_duplicate.clone())),
+                                                "Duplicate"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-sm text-red-400", "Couldn't load courses: {e}" }
+                },
+                None => rsx! {
+                    p { class: "text-sm text-gray-500", "Loading..." }
+                },
+            }
+        }
+    }
+}
+
+/// Edit form for one course. `on_done` gets a confirmation message after a save, or `None`
+/// when the edit is cancelled.
+#[component]
+fn CourseEditor(
+    course: AdminCourse,
+    session_token: String,
+    on_done: EventHandler<Option<String>>,
+) -> Element {
+    let mut draft = use_signal(|| course.clone());
+    let mut duration = use_signal(|| {
+        course
+            .duration_minutes
+            .map(|m| m.to_string())
+            .unwrap_or_default()
+    });
+    let mut saving = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let save = move |_: MouseEvent| {
+        let session_token = session_token.clone();
+        let mut course = draft();
+        course.duration_minutes = duration().trim().parse().ok();
+        spawn(async move {
+            saving.set(true);
+            match admin_update_course(session_token, course).await {
+                Ok(()) => on_done.call(Some("Course saved.".to_string())),
+                Err(e) => error.set(Some(format!("Save failed: {e}"))),
+            }
+            saving.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "p-6 bg-[#0b0c0e] border border-teal-500/20 rounded-xl space-y-4",
+            h3 { class: "text-lg font-semibold text-gray-100", "Edit course" }
+            if let Some(err) = error() {
+                p { class: "text-sm text-red-400", "{err}" }
+            }
+            div { class: "grid grid-cols-1 md:grid-cols-2 gap-4",
+                label { class: "block text-xs text-gray-400 space-y-1 md:col-span-2",
+                    span { "Title" }
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{draft().title}",
+                        oninput: move |e| draft.write().title = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1 md:col-span-2",
+                    span { "URL" }
+                    input {
+                        class: INPUT_CLASS,
+                        placeholder: "https://",
+                        value: "{draft().url}",
+                        oninput: move |e| draft.write().url = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1 md:col-span-2",
+                    span { "Description" }
+                    textarea {
+                        class: "{INPUT_CLASS} h-24",
+                        value: "{draft().description}",
+                        oninput: move |e| draft.write().description = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1",
+                    span { "Channel" }
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{draft().channel_name}",
+                        oninput: move |e| draft.write().channel_name = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1",
+                    span { "Topic" }
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{draft().topic}",
+                        oninput: move |e| draft.write().topic = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1",
+                    span { "Skill path" }
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{draft().skill_path}",
+                        oninput: move |e| draft.write().skill_path = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1",
+                    span { "Level" }
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{draft().level}",
+                        oninput: move |e| draft.write().level = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1",
+                    span { "Type" }
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{draft().ctype}",
+                        oninput: move |e| draft.write().ctype = e.value(),
+                    }
+                }
+                label { class: "block text-xs text-gray-400 space-y-1",
+                    span { "Duration (minutes)" }
+                    input {
+                        class: INPUT_CLASS,
+                        r#type: "number",
+                        value: "{duration}",
+                        oninput: move |e| duration.set(e.value()),
+                    }
+                }
+            }
+            div { class: "flex gap-3",
+                button {
+                    class: "px-5 py-2 bg-gradient-to-r from-teal-500 to-blue-600 text-white rounded-lg text-sm font-medium transition disabled:opacity-50",
+                    disabled: saving(),
+                    onclick: save,
+                    if saving() {
+                        "Saving..."
+                    } else {
+                        "Save"
+                    }
+                }
+                button {
+                    class: ROW_BUTTON_CLASS,
+                    onclick: move |_| on_done.call(None),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn UsersTab(session_token: String) -> Element {
+    let mut query = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    // Deleting takes a second click on the same row.
+    let mut confirm_delete = use_signal(|| None::<String>);
+
+    let token_for_list = session_token.clone();
+    let mut users = use_resource(move || {
+        let session_token = token_for_list.clone();
+        let query = query();
+        async move { admin_list_users(session_token, query).await }
+    });
+
+    rsx! {
+        div { class: "space-y-6",
+            input {
+                r#type: "text",
+                class: INPUT_CLASS,
+                placeholder: "Search by username or name",
+                value: "{query}",
+                oninput: move |e| query.set(e.value()),
+            }
+            if let Some(err) = error() {
+                div { class: "p-3 bg-red-500/10 text-red-300 rounded-lg text-sm border border-red-500/20",
+                    {err}
+                }
+            }
+            match users.read_unchecked().as_ref() {
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-2",
+                        for user in list.iter().cloned() {
+                            UserRow {
+                                key: "{user.id}",
+                                user: user.clone(),
+                                session_token: session_token.clone(),
+                                confirming_delete: confirm_delete() == Some(user.id.clone()),
+                                on_confirm_delete: move |id: String| confirm_delete.set(Some(id)),
+                                on_change: move |result: Result<(), String>| {
+                                    confirm_delete.set(None);
+                                    match result {
+                                        Ok(()) => {
+                                            error.set(None);
+                                            users.restart();
+                                        }
+                                        Err(e) => error.set(Some(e)),
+                                    }
+                                },
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-sm text-red-400", "Couldn't load users: {e}" }
+                },
+                None => rsx! {
+                    p { class: "text-sm text-gray-500", "Loading..." }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn UserRow(
+    user: AdminUser,
+    session_token: String,
+    confirming_delete: bool,
+    on_confirm_delete: EventHandler<String>,
+    on_change: EventHandler<Result<(), String>>,
+) -> Element {
+    let token_for_role = session_token.clone();
+    let token_for_lock = session_token.clone();
+    let token_for_delete = session_token;
+    let id_for_role = user.id.clone();
+    let id_for_lock = user.id.clone();
+    let id_for_delete = user.id.clone();
+    let next_role = match user.role {
+        UserRole::Admin => UserRole::Learner,
+        UserRole::Learner => UserRole::Admin,
+    };
+    let role_label = match next_role {
+        UserRole::Admin => "Make admin",
+        UserRole::Learner => "Remove admin",
+    };
+    let locked = user.locked;
+    let joined = user.created_at.format("%b %-d, %Y").to_string();
+
+    rsx! {
+        div { class: "p-3 bg-white/5 border border-white/10 rounded-lg flex items-center justify-between gap-4",
+            div { class: "min-w-0",
+                div { class: "text-sm text-gray-200 font-medium",
+                    "{user.name}"
+                    span { class: "ml-2 text-xs text-gray-500", "@{user.username}" }
+                    if user.role == UserRole::Admin {
+                        span { class: "ml-2 px-2 py-0.5 rounded text-[10px] font-semibold uppercase bg-teal-500/10 text-teal-300",
+                            "Admin"
+                        }
+                    }
+                    if locked {
+                        span { class: "ml-2 px-2 py-0.5 rounded text-[10px] font-semibold uppercase bg-red-500/10 text-red-400",
+                            "Locked"
+                        }
+                    }
+                }
+                div { class: "text-xs text-gray-500",
+                    "Joined {joined} · {user.roadmap_count} roadmaps"
+                }
+            }
+            div { class: "flex gap-2 shrink-0",
+                button {
+                    class: ROW_BUTTON_CLASS,
+                    onclick: move |_| {
+                        let session_token = token_for_role.clone();
+                        let user_id = id_for_role.clone();
+                        spawn(async move {
+                            let result = admin_set_user_role(session_token, user_id, next_role).await;
+                            on_change.call(result.map_err(|e| format!("Couldn't change the role: {e}")));
+                        });
+                    },
+                    "{role_label}"
+                }
+                button {
+                    class: ROW_BUTTON_CLASS,
+                    onclick: move |_| {
+                        let session_token = token_for_lock.clone();
+                        let user_id = id_for_lock.clone();
+                        spawn(async move {
+                            let result = admin_set_user_locked(session_token, user_id, !locked).await;
+                            on_change.call(result.map_err(|e| format!("Couldn't update the account: {e}")));
+                        });
+                    },
+                    if locked {
+                        "Unlock"
+                    } else {
+                        "Lock"
+                    }
+                }
+                button {
+                    class: "px-3 py-1.5 rounded-md text-xs font-medium text-red-400 bg-red-500/10 hover:bg-red-500/20 transition",
+                    onclick: move |_| {
+                        let user_id = id_for_delete.clone();
+                        if !confirming_delete {
+                            on_confirm_delete.call(user_id);
+                            return;
+                        }
+                        let session_token = token_for_delete.clone();
+                        spawn(async move {
+                            let result = admin_delete_user(session_token, user_id).await;
+                            on_change.call(result.map_err(|e| format!("Couldn't delete the user: {e}")));
+                        });
+                    },
+                    if confirming_delete {
+                        "Confirm delete"
+                    } else {
+                        "Delete"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FailuresTab(session_token: String) -> Element {
+    let failures = use_resource(move || {
+        let session_token = session_token.clone();
+        async move { admin_generation_failures(session_token).await }
+    });
+
+    match failures.read_unchecked().as_ref() {
+        Some(Ok(list)) if list.is_empty() => rsx! {
+            p { class: "text-sm text-gray-500", "No generation failures recorded." }
+        },
+        Some(Ok(list)) => rsx! {
+            div { class: "space-y-2",
+                for failure in list.iter() {
+                    {
+                        let when = failure.occurred_at.format("%b %-d, %Y %H:%M").to_string();
+                        rsx! {
+                            div {
+                                key: "{failure.id}",
+                                class: "p-3 bg-white/5 border border-white/10 rounded-lg",
+                                div { class: "flex items-center justify-between text-xs text-gray-500 mb-1",
+                                    span {
+                                        span { class: "uppercase font-semibold text-red-400 mr-2", "{failure.job}" }
+                                        "{failure.subject}"
+                                    }
+                                    span { "{when} · {failure.user_id}" }
+                                }
+                                p { class: "text-sm text-gray-300 font-mono break-words", "{failure.error}" }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { class: "text-sm text-red-400", "Couldn't load failures: {e}" }
+        },
+        None => rsx! {
+            p { class: "text-sm text-gray-500", "Loading..." }
+        },
+    }
+}
+
+#[component]
+fn AuditLogTab(session_token: String) -> Element {
+    let entries = use_resource(move || {
+        let session_token = session_token.clone();
+        async move { admin_audit_log(session_token).await }
+    });
+
+    match entries.read_unchecked().as_ref() {
+        Some(Ok(list)) if list.is_empty() => rsx! {
+            p { class: "text-sm text-gray-500", "No admin actions recorded yet." }
+        },
+        Some(Ok(list)) => rsx! {
+            div { class: "divide-y divide-white/5",
+                for entry in list.iter() {
+                    {
+                        let when = entry.created_at.format("%b %-d, %Y %H:%M").to_string();
+                        rsx! {
+                            div { key: "{entry.id}", class: "py-3 grid grid-cols-[10rem_8rem_1fr] gap-4 text-sm",
+                                span { class: "text-gray-500 text-xs", "{when}" }
+                                span { class: "text-gray-300", "@{entry.admin_username}" }
+                                div {
+                                    span { class: "font-mono text-teal-300 mr-2", "{entry.action}" }
+                                    span { class: "text-gray-500 text-xs mr-2", "{entry.target}" }
+                                    span { class: "text-gray-400", "{entry.details}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { class: "text-sm text-red-400", "Couldn't load the audit log: {e}" }
+        },
+        None => rsx! {
+            p { class: "text-sm text-gray-500", "Loading..." }
+        },
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{
+    Route,
+    models::{CatalogCourse, CourseFilters, CourseSearchMode, FacetCount, Roadmap},
+    server_functions::{add_course_to_node, get_user_roadmaps, search_courses},
+    utils::get_session_token,
+};
+use dioxus::prelude::*;
+
+const INPUT_CLASS: &str = "w-full px-4 py-2.5 bg-[#050505] text-gray-100 text-sm border border-white/10 rounded-lg focus:ring-2 focus:ring-teal-500/30 focus:border-transparent outline-none transition placeholder:text-gray-600";
+const SELECT_CLASS: &str = "flex-1 px-3 py-2 bg-[#050505] text-gray-300 text-xs border border-white/10 rounded-lg outline-none";
+
+#[component]
+pub fn Courses() -> Element {
+    // Browsing is open to everyone; adding a course to a roadmap needs a session.
+    let session_token = get_session_token();
+    let session_token_for_roadmaps = session_token.clone();
+
+    let mut query = use_signal(String::new);
+    let mut mode = use_signal(CourseSearchMode::default);
+    let mut filters = use_signal(CourseFilters::default);
+
+    let results = use_resource(move || {
+        let query = query();
+        let mode = mode();
+        let filters = filters();
+        async move { search_courses(query, mode, filters).await }
+    });
+    let roadmaps = use_resource(move || {
+        let session_token = session_token_for_roadmaps.clone();
+        async move {
+            match session_token {
+                Some(token) => get_user_roadmaps(token).await,
+                None => Ok(Vec::new()),
+            }
+        }
+    });
+    let my_roadmaps = match roadmaps.read_unchecked().as_ref() {
+        Some(Ok(r)) => r.clone(),
+        _ => Vec::new(),
+    };
+
+    let back_route = if session_token.is_some() {
+        Route::Dashboard {}
+    } else {
+        Route::Landing {}
+    };
+
+    rsx! {
+        div { class: "min-h-screen bg-[#050505] text-gray-100 font-sans selection:bg-teal-500/30 selection:text-teal-200",
+            nav { class: "bg-[#050505]/80 backdrop-blur-md border-b border-white/5",
+                div { class: "container mx-auto px-6 py-4 flex justify-between items-center",
+                    Link {
+                        to: back_route,
+                        class: "text-teal-400 hover:text-teal-300 font-medium",
+                        "← Back"
+                    }
+                    h1 { class: "text-xl font-bold text-gray-100", "Course Catalog" }
+                }
+            }
+
+            main { class: "container mx-auto px-6 py-8 max-w-6xl space-y-6",
+                div { class: "flex gap-3",
+                    input {
+                        r#type: "text",
+                        class: INPUT_CLASS,
+                        placeholder: "Search courses, e.g. deploying web apps on Google Cloud",
+                        value: "{query}",
+                        oninput: move |e| query.set(e.value()),
+                    }
+                    div { class: "flex rounded-lg border border-white/10 overflow-hidden shrink-0",
+                        for choice in [CourseSearchMode::Semantic, CourseSearchMode::Keyword] {
+                            button {
+                                class: if mode() == choice { "px-4 py-2 text-xs font-medium bg-teal-500/20 text-teal-300" } else { "px-4 py-2 text-xs font-medium bg-[#1a1b1e] text-gray-400 hover:text-gray-200" },
+                                onclick: move |_| mode.set(choice),
+                                "{choice.label()}"
+                            }
+                        }
+                    }
+                }
+
+                match results.read_unchecked().as_ref() {
+                    Some(Ok(found)) => rsx! {
+                        div { class: "grid grid-cols-1 lg:grid-cols-[16rem_1fr] gap-6",
+                            aside { class: "space-y-6",
+                                if !filters().is_empty() {
+                                    button {
+                                        class: "text-xs text-teal-400 hover:text-teal-300",
+                                        onclick: move |_| filters.set(CourseFilters::default()),
+                                        "Clear filters"
+                                    }
+                                }
+                                FacetGroup {
+                                    title: "Level",
+                                    options: found.facets.levels.clone(),
+                                    selected: filters().level,
+                                    on_select: move |value| filters.write().level = value,
+                                }
+                                FacetGroup {
+                                    title: "Type",
+                                    options: found.facets.ctypes.clone(),
+                                    selected: filters().ctype,
+                                    on_select: move |value| filters.write().ctype = value,
+                                }
+                                FacetGroup {
+                                    title: "Channel",
+                                    options: found.facets.channels.clone(),
+                                    selected: filters().channel_name,
+                                    on_select: move |value| filters.write().channel_name = value,
+                                }
+                                FacetGroup {
+                                    title: "Skill path",
+                                    options: found.facets.skill_paths.clone(),
+                                    selected: filters().skill_path,
+                                    on_select: move |value| filters.write().skill_path = value,
+                                }
+                            }
+                            div { class: "space-y-3",
+                                if found.courses.is_empty() {
+                                    p { class: "text-sm text-gray-500", "No courses match." }
+                                }
+                                for course in found.courses.iter().cloned() {
+                                    CourseCard {
+                                        key: "{course.id}",
+                                        course,
+                                        session_token: session_token.clone(),
+                                        roadmaps: my_roadmaps.clone(),
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "bg-red-500/10 text-red-300 p-6 rounded-lg border border-red-500/20",
+                            "Search failed: {e}"
+                        }
+                    },
+                    None => rsx! {
+                        div { class: "text-center text-gray-500", "Searching..." }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// One facet's values as toggle buttons. Picking the selected value again clears it.
+#[component]
+fn FacetGroup(
+    title: &'static str,
+    options: Vec<FacetCount>,
+    selected: Option<String>,
+    on_select: EventHandler<Option<String>>,
+) -> Element {
+    if options.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            h3 { class: "text-xs font-semibold uppercase tracking-wider text-gray-500 mb-2",
+                "{title}"
+            }
+            div { class: "flex flex-wrap gap-1.5",
+                for facet in options {
+                    {
+                        let is_selected = selected
+                            .as_deref()
+                            .is_some_and(|s| s.eq_ignore_ascii_case(&facet.value));
+                        let value = facet.value.clone();
+                        rsx! {
+                            button {
+                                key: "{facet.value}",
+                                class: if is_selected { "px-2.5 py-1 rounded-md text-xs border bg-teal-500/20 border-teal-500/40 text-teal-300" } else { "px-2.5 py-1 rounded-md text-xs border bg-[#1a1b1e] border-white/10 text-gray-400 hover:text-gray-200" },
+                                onclick: move |_| {
+                                    on_select.call(if is_selected { None } else { Some(value.clone()) })
+                                },
+                                "{facet.value} "
+                                span { class: "text-gray-600", "{facet.count}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn CourseCard(
+    course: CatalogCourse,
+    session_token: Option<String>,
+    roadmaps: Vec<Roadmap>,
+) -> Element {
+    let mut picking = use_signal(|| false);
+    let duration = course
+        .duration_minutes
+        .map(|m| format!(" · {m} min"))
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "p-5 bg-[#0f1012]/60 rounded-xl border border-white/5 space-y-3",
+            div { class: "flex items-start justify-between gap-4",
+                div { class: "min-w-0",
+                    if course.url.is_empty() {
+                        h3 { class: "text-base font-semibold text-gray-100", "{course.title}" }
+                    } else {
+                        a {
+                            href: "{course.url}",
+                            target: "_blank",
+                            class: "text-base font-semibold text-gray-100 hover:text-teal-300 transition",
+                            "{course.title}"
+                        }
+                    }
+                    div { class: "text-xs text-gray-500 mt-1",
+                        "{course.channel_name} · {course.level} · {course.ctype}{duration}"
+                    }
+                }
+                if session_token.is_some() {
+                    button {
+                        class: "shrink-0 px-3 py-1.5 rounded-md text-xs font-medium border border-white/10 bg-[#1a1b1e] text-gray-300 hover:text-white hover:border-teal-500/30 transition",
+                        onclick: move |_| picking.set(!picking()),
+                        if picking() {
+                            "Close"
+                        } else {
+                            "Add to roadmap"
+                        }
+                    }
+                }
+            }
+            p { class: "text-sm text-gray-400 line-clamp-3", "{course.description}" }
+            if !course.skill_path.is_empty() {
+                p { class: "text-xs text-gray-600", "{course.skill_path}" }
+            }
+            if let (true, Some(token)) = (picking(), session_token.clone()) {
+                AddToRoadmap {
+                    course_id: course.id.clone(),
+                    session_token: token,
+                    roadmaps,
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AddToRoadmap(course_id: String, session_token: String, roadmaps: Vec<Roadmap>) -> Element {
+    let mut roadmap_id = use_signal(String::new);
+    let mut node_id = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    if roadmaps.is_empty() {
+        return rsx! {
+            p { class: "text-xs text-gray-500",
+                "You don't have any roadmaps yet. "
+                Link {
+                    to: Route::CreateRoadmap {
+                        skill: String::new(),
+                    },
+                    class: "text-teal-400 hover:text-teal-300",
+                    "Create one"
+                }
+                " to add courses to it."
+            }
+        };
+    }
+
+    let nodes = roadmaps
+        .iter()
+        .find(|r| r.id.as_deref() == Some(roadmap_id().as_str()))
+        .map(|r| r.nodes.clone())
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "pt-3 border-t border-white/5 space-y-2",
+            div { class: "flex gap-2",
+                select {
+                    class: SELECT_CLASS,
+                    onchange: move |e| {
+                        roadmap_id.set(e.value());
+                        node_id.set(String::new());
+                        status.set(None);
+                    },
+                    option { value: "", "Choose a roadmap..." }
+                    for roadmap in roadmaps.iter() {
+                        option {
+                            value: "{roadmap.id.clone().unwrap_or_default()}",
+                            "{roadmap.skill_name}"
+                        }
+                    }
+                }
+                select {
+                    class: SELECT_CLASS,
+                    disabled: nodes.is_empty(),
+                    onchange: move |e| node_id.set(e.value()),
+                    option { value: "", "Choose a step..." }
+                    for node in nodes.iter() {
+                        option { value: "{node.id}", selected: node.id == node_id(), "{node.skill_name}" }
+                    }
+                }
+                button {
+                    class: "px-4 py-2 rounded-lg text-xs font-medium bg-gradient-to-r from-teal-500 to-blue-600 text-white transition disabled:opacity-50",
+                    disabled: roadmap_id().is_empty() || node_id().is_empty(),
+                    onclick: move |_| {
+                        let session_token = session_token.clone();
+                        let course_id = course_id.clone();
+                        let roadmap_id = roadmap_id();
+                        let node_id = node_id();
+                        spawn(async move {
+                            let result = add_course_to_node(session_token, roadmap_id, node_id, course_id).await;
+                            status.set(Some(match result {
+                                Ok(()) => Ok("Added to your roadmap.".to_string()),
+                                Err(e) => Err(format!("Couldn't add the course: {e}")),
+                            }));
+                        });
+                    },
+                    "Add"
+                }
+            }
+            match status() {
+                Some(Ok(msg)) => rsx! {
+                    p { class: "text-xs text-green-400", "{msg}" }
+                },
+                Some(Err(msg)) => rsx! {
+                    p { class: "text-xs text-red-400", "{msg}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
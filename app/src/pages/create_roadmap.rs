@@ -1,8 +1,8 @@
 use crate::utils::get_session_token;
 use crate::{
-    models::{Question, QuestionResponse, QuestionType},
-    server_functions::{generate_questions, generate_roadmap},
     Route,
+    models::{Question, QuestionResponse, QuestionType},
+    server_functions::{generate_questions, generate_roadmap, import_roadmap},
 };
 use dioxus::prelude::*;
 
@@ -14,10 +14,11 @@ enum FlowStep {
     Complete(String),
 }
 
+/// `skill` pre-fills the skill name, e.g. when starting from a recommendation.
 #[component]
-pub fn CreateRoadmap() -> Element {
+pub fn CreateRoadmap(skill: String) -> Element {
     let mut step = use_signal(|| FlowStep::SkillInput);
-    let skill_name = use_signal(String::new);
+    let skill_name = use_signal(|| skill.clone());
     let mut questions = use_signal(Vec::<Question>::new);
     let mut current_question_idx = use_signal(|| 0);
     let mut responses = use_signal(Vec::<QuestionResponse>::new);
@@ -33,6 +34,7 @@ pub fn CreateRoadmap() -> Element {
 
     let session_token_for_roadmap = token.unwrap();
     let session_token_for_questions = session_token_for_roadmap.clone();
+    let session_token_for_import = session_token_for_roadmap.clone();
 
     let load_questions = move |_| {
         let skill = skill_name();
@@ -125,6 +127,10 @@ pub fn CreateRoadmap() -> Element {
                 match step() {
                     FlowStep::SkillInput => rsx! {
                         SkillInputStep { skill_name, error, on_continue: load_questions }
+                        ImportRoadmapCard {
+                            session_token: session_token_for_import.clone(),
+                            on_imported: move |roadmap_id| step.set(FlowStep::Complete(roadmap_id)),
+                        }
                     },
                     FlowStep::Questions => rsx! {
                         QuestionStep {
@@ -150,6 +156,71 @@ pub fn CreateRoadmap() -> Element {
     }
 }
 
+#[component]
+fn ImportRoadmapCard(session_token: String, on_imported: EventHandler<String>) -> Element {
+    let mut expanded = use_signal(|| false);
+    let mut contents = use_signal(String::new);
+    let mut is_importing = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "mt-6 bg-[#0f1012]/60 rounded-2xl p-6 border border-white/5 backdrop-blur-md",
+            button {
+                class: "w-full flex items-center justify-between text-left text-gray-300 hover:text-white transition",
+                onclick: move |_| expanded.set(!expanded()),
+                span { class: "font-semibold", "Already have a curriculum?" }
+                span { class: "text-sm text-gray-500",
+                    if expanded() {
+                        "Hide"
+                    } else {
+                        "Import Markdown or JSON"
+                    }
+                }
+            }
+
+            if expanded() {
+                p { class: "mt-4 mb-3 text-sm text-gray-400",
+                    "Paste a SkillForge JSON export, or a Markdown outline: each heading becomes a step and the bullets under it become its resources."
+                }
+                if let Some(err) = error() {
+                    div { class: "mb-3 p-3 bg-red-500/10 text-red-300 rounded-lg border border-red-500/20 text-sm",
+                        {err}
+                    }
+                }
+                textarea {
+                    disabled: is_importing(),
+                    rows: "10",
+                    class: "w-full px-4 py-3 bg-[#050505] text-gray-100 text-sm font-mono border border-white/10 rounded-xl focus:ring-2 focus:ring-teal-500/30 focus:border-transparent outline-none",
+                    placeholder: "# Backend Onboarding\n\n## Git basics\n- [Pro Git](https://git-scm.com/book)\n\n## Our CI pipeline\n- Internal wiki: https://...",
+                    value: "{contents}",
+                    oninput: move |e| contents.set(e.value()),
+                }
+                button {
+                    disabled: is_importing() || contents().trim().is_empty(),
+                    class: "mt-3 w-full py-3 bg-white/5 border border-white/10 text-gray-200 rounded-xl hover:bg-white/10 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                    onclick: move |_| {
+                        let session_token = session_token.clone();
+                        spawn(async move {
+                            is_importing.set(true);
+                            error.set(None);
+                            match import_roadmap(session_token, contents()).await {
+                                Ok(roadmap_id) => on_imported.call(roadmap_id),
+                                Err(e) => error.set(Some(format!("Import failed: {e}"))),
+                            }
+                            is_importing.set(false);
+                        });
+                    },
+                    if is_importing() {
+                        "Importing..."
+                    } else {
+                        "Import Roadmap"
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn SkillInputStep(
     skill_name: Signal<String>,
@@ -1,8 +1,15 @@
-use crate::utils::{clear_session_token, get_session_token};
+use crate::utils::{clear_session_token, format_hours, get_session_token, topic_label};
 use crate::{
-    models::Roadmap,
-    server_functions::{delete_roadmap, delete_session, get_progress_report, get_user_roadmaps},
     Route,
+    models::{
+        NodeQuiz, PlanStatus, ProficiencyLevel, QuestionResponse, ReviewItem, ReviewOutcome,
+        Roadmap, SkillRecommendation, UserRole, UserSkills,
+    },
+    pages::quiz::QuizPanel,
+    server_functions::{
+        delete_roadmap, delete_session, get_due_reviews, get_progress_report, get_recommendations,
+        get_stale_skills, get_user_data, get_user_roadmaps, start_review, submit_review,
+    },
 };
 use chrono::{DateTime, Duration, Utc};
 use dioxus::prelude::*;
@@ -21,6 +28,11 @@ pub fn Dashboard() -> Element {
     let session_token = token.unwrap();
     let session_token_clone = session_token.clone();
     let session_token_for_progress = session_token.clone();
+    let session_token_for_stale = session_token.clone();
+    let session_token_for_reviews = session_token.clone();
+    let session_token_for_review_panel = session_token.clone();
+    let session_token_for_user = session_token.clone();
+    let session_token_for_recommendations = session_token.clone();
 
     // Default graph duration
     let report_days = use_signal(|| 7u16);
@@ -37,6 +49,34 @@ pub fn Dashboard() -> Element {
         async move { get_progress_report(days, session_token).await }
     });
 
+    let stale_skills = use_resource(move || {
+        let session_token = session_token_for_stale.clone();
+        async move { get_stale_skills(session_token).await }
+    });
+
+    let user = use_resource(move || {
+        let session_token = session_token_for_user.clone();
+        async move { get_user_data(session_token).await }
+    });
+    let weekly_hours = match user.read_unchecked().as_ref() {
+        Some(Ok(u)) => Some(u.preferences.weekly_hours()),
+        _ => None,
+    };
+    let is_admin = matches!(
+        user.read_unchecked().as_ref(),
+        Some(Ok(u)) if u.role == UserRole::Admin
+    );
+
+    let recommendations = use_resource(move || {
+        let session_token = session_token_for_recommendations.clone();
+        async move { get_recommendations(session_token).await }
+    });
+
+    let due_reviews = use_resource(move || {
+        let session_token = session_token_for_reviews.clone();
+        async move { get_due_reviews(session_token).await }
+    });
+
     rsx! {
         div { class: "min-h-screen bg-[#050505] text-gray-100 font-sans selection:bg-teal-500/30 selection:text-teal-200",
             // Navigation
@@ -49,6 +89,23 @@ pub fn Dashboard() -> Element {
                         span { class: "text-gray-100", "Forge" }
                     }
                     div { class: "flex items-center gap-6",
+                        if is_admin {
+                            Link {
+                                to: Route::Admin {},
+                                class: "text-gray-400 hover:text-white transition-colors text-sm font-medium",
+                                "Admin"
+                            }
+                        }
+                        Link {
+                            to: Route::Courses {},
+                            class: "text-gray-400 hover:text-white transition-colors text-sm font-medium",
+                            "Courses"
+                        }
+                        Link {
+                            to: Route::Organizations {},
+                            class: "text-gray-400 hover:text-white transition-colors text-sm font-medium",
+                            "Organizations"
+                        }
                         Link {
                             to: Route::Profile {},
                             class: "text-gray-400 hover:text-white transition-colors text-sm font-medium",
@@ -104,10 +161,34 @@ pub fn Dashboard() -> Element {
                     }
                 }
 
+                if let Some(Ok(reviews)) = due_reviews.read_unchecked().as_ref() {
+                    if !reviews.is_empty() {
+                        ReviewQueuePanel {
+                            reviews: reviews.clone(),
+                            session_token: session_token_for_review_panel.clone(),
+                            reviews_resource: due_reviews,
+                        }
+                    }
+                }
+
+                if let Some(Ok(skills)) = stale_skills.read_unchecked().as_ref() {
+                    if !skills.is_empty() {
+                        StaleSkillsPanel { skills: skills.clone() }
+                    }
+                }
+
+                if let Some(Ok(suggestions)) = recommendations.read_unchecked().as_ref() {
+                    if !suggestions.is_empty() {
+                        RecommendationsPanel { recommendations: suggestions.clone() }
+                    }
+                }
+
                 div { class: "flex justify-between items-center mb-10",
                     h2 { class: "text-3xl font-bold text-gray-100", "My Roadmaps" }
                     Link {
-                        to: Route::CreateRoadmap {},
+                        to: Route::CreateRoadmap {
+                            skill: String::new(),
+                        },
                         class: "px-5 py-2.5 bg-gradient-to-r from-teal-500 to-blue-600 text-white rounded-lg hover:shadow-[0_0_20px_rgba(20,184,166,0.3)] hover:-translate-y-0.5 transition-all duration-300 font-medium text-sm flex items-center gap-2",
                         span { class: "text-lg leading-none", "+" }
                         "New Roadmap"
@@ -118,7 +199,11 @@ pub fn Dashboard() -> Element {
                     Some(Ok(roadmaps_data)) => rsx! {
                         div { class: "grid md:grid-cols-2 lg:grid-cols-3 gap-6 animate-slide-up",
                             for roadmap in roadmaps_data {
-                                RoadmapCard { roadmap: roadmap.clone(), roadmaps_resource: roadmaps }
+                                RoadmapCard {
+                                    roadmap: roadmap.clone(),
+                                    roadmaps_resource: roadmaps,
+                                    weekly_hours,
+                                }
                             }
                         }
                     },
@@ -206,11 +291,260 @@ fn ActivityChart(data: HashMap<DateTime<Utc>, u8>, days: u16) -> Element {
     }
 }
 
+#[component]
+fn StaleSkillsPanel(skills: Vec<UserSkills>) -> Element {
+    let now = Utc::now();
+
+    rsx! {
+        div { class: "mb-12",
+            div { class: "flex items-baseline justify-between mb-4",
+                h2 { class: "text-xl font-bold text-gray-100", "Getting Rusty" }
+                span { class: "text-xs text-gray-500",
+                    "Skills you haven't practiced in a while"
+                }
+            }
+            div { class: "flex flex-wrap gap-3",
+                for skill in skills {
+                    {
+                        let level = skill.current_level(now);
+                        let label = ProficiencyLevel::from_level(level).label();
+                        let idle_days = skill.days_since_practice(now);
+                        rsx! {
+                            div {
+                                key: "{skill.skillname}",
+                                class: "px-4 py-3 bg-amber-500/5 border border-amber-500/20 rounded-lg",
+                                div { class: "text-sm font-medium text-amber-200", "{skill.skillname}" }
+                                div { class: "text-xs text-gray-500 mt-1",
+                                    "{label} · {level}/100 (was {skill.skill_level}) · {idle_days} days idle"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RecommendationsPanel(recommendations: Vec<SkillRecommendation>) -> Element {
+    rsx! {
+        div { class: "mb-12",
+            div { class: "flex items-baseline justify-between mb-4",
+                h2 { class: "text-xl font-bold text-gray-100", "Learn Next" }
+                span { class: "text-xs text-gray-500", "Skills you're ready to start" }
+            }
+            div { class: "grid md:grid-cols-2 lg:grid-cols-3 gap-4",
+                for recommendation in recommendations {
+                    {
+                        let mut reasons = Vec::new();
+                        if !recommendation.known_prerequisites.is_empty() {
+                            reasons.push(format!(
+                                "Builds on {}",
+                                recommendation
+                                    .known_prerequisites
+                                    .iter()
+                                    .map(|p| topic_label(p))
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            ));
+                        } else if recommendation.readiness < 1.0 {
+                            reasons.push(format!(
+                                "{:.0}% of prerequisites covered",
+                                recommendation.readiness * 100.0,
+                            ));
+                        }
+                        if recommendation.similarity > 0.0 {
+                            reasons.push(format!(
+                                "{:.0}% match with your skills",
+                                recommendation.similarity * 100.0,
+                            ));
+                        }
+                        if recommendation.team_demand > 0 {
+                            reasons.push(match recommendation.team_demand {
+                                1 => "Assigned by your team".to_string(),
+                                n => format!("Assigned in {n} team roadmaps"),
+                            });
+                        }
+                        if recommendation.in_progress {
+                            reasons.push("On one of your roadmaps".to_string());
+                        }
+                        let reasons = reasons.join(" · ");
+                        rsx! {
+                            div {
+                                key: "{recommendation.topic}",
+                                class: "p-4 bg-[#0f1012]/60 border border-white/5 rounded-xl flex flex-col justify-between gap-3",
+                                div {
+                                    div { class: "text-sm font-semibold text-gray-100", "{recommendation.skill_name}" }
+                                    div { class: "text-xs text-gray-500 mt-1", "{reasons}" }
+                                }
+                                Link {
+                                    to: Route::CreateRoadmap {
+                                        skill: recommendation.skill_name.clone(),
+                                    },
+                                    class: "self-start px-3 py-1.5 rounded-md text-xs font-medium bg-teal-500/10 text-teal-300 border border-teal-500/20 hover:bg-teal-500/20 transition",
+                                    "Create roadmap"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ReviewQueuePanel(
+    reviews: Vec<ReviewItem>,
+    session_token: String,
+    reviews_resource: Resource<Result<Vec<ReviewItem>, ServerFnError>>,
+) -> Element {
+    // (review_id, quiz) for the review currently being taken
+    let mut active = use_signal(|| None::<(String, NodeQuiz)>);
+    let mut outcome = use_signal(|| None::<ReviewOutcome>);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let now = Utc::now();
+    let active_skill = active()
+        .and_then(|(id, _)| reviews.iter().find(|r| r.review_id == id))
+        .map(|r| r.skill_name.clone())
+        .unwrap_or_default();
+    let next_due_label = outcome().map(|o| {
+        format!(
+            "Next review in {} day(s), on {}.",
+            o.interval_days,
+            o.next_due.format("%b %-d")
+        )
+    });
+
+    let submit = {
+        let session_token = session_token.clone();
+        move |responses: Vec<QuestionResponse>| {
+            let session_token = session_token.clone();
+            let Some((review_id, quiz)) = active() else {
+                return;
+            };
+            spawn(async move {
+                busy.set(true);
+                match submit_review(session_token, review_id, quiz.quiz_id, responses).await {
+                    Ok(o) => outcome.set(Some(o)),
+                    Err(e) => error.set(Some(format!("Couldn't grade the review: {e}"))),
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "mb-12",
+            div { class: "flex items-baseline justify-between mb-4",
+                h2 { class: "text-xl font-bold text-gray-100", "Due for Review" }
+                span { class: "text-xs text-gray-500",
+                    "Quick recall checks on steps you've already completed"
+                }
+            }
+
+            if let Some(err) = error() {
+                div { class: "mb-4 p-3 bg-red-500/10 border border-red-500/20 text-red-400 rounded-lg text-sm",
+                    "{err}"
+                }
+            }
+
+            if let Some((_, quiz)) = active() {
+                div { class: "p-6 bg-[#0f1012]/60 border border-white/5 rounded-xl",
+                    div { class: "flex items-center justify-between mb-4",
+                        h3 { class: "text-sm font-semibold text-gray-200", "{active_skill}" }
+                        button {
+                            class: "text-xs text-gray-500 hover:text-white transition",
+                            onclick: move |_| {
+                                let finished = outcome().is_some();
+                                active.set(None);
+                                outcome.set(None);
+                                if finished {
+                                    reviews_resource.restart();
+                                }
+                            },
+                            if outcome().is_some() {
+                                "Done"
+                            } else {
+                                "Cancel"
+                            }
+                        }
+                    }
+                    QuizPanel {
+                        key: "{quiz.quiz_id}",
+                        questions: quiz.questions,
+                        result: outcome().map(|o| o.result),
+                        submitting: busy(),
+                        on_submit: submit,
+                    }
+                    if let Some(label) = next_due_label {
+                        p { class: "mt-3 text-xs text-gray-400", "{label}" }
+                    }
+                }
+            } else {
+                div { class: "grid md:grid-cols-2 lg:grid-cols-3 gap-3",
+                    for review in reviews.clone() {
+                        {
+                            let overdue_days = (now - review.schedule.due_at).num_days();
+                            let due_label = if overdue_days > 0 {
+                                format!("Overdue by {overdue_days} day(s)")
+                            } else {
+                                "Due today".to_string()
+                            };
+                            let review_id = review.review_id.clone();
+                            let session_token = session_token.clone();
+                            rsx! {
+                                div {
+                                    key: "{review.review_id}",
+                                    class: "px-4 py-3 bg-teal-500/5 border border-teal-500/20 rounded-lg flex items-center justify-between gap-3",
+                                    div { class: "min-w-0",
+                                        div { class: "text-sm font-medium text-teal-200 truncate",
+                                            "{review.skill_name}"
+                                        }
+                                        div { class: "text-xs text-gray-500 mt-1 truncate",
+                                            "{review.roadmap_title} · {due_label}"
+                                        }
+                                    }
+                                    button {
+                                        disabled: busy(),
+                                        class: "shrink-0 px-3 py-1.5 text-xs font-medium rounded-md bg-teal-500/10 text-teal-300 hover:bg-teal-500/20 transition disabled:opacity-50",
+                                        onclick: move |_| {
+                                            let session_token = session_token.clone();
+                                            let review_id = review_id.clone();
+                                            spawn(async move {
+                                                busy.set(true);
+                                                error.set(None);
+                                                match start_review(session_token, review_id.clone()).await {
+                                                    Ok(quiz) => {
+                                                        outcome.set(None);
+                                                        active.set(Some((review_id, quiz)));
+                                                    }
+                                                    Err(e) => error.set(Some(format!("Couldn't start the review: {e}"))),
+                                                }
+                                                busy.set(false);
+                                            });
+                                        },
+                                        "Review"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn RoadmapCard(
     roadmap: Roadmap,
     roadmaps_resource: Resource<Result<Vec<Roadmap>, ServerFnError>>,
+    weekly_hours: Option<f32>,
 ) -> Element {
+    let schedule = weekly_hours.map(|h| roadmap.plan_schedule(h, Utc::now()));
     let completed = roadmap.nodes.iter().filter(|n| n.is_completed).count();
     let total = roadmap.nodes.len();
     let progress = if total > 0 {
@@ -263,8 +597,9 @@ fn RoadmapCard(
                             button {
                                 onclick: move |_| {
                                     let roadmap_id = roadmap_id_clone.clone();
+                                    let session_token = get_session_token().unwrap_or_default();
                                     spawn(async move {
-                                        if delete_roadmap(roadmap_id).await.is_ok() {
+                                        if delete_roadmap(session_token, roadmap_id).await.is_ok() {
                                             roadmaps_resource.restart();
                                         }
                                     });
@@ -300,6 +635,26 @@ fn RoadmapCard(
                     }
                 }
 
+                if let Some(schedule) = schedule {
+                    {
+                        let status_label = schedule.status.label();
+                        let status_class = match schedule.status {
+                            PlanStatus::Ahead { .. } => "text-green-400",
+                            PlanStatus::OnTrack => "text-teal-400",
+                            PlanStatus::Behind { .. } => "text-amber-400",
+                        };
+                        let remaining = format_hours(schedule.remaining_hours);
+                        rsx! {
+                            div { class: "flex justify-between text-xs mb-2",
+                                span { class: "text-gray-500",
+                                    "{remaining} left · done by {schedule.projected_finish.format(\"%b %d\")}"
+                                }
+                                span { class: "{status_class}", "{status_label}" }
+                            }
+                        }
+                    }
+                }
+
                 p { class: "text-xs text-gray-600",
                     "Updated {roadmap.updated_at.format(\"%b %d, %Y\")}"
                 }
@@ -1,4 +1,4 @@
-use crate::{utils::get_session_token, Route};
+use crate::{Route, utils::get_session_token};
 use dioxus::prelude::*;
 
 #[component]
@@ -1,6 +1,6 @@
-use crate::utils::*;
 use crate::SESSION_DURATION_DAYS;
-use crate::{server_functions::login_user, Route};
+use crate::utils::*;
+use crate::{Route, server_functions::login_user};
 use dioxus::prelude::*;
 
 #[component]
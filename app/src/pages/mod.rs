@@ -1,15 +1,22 @@
+pub mod admin;
+pub mod courses;
 pub mod create_roadmap;
 pub mod dashboard;
 pub mod landing;
 pub mod login;
+pub mod organizations;
 pub mod profile;
+pub mod quiz;
 pub mod roadmap_view;
 pub mod signup;
 
+pub use admin::Admin;
+pub use courses::Courses;
 pub use create_roadmap::CreateRoadmap;
 pub use dashboard::Dashboard;
 pub use landing::Landing;
 pub use login::Login;
+pub use organizations::Organizations;
 pub use profile::Profile;
-pub use roadmap_view::RoadmapView;
+pub use roadmap_view::{RoadmapView, SharedRoadmapView};
 pub use signup::Signup;
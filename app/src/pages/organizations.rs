@@ -0,0 +1,645 @@
+use crate::{
+    Route,
+    models::{OrgMember, OrgRole, Organization, Roadmap, Team},
+    server_functions::{
+        add_org_member, assign_team_roadmap, create_organization, create_team, get_organizations,
+        get_team_progress, get_user_data, get_user_roadmaps, remove_org_member,
+        set_org_member_role, set_team_membership,
+    },
+    utils::get_session_token,
+};
+use dioxus::prelude::*;
+
+const INPUT_CLASS: &str = "w-full px-4 py-2.5 bg-[#050505] text-gray-100 text-sm border border-white/10 rounded-lg focus:ring-2 focus:ring-teal-500/30 focus:border-transparent outline-none transition placeholder:text-gray-600";
+const SMALL_BUTTON_CLASS: &str = "px-4 py-2.5 rounded-lg text-sm font-medium bg-gradient-to-r from-teal-500 to-blue-600 text-white hover:shadow-[0_0_20px_rgba(20,184,166,0.25)] transition disabled:opacity-50";
+
+#[component]
+pub fn Organizations() -> Element {
+    let nav = navigator();
+    let token = get_session_token();
+
+    if token.is_none() {
+        nav.push(Route::Login {});
+        return rsx! { "Redirecting..." };
+    }
+
+    let session_token = token.unwrap();
+    let session_token_for_orgs = session_token.clone();
+    let session_token_for_roadmaps = session_token.clone();
+    let session_token_for_user = session_token.clone();
+    let session_token_for_create = session_token.clone();
+
+    let mut organizations = use_resource(move || {
+        let session_token = session_token_for_orgs.clone();
+        async move { get_organizations(session_token).await }
+    });
+    // The caller's own roadmaps are what a manager can assign as templates.
+    let roadmaps = use_resource(move || {
+        let session_token = session_token_for_roadmaps.clone();
+        async move { get_user_roadmaps(session_token).await }
+    });
+    let user = use_resource(move || {
+        let session_token = session_token_for_user.clone();
+        async move { get_user_data(session_token).await }
+    });
+
+    let my_user_id = match user.read_unchecked().as_ref() {
+        Some(Ok(u)) => u.id.clone().unwrap_or_default(),
+        _ => String::new(),
+    };
+    let my_roadmaps = match roadmaps.read_unchecked().as_ref() {
+        Some(Ok(r)) => r.clone(),
+        _ => Vec::new(),
+    };
+
+    let mut new_org_name = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "min-h-screen bg-[#050505] text-gray-100 font-sans selection:bg-teal-500/30 selection:text-teal-200",
+            nav { class: "bg-[#050505]/80 backdrop-blur-md border-b border-white/5",
+                div { class: "container mx-auto px-6 py-4 flex justify-between items-center",
+                    Link {
+                        to: Route::Dashboard {},
+                        class: "text-teal-400 hover:text-teal-300 font-medium",
+                        "← Back to Dashboard"
+                    }
+                    h1 { class: "text-xl font-bold text-gray-100", "Organizations" }
+                }
+            }
+
+            main { class: "container mx-auto px-6 py-8 max-w-5xl space-y-8",
+                div { class: "p-6 bg-[#0f1012]/60 rounded-2xl border border-white/5 backdrop-blur-md",
+                    h2 { class: "text-lg font-bold text-gray-100 mb-4", "Create an organization" }
+                    div { class: "flex gap-3",
+                        input {
+                            r#type: "text",
+                            class: INPUT_CLASS,
+                            placeholder: "Organization name",
+                            value: "{new_org_name}",
+                            oninput: move |e| new_org_name.set(e.value()),
+                        }
+                        button {
+                            class: SMALL_BUTTON_CLASS,
+                            disabled: new_org_name().trim().is_empty(),
+                            onclick: move |_| {
+                                let session_token = session_token_for_create.clone();
+                                spawn(async move {
+                                    match create_organization(session_token, new_org_name()).await {
+                                        Ok(_) => {
+                                            new_org_name.set(String::new());
+                                            error.set(None);
+                                            organizations.restart();
+                                        }
+                                        Err(e) => error.set(Some(format!("Couldn't create the organization: {e}"))),
+                                    }
+                                });
+                            },
+                            "Create"
+                        }
+                    }
+                    if let Some(err) = error() {
+                        p { class: "mt-3 text-sm text-red-400", "{err}" }
+                    }
+                }
+
+                match organizations.read_unchecked().as_ref() {
+                    Some(Ok(orgs)) if orgs.is_empty() => rsx! {
+                        div { class: "p-6 bg-[#0f1012]/60 border border-white/5 rounded-xl text-gray-400 text-center",
+                            "You're not part of any organization yet."
+                        }
+                    },
+                    Some(Ok(orgs)) => rsx! {
+                        for org in orgs.iter().cloned() {
+                            OrganizationCard {
+                                key: "{org.id}",
+                                org,
+                                my_user_id: my_user_id.clone(),
+                                session_token: session_token.clone(),
+                                roadmaps: my_roadmaps.clone(),
+                                on_change: move |_| organizations.restart(),
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "p-4 bg-red-500/10 border border-red-500/20 text-red-400 rounded-lg",
+                            "Error loading organizations: {e}"
+                        }
+                    },
+                    None => rsx! {
+                        div { class: "text-center text-gray-500", "Loading..." }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OrganizationCard(
+    org: Organization,
+    my_user_id: String,
+    session_token: String,
+    roadmaps: Vec<Roadmap>,
+    on_change: EventHandler<()>,
+) -> Element {
+    let mut new_member = use_signal(String::new);
+    let mut new_member_role = use_signal(|| OrgRole::Member);
+    let mut new_team_name = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let can_manage = org.my_role.can_manage();
+    let is_owner = org.my_role == OrgRole::Owner;
+    // Managers can only bring in plain members.
+    let grantable_roles: Vec<OrgRole> = OrgRole::ALL
+        .into_iter()
+        .filter(|r| is_owner || *r == OrgRole::Member)
+        .collect();
+
+    let org_id_for_add = org.id.clone();
+    let token_for_add = session_token.clone();
+    let org_id_for_team = org.id.clone();
+    let token_for_team = session_token.clone();
+
+    rsx! {
+        div { class: "p-6 bg-[#0f1012]/60 rounded-2xl border border-white/5 backdrop-blur-md space-y-8",
+            div { class: "flex items-center justify-between",
+                h2 { class: "text-2xl font-bold text-gray-100", "{org.name}" }
+                span { class: "px-2.5 py-0.5 rounded text-xs font-semibold tracking-wide uppercase bg-teal-500/10 text-teal-300",
+                    "{org.my_role.label()}"
+                }
+            }
+
+            if let Some(err) = error() {
+                div { class: "p-3 bg-red-500/10 text-red-300 rounded-lg text-sm border border-red-500/20",
+                    {err}
+                }
+            }
+
+            // Members
+            div {
+                h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-3",
+                    "Members"
+                }
+                div { class: "space-y-2",
+                    for member in org.members.iter().cloned() {
+                        MemberRow {
+                            key: "{member.user_id}",
+                            org_id: org.id.clone(),
+                            is_me: member.user_id == my_user_id,
+                            member,
+                            my_role: org.my_role,
+                            session_token: session_token.clone(),
+                            on_change,
+                            on_error: move |msg| error.set(Some(msg)),
+                        }
+                    }
+                }
+
+                if can_manage {
+                    div { class: "flex gap-3 mt-4",
+                        input {
+                            r#type: "text",
+                            class: INPUT_CLASS,
+                            placeholder: "Username",
+                            value: "{new_member}",
+                            oninput: move |e| new_member.set(e.value()),
+                        }
+                        select {
+                            class: "px-3 py-2.5 bg-[#050505] text-gray-300 text-sm border border-white/10 rounded-lg outline-none",
+                            onchange: move |e| {
+                                if let Some(role) = OrgRole::ALL.into_iter().find(|r| r.label() == e.value()) {
+                                    new_member_role.set(role);
+                                }
+                            },
+                            for role in grantable_roles.iter().rev() {
+                                option {
+                                    value: "{role.label()}",
+                                    selected: *role == new_member_role(),
+                                    "{role.label()}"
+                                }
+                            }
+                        }
+                        button {
+                            class: SMALL_BUTTON_CLASS,
+                            disabled: new_member().trim().is_empty(),
+                            onclick: move |_| {
+                                let session_token = token_for_add.clone();
+                                let org_id = org_id_for_add.clone();
+                                spawn(async move {
+                                    match add_org_member(session_token, org_id, new_member(), new_member_role())
+                                        .await
+                                    {
+                                        Ok(()) => {
+                                            new_member.set(String::new());
+                                            error.set(None);
+                                            on_change.call(());
+                                        }
+                                        Err(e) => error.set(Some(format!("Couldn't add member: {e}"))),
+                                    }
+                                });
+                            },
+                            "Add"
+                        }
+                    }
+                }
+            }
+
+            // Teams
+            div {
+                h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-3",
+                    "Teams"
+                }
+                if org.teams.is_empty() {
+                    p { class: "text-sm text-gray-500", "No teams yet." }
+                }
+                div { class: "space-y-4",
+                    for team in org.teams.iter().cloned() {
+                        TeamCard {
+                            key: "{team.id}",
+                            team,
+                            members: org.members.clone(),
+                            can_manage,
+                            session_token: session_token.clone(),
+                            roadmaps: roadmaps.clone(),
+                            on_change,
+                        }
+                    }
+                }
+
+                if can_manage {
+                    div { class: "flex gap-3 mt-4",
+                        input {
+                            r#type: "text",
+                            class: INPUT_CLASS,
+                            placeholder: "New team name",
+                            value: "{new_team_name}",
+                            oninput: move |e| new_team_name.set(e.value()),
+                        }
+                        button {
+                            class: SMALL_BUTTON_CLASS,
+                            disabled: new_team_name().trim().is_empty(),
+                            onclick: move |_| {
+                                let session_token = token_for_team.clone();
+                                let org_id = org_id_for_team.clone();
+                                spawn(async move {
+                                    match create_team(session_token, org_id, new_team_name()).await {
+                                        Ok(_) => {
+                                            new_team_name.set(String::new());
+                                            error.set(None);
+                                            on_change.call(());
+                                        }
+                                        Err(e) => error.set(Some(format!("Couldn't create the team: {e}"))),
+                                    }
+                                });
+                            },
+                            "Create team"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn MemberRow(
+    org_id: String,
+    member: OrgMember,
+    is_me: bool,
+    my_role: OrgRole,
+    session_token: String,
+    on_change: EventHandler<()>,
+    on_error: EventHandler<String>,
+) -> Element {
+    let can_remove = is_me
+        || my_role == OrgRole::Owner
+        || (my_role == OrgRole::Manager && member.role == OrgRole::Member);
+    let org_id_for_role = org_id.clone();
+    let member_id_for_role = member.user_id.clone();
+    let token_for_role = session_token.clone();
+
+    rsx! {
+        div { class: "flex items-center justify-between gap-4 p-3 bg-white/5 border border-white/10 rounded-lg",
+            div {
+                div { class: "text-sm text-gray-200 font-medium", "{member.name}" }
+                div { class: "text-xs text-gray-500", "@{member.username}" }
+            }
+            div { class: "flex items-center gap-2",
+                if my_role == OrgRole::Owner {
+                    select {
+                        class: "px-2 py-1.5 bg-[#050505] text-gray-300 text-xs border border-white/10 rounded-md outline-none",
+                        onchange: move |e| {
+                            let Some(role) = OrgRole::ALL.into_iter().find(|r| r.label() == e.value()) else {
+                                return;
+                            };
+                            let session_token = token_for_role.clone();
+                            let org_id = org_id_for_role.clone();
+                            let member_id = member_id_for_role.clone();
+                            spawn(async move {
+                                match set_org_member_role(session_token, org_id, member_id, role).await {
+                                    Ok(()) => on_change.call(()),
+                                    Err(e) => on_error.call(format!("Couldn't change the role: {e}")),
+                                }
+                            });
+                        },
+                        for role in OrgRole::ALL {
+                            option {
+                                value: "{role.label()}",
+                                selected: role == member.role,
+                                "{role.label()}"
+                            }
+                        }
+                    }
+                } else {
+                    span { class: "text-xs text-gray-400", "{member.role.label()}" }
+                }
+                if can_remove {
+                    button {
+                        class: "px-3 py-1.5 rounded-md text-xs text-red-400 bg-red-500/10 hover:bg-red-500/20 transition",
+                        onclick: move |_| {
+                            let session_token = session_token.clone();
+                            let org_id = org_id.clone();
+                            let member_id = member.user_id.clone();
+                            spawn(async move {
+                                match remove_org_member(session_token, org_id, member_id).await {
+                                    Ok(()) => on_change.call(()),
+                                    Err(e) => on_error.call(format!("Couldn't remove the member: {e}")),
+                                }
+                            });
+                        },
+                        if is_me {
+                            "Leave"
+                        } else {
+                            "Remove"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TeamCard(
+    team: Team,
+    members: Vec<OrgMember>,
+    can_manage: bool,
+    session_token: String,
+    roadmaps: Vec<Roadmap>,
+    on_change: EventHandler<()>,
+) -> Element {
+    let mut error = use_signal(|| None::<String>);
+    let mut selected_member = use_signal(String::new);
+    let mut selected_roadmap = use_signal(String::new);
+    let mut show_progress = use_signal(|| false);
+
+    let in_team: Vec<OrgMember> = members
+        .iter()
+        .filter(|m| team.member_ids.contains(&m.user_id))
+        .cloned()
+        .collect();
+    let not_in_team: Vec<OrgMember> = members
+        .iter()
+        .filter(|m| !team.member_ids.contains(&m.user_id))
+        .cloned()
+        .collect();
+    let assignable: Vec<Roadmap> = roadmaps
+        .into_iter()
+        .filter(|r| {
+            !team
+                .assignments
+                .iter()
+                .any(|a| a.template_roadmap_id.is_some() && a.template_roadmap_id == r.id)
+        })
+        .collect();
+
+    let team_id_for_add = team.id.clone();
+    let token_for_add = session_token.clone();
+    let team_id_for_assign = team.id.clone();
+    let token_for_assign = session_token.clone();
+    let team_id_for_progress = team.id.clone();
+    let token_for_progress = session_token.clone();
+
+    rsx! {
+        div { class: "p-4 bg-[#0b0c0e] border border-white/10 rounded-xl space-y-4",
+            div { class: "flex items-center justify-between",
+                h4 { class: "text-lg font-semibold text-gray-100", "{team.name}" }
+                if can_manage {
+                    button {
+                        class: "text-xs text-teal-400 hover:text-teal-300 transition",
+                        onclick: move |_| show_progress.set(!show_progress()),
+                        if show_progress() {
+                            "Hide progress"
+                        } else {
+                            "Team progress"
+                        }
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                p { class: "text-xs text-red-400", "{err}" }
+            }
+
+            div { class: "flex flex-wrap gap-2",
+                if in_team.is_empty() {
+                    span { class: "text-xs text-gray-500", "No members yet." }
+                }
+                for member in in_team {
+                    {
+                        let team_id = team.id.clone();
+                        let session_token = session_token.clone();
+                        let member_id = member.user_id.clone();
+                        rsx! {
+                            span {
+                                key: "{member.user_id}",
+                                class: "inline-flex items-center gap-2 px-3 py-1.5 rounded-md text-xs bg-white/5 border border-white/10 text-gray-300",
+                                "{member.name}"
+                                if can_manage {
+                                    button {
+                                        class: "text-gray-500 hover:text-red-400 transition",
+                                        title: "Remove from team",
+                                        onclick: move |_| {
+                                            let team_id = team_id.clone();
+                                            let session_token = session_token.clone();
+                                            let member_id = member_id.clone();
+                                            spawn(async move {
+                                                match set_team_membership(session_token, team_id, member_id, false).await {
+                                                    Ok(()) => on_change.call(()),
+                                                    Err(e) => error.set(Some(format!("Couldn't update the team: {e}"))),
+                                                }
+                                            });
+                                        },
+                                        "✕"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                div { class: "text-[10px] text-gray-500 font-bold uppercase tracking-wider mb-2",
+                    "Assigned roadmaps"
+                }
+                if team.assignments.is_empty() {
+                    p { class: "text-xs text-gray-500", "Nothing assigned yet." }
+                }
+                ul { class: "space-y-1",
+                    for assignment in team.assignments.iter() {
+                        {
+                            let since = assignment.assigned_at.format("%b %-d, %Y").to_string();
+                            rsx! {
+                                li { key: "{assignment.id}", class: "text-sm text-gray-300",
+                                    "{assignment.skill_name}"
+                                    span { class: "ml-2 text-xs text-gray-500", "since {since}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if can_manage {
+                div { class: "grid grid-cols-1 md:grid-cols-2 gap-3",
+                    div { class: "flex gap-2",
+                        select {
+                            class: "flex-1 px-3 py-2 bg-[#050505] text-gray-300 text-xs border border-white/10 rounded-lg outline-none",
+                            onchange: move |e| selected_member.set(e.value()),
+                            option { value: "", "Add a member..." }
+                            for member in not_in_team {
+                                option { value: "{member.user_id}", "{member.name}" }
+                            }
+                        }
+                        button {
+                            class: "px-3 py-2 rounded-lg text-xs font-medium border border-white/10 bg-[#1a1b1e] text-gray-300 hover:text-white hover:border-teal-500/30 transition disabled:opacity-50",
+                            disabled: selected_member().is_empty(),
+                            onclick: move |_| {
+                                let team_id = team_id_for_add.clone();
+                                let session_token = token_for_add.clone();
+                                spawn(async move {
+                                    match set_team_membership(session_token, team_id, selected_member(), true)
+                                        .await
+                                    {
+                                        Ok(()) => {
+                                            selected_member.set(String::new());
+                                            on_change.call(());
+                                        }
+                                        Err(e) => error.set(Some(format!("Couldn't update the team: {e}"))),
+                                    }
+                                });
+                            },
+                            "Add"
+                        }
+                    }
+                    div { class: "flex gap-2",
+                        select {
+                            class: "flex-1 px-3 py-2 bg-[#050505] text-gray-300 text-xs border border-white/10 rounded-lg outline-none",
+                            onchange: move |e| selected_roadmap.set(e.value()),
+                            option { value: "", "Assign one of your roadmaps..." }
+                            for roadmap in assignable {
+                                option {
+                                    value: "{roadmap.id.clone().unwrap_or_default()}",
+                                    "{roadmap.skill_name}"
+                                }
+                            }
+                        }
+                        button {
+                            class: "px-3 py-2 rounded-lg text-xs font-medium border border-white/10 bg-[#1a1b1e] text-gray-300 hover:text-white hover:border-teal-500/30 transition disabled:opacity-50",
+                            disabled: selected_roadmap().is_empty(),
+                            onclick: move |_| {
+                                let team_id = team_id_for_assign.clone();
+                                let session_token = token_for_assign.clone();
+                                spawn(async move {
+                                    match assign_team_roadmap(session_token, team_id, selected_roadmap())
+                                        .await
+                                    {
+                                        Ok(()) => {
+                                            selected_roadmap.set(String::new());
+                                            on_change.call(());
+                                        }
+                                        Err(e) => error.set(Some(format!("Couldn't assign the roadmap: {e}"))),
+                                    }
+                                });
+                            },
+                            "Assign"
+                        }
+                    }
+                }
+            }
+
+            if show_progress() {
+                TeamProgressPanel {
+                    team_id: team_id_for_progress,
+                    session_token: token_for_progress,
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TeamProgressPanel(team_id: String, session_token: String) -> Element {
+    let progress = use_resource(move || {
+        let team_id = team_id.clone();
+        let session_token = session_token.clone();
+        async move { get_team_progress(session_token, team_id).await }
+    });
+
+    match progress.read_unchecked().as_ref() {
+        Some(Ok(progress)) if progress.assignments.is_empty() => rsx! {
+            p { class: "text-xs text-gray-500", "Assign a roadmap to start tracking progress." }
+        },
+        Some(Ok(progress)) => rsx! {
+            div { class: "space-y-4",
+                for assignment in progress.assignments.iter() {
+                    div {
+                        key: "{assignment.assignment_id}",
+                        class: "p-4 bg-white/5 border border-white/10 rounded-lg",
+                        div { class: "flex items-center justify-between mb-3",
+                            span { class: "text-sm font-semibold text-gray-200", "{assignment.skill_name}" }
+                            span { class: "text-xs text-gray-400",
+                                "{assignment.average_percent()}% average · {assignment.finished_members()}/{assignment.members.len()} finished"
+                            }
+                        }
+                        div { class: "space-y-2",
+                            for member in assignment.members.iter() {
+                                {
+                                    let status = match (member.has_copy, member.last_activity) {
+                                        (false, _) => "no copy".to_string(),
+                                        (true, Some(at)) => {
+                                            format!("{}% · {}", member.percent(), at.format("%b %-d"))
+                                        }
+                                        (true, None) => format!("{}% · not started", member.percent()),
+                                    };
+                                    rsx! {
+                                        div {
+                                            key: "{member.user_id}",
+                                            class: "grid grid-cols-[8rem_1fr_6rem] items-center gap-3 text-xs",
+                                            span { class: "text-gray-300 truncate", "{member.name}" }
+                                            div { class: "h-1.5 bg-[#1a1b1e] rounded-full overflow-hidden",
+                                                div {
+                                                    class: "h-full bg-gradient-to-r from-teal-500 to-blue-500",
+                                                    style: "width: {member.percent()}%",
+                                                }
+                                            }
+                                            span { class: "text-right text-gray-500", "{status}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { class: "text-xs text-red-400", "Couldn't load team progress: {e}" }
+        },
+        None => rsx! {
+            p { class: "text-xs text-gray-500", "Loading progress..." }
+        },
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
-    models::{User, UserPreferences, UserSkills},
+    Route,
+    models::{ProficiencyLevel, ProficiencySource, User, UserPreferences, UserSkills},
     server_functions::{change_password, get_user_data, update_user_profile},
     utils::get_session_token,
-    Route,
 };
 use chrono::Utc;
 use dioxus::prelude::*;
@@ -212,6 +212,7 @@ fn SkillsTab(user: User) -> Element {
 
     let mut skills = use_signal(|| user.skills_learned.clone());
     let mut new_skill = use_signal(String::new);
+    let mut new_skill_level = use_signal(|| ProficiencyLevel::Intermediate);
     let mut success = use_signal(|| None::<String>);
     let mut error = use_signal(|| None::<String>);
 
@@ -227,11 +228,13 @@ fn SkillsTab(user: User) -> Element {
                 .any(|s| s.skillname.eq_ignore_ascii_case(&input_val));
 
             if !exists {
-                // Construct the simplified struct
-                let new_entry: UserSkills = UserSkills {
-                    skillname: input_val.trim().to_string(),
-                    date_learnt: Utc::now(), // Auto-timestamp
-                };
+                // Self-assessed level, auto-timestamped
+                let new_entry: UserSkills = UserSkills::new(
+                    &input_val,
+                    new_skill_level().representative_level(),
+                    ProficiencySource::SelfAssessment,
+                    Utc::now(),
+                );
 
                 current_skills.push(new_entry);
                 skills.set(current_skills);
@@ -248,6 +251,22 @@ fn SkillsTab(user: User) -> Element {
         }
     };
 
+    // --- Re-assess Skill Logic ---
+    let mut reassess_skill = move |skill_name: String, level: ProficiencyLevel| {
+        let mut current_skills = skills();
+        if let Some(skill) = current_skills
+            .iter_mut()
+            .find(|s| s.skillname == skill_name)
+        {
+            skill.record_evidence(
+                level.representative_level(),
+                ProficiencySource::SelfAssessment,
+                Utc::now(),
+            );
+        }
+        skills.set(current_skills);
+    };
+
     // --- Remove Skill Logic ---
     let mut remove_skill = move |skill_name: String| {
         let mut current_skills = skills();
@@ -333,6 +352,26 @@ fn SkillsTab(user: User) -> Element {
                         }
                     }
 
+                    select {
+                        class: "px-3 py-3 bg-[#050505] text-gray-300 border border-white/10 rounded-lg focus:ring-2 focus:ring-teal-500/30 outline-none text-sm",
+                        title: "How well do you know it?",
+                        onchange: move |e| {
+                            if let Some(level) = ProficiencyLevel::ALL
+                                .into_iter()
+                                .find(|l| l.label() == e.value())
+                            {
+                                new_skill_level.set(level);
+                            }
+                        },
+                        for level in ProficiencyLevel::ALL {
+                            option {
+                                value: level.label(),
+                                selected: level == new_skill_level(),
+                                {level.label()}
+                            }
+                        }
+                    }
+
                     button {
                         onclick: move |_| add_skill(()),
                         class: "px-6 py-3 bg-gradient-to-r from-teal-600 to-blue-600 text-white rounded-lg hover:shadow-[0_0_18px_rgba(20,184,166,0.25)] hover:scale-[1.02] active:scale-[0.98] transition-all font-medium whitespace-nowrap",
@@ -354,21 +393,68 @@ fn SkillsTab(user: User) -> Element {
                         for skill in skills() {
                             {
                                 let skill_name = skill.skillname.clone();
-                                // Format date for tooltip
+                                let skill_name_for_level = skill_name.clone();
+                                let now = Utc::now();
+                                let current_level = skill.current_level(now);
+                                let proficiency = ProficiencyLevel::from_level(current_level);
+                                let is_stale = skill.is_stale(now);
+                                let meter_color = if is_stale { "bg-amber-500" } else { "bg-teal-500" };
+                                // Format dates for tooltip
                                 let date_display = skill
                                     .date_learnt
-                                    .format("%b %Y")
+                                    .map(|date| date.format("%b %Y").to_string())
+                                    .unwrap_or_else(|| "prior knowledge".to_string());
+                                let practiced_display = skill
+                                    .last_practiced_at()
+                                    .format("%b %d, %Y")
                                     .to_string();
+                                let source_display = match skill.level_source {
+                                    ProficiencySource::SelfAssessment => "self-assessed",
+                                    ProficiencySource::NodeCompletion => "from roadmap progress",
+                                    ProficiencySource::Quiz => "from quiz results",
+                                };
                                 rsx! {
                                     div {
                                         key: "{skill_name}",
-                                        class: "group relative inline-flex items-center px-4 py-2 bg-gradient-to-br from-teal-500/5 to-blue-500/5 text-teal-200 rounded-lg border border-teal-500/10 hover:border-teal-500/30 backdrop-blur-md transition-all duration-200 hover:shadow-[0_0_15px_rgba(20,184,166,0.1)] cursor-default",
+                                        class: "group relative inline-flex items-center gap-3 px-4 py-2 bg-gradient-to-br from-teal-500/5 to-blue-500/5 text-teal-200 rounded-lg border border-teal-500/10 hover:border-teal-500/30 backdrop-blur-md transition-all duration-200 hover:shadow-[0_0_15px_rgba(20,184,166,0.1)] cursor-default",
 
                                         span { class: "font-medium tracking-wide", "{skill_name}" }
 
-                                        // Tooltip: Shows Date Added
+                                        // Proficiency meter (decayed level)
+                                        div { class: "w-12 h-1 bg-white/10 rounded-full overflow-hidden",
+                                            div {
+                                                class: "h-full rounded-full {meter_color}",
+                                                style: "width: {current_level}%",
+                                            }
+                                        }
+
+                                        select {
+                                            class: "bg-transparent text-xs text-gray-400 outline-none cursor-pointer",
+                                            title: "Re-assess your level",
+                                            onchange: move |e| {
+                                                if let Some(level) = ProficiencyLevel::ALL
+                                                    .into_iter()
+                                                    .find(|l| l.label() == e.value())
+                                                {
+                                                    reassess_skill(skill_name_for_level.clone(), level);
+                                                }
+                                            },
+                                            for level in ProficiencyLevel::ALL {
+                                                option {
+                                                    value: level.label(),
+                                                    selected: level == proficiency,
+                                                    {level.label()}
+                                                }
+                                            }
+                                        }
+
+                                        if is_stale {
+                                            span { class: "text-[10px] uppercase tracking-wider text-amber-400", "Stale" }
+                                        }
+
+                                        // Tooltip: Shows Date Added and proficiency details
                                         div { class: "absolute bottom-full left-1/2 -translate-x-1/2 mb-2 px-2 py-1 bg-black text-xs text-gray-300 rounded opacity-0 group-hover:opacity-100 transition-opacity pointer-events-none whitespace-nowrap border border-white/10 z-10",
-                                            "Added: {date_display}"
+                                            "Added: {date_display} · {current_level}/100 {source_display} · Last practiced: {practiced_display}"
                                         }
 
                                         // Remove Button
@@ -0,0 +1,136 @@
+use crate::models::{Question, QuestionResponse, QuestionType, QuizResult};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+/// Renders every question of a graded quiz at once and hands the answers back on submit.
+/// Once `result` is set, questions are marked right or wrong without revealing the key.
+#[component]
+pub fn QuizPanel(
+    questions: Vec<Question>,
+    result: Option<QuizResult>,
+    submitting: bool,
+    on_submit: EventHandler<Vec<QuestionResponse>>,
+) -> Element {
+    let mut answers = use_signal(HashMap::<String, Vec<String>>::new);
+
+    let all_answered = questions.iter().all(|q| {
+        answers
+            .read()
+            .get(&q.id)
+            .is_some_and(|a| a.iter().any(|x| !x.trim().is_empty()))
+    });
+    let questions_for_submit = questions.clone();
+    let result_class = match &result {
+        Some(r) if r.passed => "bg-green-500/10 border-green-500/20 text-green-300",
+        _ => "bg-amber-500/10 border-amber-500/20 text-amber-300",
+    };
+
+    rsx! {
+        div { class: "space-y-6",
+            for (idx , question) in questions.into_iter().enumerate() {
+                {
+                    let qid = question.id.clone();
+                    let qid_for_input = qid.clone();
+                    let is_msq = question.question_type == QuestionType::Msq;
+                    let selected = answers.read().get(&qid).cloned().unwrap_or_default();
+                    let verdict_class = match &result {
+                        Some(r) if r.incorrect_question_ids.contains(&qid) => "border-red-500/40",
+                        Some(_) => "border-green-500/40",
+                        None => "border-white/10",
+                    };
+                    rsx! {
+                        div {
+                            key: "{qid}",
+                            class: "p-4 bg-[#0f1012] border rounded-lg {verdict_class}",
+                            p { class: "text-sm font-medium text-gray-200 mb-3",
+                                span { class: "text-teal-400 font-mono mr-2", "{idx + 1}." }
+                                "{question.question_text}"
+                            }
+                            match question.question_type {
+                                QuestionType::OneWord => rsx! {
+                                    input {
+                                        r#type: "text",
+                                        disabled: result.is_some(),
+                                        class: "w-full px-3 py-2 bg-[#050505] text-gray-100 text-sm border border-white/10 rounded-lg focus:ring-2 focus:ring-teal-500/30 outline-none",
+                                        placeholder: "Type your answer...",
+                                        value: "{selected.first().cloned().unwrap_or_default()}",
+                                        oninput: move |e| {
+                                            answers.write().insert(qid_for_input.clone(), vec![e.value()]);
+                                        },
+                                    }
+                                },
+                                _ => rsx! {
+                                    div { class: "space-y-2",
+                                        for option in question.options.clone() {
+                                            {
+                                                let is_selected = selected.contains(&option);
+                                                let label = option.clone();
+                                                let qid = qid.clone();
+                                                let option_class = if is_selected {
+                                                    "border-teal-500/40 bg-teal-500/10 text-gray-100"
+                                                } else {
+                                                    "border-white/10 bg-white/5 hover:bg-white/10 text-gray-300"
+                                                };
+                                                rsx! {
+                                                    button {
+                                                        key: "{label}",
+                                                        disabled: result.is_some(),
+                                                        class: "w-full px-3 py-2 text-left text-sm border rounded-lg transition {option_class}",
+                                                        onclick: move |_| {
+                                                            let mut all = answers.write();
+                                                            let chosen = all.entry(qid.clone()).or_default();
+                                                            if let Some(pos) = chosen.iter().position(|x| x == &option) {
+                                                                chosen.remove(pos);
+                                                            } else {
+                                                                if !is_msq {
+                                                                    chosen.clear();
+                                                                }
+                                                                chosen.push(option.clone());
+                                                            }
+                                                        },
+                                                        "{label}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(r) = &result {
+                div { class: "p-4 rounded-lg border text-sm {result_class}",
+                    if r.passed {
+                        "Passed with {r.score}% ({r.correct}/{r.total} correct)."
+                    } else {
+                        "Scored {r.score}% ({r.correct}/{r.total} correct). Review the material and try a fresh quiz."
+                    }
+                }
+            } else {
+                button {
+                    disabled: submitting || !all_answered,
+                    class: "w-full py-3 rounded-lg font-bold text-sm bg-gradient-to-r from-teal-600 to-blue-600 text-white disabled:opacity-50 disabled:cursor-not-allowed",
+                    onclick: move |_| {
+                        let all = answers.read();
+                        let responses = questions_for_submit
+                            .iter()
+                            .map(|q| QuestionResponse {
+                                question_id: q.id.clone(),
+                                answer: all.get(&q.id).cloned().unwrap_or_default(),
+                            })
+                            .collect();
+                        on_submit.call(responses);
+                    },
+                    if submitting {
+                        "Grading..."
+                    } else {
+                        "Submit Answers"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,20 +1,81 @@
 use crate::{
-    models::{LearningResource, Roadmap, RoadmapNode},
-    server_functions::{get_roadmap, toggle_node_completion},
     Route,
+    models::{
+        CloneSource, ExportFormat, LearningResource, NodeQuiz, PlanStatus, QuestionResponse,
+        QuizResult, ResourceFeedback, ResourceFeedbackKind, Roadmap, RoadmapNode, RoadmapSchedule,
+        ShareLink,
+    },
+    pages::quiz::QuizPanel,
+    server_functions::{
+        clone_roadmap, create_share_link, export_roadmap, export_roadmap_ics, generate_node_quiz,
+        get_calendar_feed_path, get_resource_feedback, get_roadmap, get_shared_roadmap,
+        get_user_data, list_share_links, revoke_calendar_feed, revoke_share_link,
+        rotate_calendar_feed, set_mastery_quiz_required, submit_node_quiz, toggle_node_completion,
+        toggle_resource_feedback,
+    },
+    utils::{download_text_file, file_slug, format_hours, get_session_token, page_origin},
 };
+use chrono::Utc;
 use dioxus::prelude::*;
 use std::collections::{HashMap, HashSet};
 
 #[component]
 pub fn RoadmapView(id: String) -> Element {
-    let roadmap_id = id.clone();
+    rsx! {
+        RoadmapScreen { key: "{id}", roadmap_id: id.clone(), share_token: None }
+    }
+}
+
+/// Public, read-only view of a roadmap opened through a share link. No session is needed.
+#[component]
+pub fn SharedRoadmapView(token: String) -> Element {
+    rsx! {
+        RoadmapScreen { key: "{token}", roadmap_id: String::new(), share_token: Some(token.clone()) }
+    }
+}
+
+/// The roadmap page itself. With a share token it loads the shared copy and hides every
+/// control that would change the roadmap.
+#[component]
+fn RoadmapScreen(roadmap_id: String, share_token: Option<String>) -> Element {
+    let read_only = share_token.is_some();
+    let session_token = get_session_token().unwrap_or_default();
+    let session_token_for_user = session_token.clone();
+    let id = roadmap_id.clone();
+    let token_for_load = share_token.clone();
+    let session_token_for_load = session_token.clone();
+
+    // What the owner chose to reveal; an owner always sees everything.
+    let mut owner_name = use_signal(|| None::<String>);
+    let mut show_progress = use_signal(|| !read_only);
 
     let roadmap: Resource<Result<Roadmap, ServerFnError>> = use_resource(move || {
         let id = id.clone();
-        async move { get_roadmap(id).await }
+        let share_token = token_for_load.clone();
+        let session_token = session_token_for_load.clone();
+        async move {
+            match share_token {
+                Some(token) => {
+                    let shared = get_shared_roadmap(token).await?;
+                    owner_name.set(shared.owner_name);
+                    show_progress.set(shared.include_progress);
+                    Ok(shared.roadmap)
+                }
+                None => get_roadmap(session_token, id).await,
+            }
+        }
     });
 
+    // Only needed for the study-time preference; the roadmap renders without it.
+    let user = use_resource(move || {
+        let session_token = session_token_for_user.clone();
+        async move { get_user_data(session_token).await }
+    });
+    let weekly_hours = match user.read_unchecked().as_ref() {
+        Some(Ok(u)) if !read_only => Some(u.preferences.weekly_hours()),
+        _ => None,
+    };
+
     let mut selected_node_id = use_signal(|| None::<String>);
 
     // Animation triggers
@@ -39,11 +100,19 @@ pub fn RoadmapView(id: String) -> Element {
             // Top nav
             nav { class: "bg-[#050505]/80 backdrop-blur-md border-b border-white/5 sticky top-0 z-40",
                 div { class: "container mx-auto px-6 py-4 flex justify-between items-center",
-                    Link {
-                        to: Route::Dashboard {},
-                        class: "text-teal-400 hover:text-teal-300 transition-colors font-medium flex items-center gap-2",
-                        span { "←" }
-                        "Back to Dashboard"
+                    if read_only {
+                        Link {
+                            to: Route::Landing {},
+                            class: "text-teal-400 hover:text-teal-300 transition-colors font-medium flex items-center gap-2",
+                            "SkillForge"
+                        }
+                    } else {
+                        Link {
+                            to: Route::Dashboard {},
+                            class: "text-teal-400 hover:text-teal-300 transition-colors font-medium flex items-center gap-2",
+                            span { "←" }
+                            "Back to Dashboard"
+                        }
                     }
 
                     match roadmap.read_unchecked().as_ref() {
@@ -74,18 +143,39 @@ pub fn RoadmapView(id: String) -> Element {
                                         node,
                                         roadmap: roadmap_data.clone(),
                                         roadmap_id: roadmap_id.clone(),
+                                        session_token: session_token.clone(),
                                         roadmap_resource: roadmap,
+                                        read_only,
+                                        show_progress: show_progress(),
                                         selected_node_id,
                                         on_close: move |_| selected_node_id.set(None),
                                     }
                                 },
                                 None => rsx! {
-                                    RoadmapOverview { roadmap: roadmap_data.clone() }
+                                    RoadmapOverview {
+                                        roadmap: roadmap_data.clone(),
+                                        roadmap_id: roadmap_id.clone(),
+                                        session_token: session_token.clone(),
+                                        roadmap_resource: roadmap,
+                                        weekly_hours,
+                                        share_token: share_token.clone(),
+                                        show_progress: show_progress(),
+                                        owner_name: owner_name(),
+                                    }
                                 },
                             }
                         }
                         None => rsx! {
-                            RoadmapOverview { roadmap: roadmap_data.clone() }
+                            RoadmapOverview {
+                                roadmap: roadmap_data.clone(),
+                                roadmap_id: roadmap_id.clone(),
+                                session_token: session_token.clone(),
+                                roadmap_resource: roadmap,
+                                weekly_hours,
+                                share_token: share_token.clone(),
+                                show_progress: show_progress(),
+                                owner_name: owner_name(),
+                            }
                         },
                     };
                     rsx! {
@@ -108,7 +198,9 @@ pub fn RoadmapView(id: String) -> Element {
                                             span { class: "text-teal-500", "◈" }
                                             "Learning Path"
                                         }
-                                        RoadmapProgressPill { roadmap: roadmap_data.clone() }
+                                        if show_progress() {
+                                            RoadmapProgressPill { roadmap: roadmap_data.clone() }
+                                        }
                                     }
 
                                     // Timeline Container
@@ -158,7 +250,11 @@ pub fn RoadmapView(id: String) -> Element {
                 Some(Err(e)) => rsx! {
                     div { class: "container mx-auto px-6 py-12",
                         div { class: "bg-red-500/10 text-red-300 p-6 rounded-lg border border-red-500/20 backdrop-blur-md",
-                            "Error loading roadmap: {e}"
+                            if read_only {
+                                "This share link is invalid, expired or has been revoked."
+                            } else {
+                                "Error loading roadmap: {e}"
+                            }
                         }
                     }
                 },
@@ -268,7 +364,9 @@ fn TimelineNode(
                         span { class: "text-teal-400", "0{idx}" }
                         span { "—" }
                         span {
-                            if node.is_completed {
+                            if node.mastery_score.is_some() {
+                                "VERIFIED"
+                            } else if node.is_completed {
                                 "COMPLETED"
                             } else {
                                 "PENDING"
@@ -394,10 +492,69 @@ fn NodeDetailSidebar(
     node: RoadmapNode,
     roadmap: Roadmap,
     roadmap_id: String,
+    session_token: String,
     roadmap_resource: Resource<Result<Roadmap, ServerFnError>>,
+    read_only: bool,
+    show_progress: bool,
     selected_node_id: Signal<Option<String>>,
     on_close: EventHandler<()>,
 ) -> Element {
+    let mut quiz = use_signal(|| None::<NodeQuiz>);
+    let mut quiz_result = use_signal(|| None::<QuizResult>);
+    let mut quiz_busy = use_signal(|| false);
+    let mut quiz_error = use_signal(|| None::<String>);
+    // The sidebar is reused across nodes, so only show a quiz that belongs to this one.
+    let active_quiz = quiz().filter(|q| q.node_id == node.id);
+    let quiz_open = active_quiz.is_some();
+    let can_self_complete = node.is_completed || !roadmap.require_mastery_quiz;
+
+    let start_quiz = {
+        let session_token = session_token.clone();
+        let roadmap_id = roadmap_id.clone();
+        let node_id = node.id.clone();
+        move |_: MouseEvent| {
+            let session_token = session_token.clone();
+            let roadmap_id = roadmap_id.clone();
+            let node_id = node_id.clone();
+            spawn(async move {
+                quiz_busy.set(true);
+                quiz_error.set(None);
+                match generate_node_quiz(session_token, roadmap_id, node_id).await {
+                    Ok(q) => {
+                        quiz_result.set(None);
+                        quiz.set(Some(q));
+                    }
+                    Err(e) => quiz_error.set(Some(format!("Couldn't build a quiz: {e}"))),
+                }
+                quiz_busy.set(false);
+            });
+        }
+    };
+
+    let submit_quiz = {
+        let session_token = session_token.clone();
+        move |responses: Vec<QuestionResponse>| {
+            let session_token = session_token.clone();
+            let Some(quiz_id) = quiz().map(|q| q.quiz_id) else {
+                return;
+            };
+            spawn(async move {
+                quiz_busy.set(true);
+                match submit_node_quiz(session_token, quiz_id, responses).await {
+                    Ok(result) => {
+                        let passed = result.passed;
+                        quiz_result.set(Some(result));
+                        if passed {
+                            roadmap_resource.restart();
+                        }
+                    }
+                    Err(e) => quiz_error.set(Some(format!("Couldn't grade the quiz: {e}"))),
+                }
+                quiz_busy.set(false);
+            });
+        }
+    };
+
     let prev_label = node
         .prev_node_id
         .as_deref()
@@ -407,6 +564,8 @@ fn NodeDetailSidebar(
         .as_deref()
         .map(|v| label_for_ref(&roadmap, v));
 
+    let effort = format_hours(node.effort_hours());
+
     // extracted button class to keep rsx clean
     let button_class = if node.is_completed {
         "w-full py-3.5 rounded-lg font-bold text-sm transition-all duration-300 transform active:scale-[0.98] bg-[#1a1b1e] text-gray-400 border border-white/10 hover:bg-white/5 hover:text-white"
@@ -422,21 +581,24 @@ fn NodeDetailSidebar(
                     h2 { class: "text-2xl font-bold text-gray-100 leading-tight mb-2",
                         "{node.skill_name.clone()}"
                     }
-                    div {
-                        class: format!(
-                            "inline-flex items-center gap-1.5 px-2.5 py-0.5 rounded text-xs font-semibold tracking-wide uppercase {}",
+                    if show_progress {
+                        div {
+                            class: format!(
+                                "inline-flex items-center gap-1.5 px-2.5 py-0.5 rounded text-xs font-semibold tracking-wide uppercase {}",
+                                if node.is_completed {
+                                    "bg-green-500/20 text-green-400"
+                                } else {
+                                    "bg-yellow-500/10 text-yellow-500"
+                                },
+                            ),
                             if node.is_completed {
-                                "bg-green-500/20 text-green-400"
+                                "Completed"
                             } else {
-                                "bg-yellow-500/10 text-yellow-500"
-                            },
-                        ),
-                        if node.is_completed {
-                            "Completed"
-                        } else {
-                            "In Progress"
+                                "In Progress"
+                            }
                         }
                     }
+                    span { class: "ml-2 text-xs text-gray-500", "≈ {effort}" }
                 }
                 button {
                     onclick: move |_| on_close.call(()),
@@ -448,6 +610,35 @@ fn NodeDetailSidebar(
             // Scrollable Content
             div { class: "flex-1 overflow-y-auto custom-scroll pr-2 space-y-8",
 
+                // Mastery Quiz
+                if let Some(active) = active_quiz {
+                    div {
+                        div { class: "flex items-center justify-between mb-3",
+                            h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest",
+                                "MASTERY QUIZ"
+                            }
+                            button {
+                                class: "text-xs text-gray-500 hover:text-white transition",
+                                onclick: move |_| quiz.set(None),
+                                "Close"
+                            }
+                        }
+                        QuizPanel {
+                            key: "{active.quiz_id}",
+                            questions: active.questions,
+                            result: quiz_result(),
+                            submitting: quiz_busy(),
+                            on_submit: submit_quiz,
+                        }
+                    }
+                }
+
+                if let Some(err) = quiz_error() {
+                    div { class: "p-3 bg-red-500/10 text-red-300 rounded-lg text-sm border border-red-500/20",
+                        {err}
+                    }
+                }
+
                 // Navigation
                 if prev_label.is_some() || next_label.is_some() {
                     div { class: "grid grid-cols-2 gap-3",
@@ -542,9 +733,19 @@ fn NodeDetailSidebar(
                         h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-3",
                             "LEARNING RESOURCES"
                         }
-                        div { class: "space-y-3",
-                            for resource in &node.resources {
-                                ResourceCard { resource: resource.clone() }
+                        if read_only {
+                            div { class: "space-y-3",
+                                for resource in &node.resources {
+                                    ResourceCard { resource: resource.clone() }
+                                }
+                            }
+                        } else {
+                            NodeResources {
+                                key: "{node.id}",
+                                resources: node.resources.clone(),
+                                roadmap_id: roadmap_id.clone(),
+                                node_id: node.id.clone(),
+                                session_token: session_token.clone(),
                             }
                         }
                     }
@@ -552,23 +753,45 @@ fn NodeDetailSidebar(
             }
 
             // Footer Action
-            div { class: "mt-6 pt-6 border-t border-white/10 shrink-0",
-                button {
-                    class: "{button_class}",
-                    onclick: move |_| {
-                        spawn({
-                            let roadmap_id = roadmap_id.clone();
-                            let node_id = node.id.clone();
-                            async move {
-                                let _ = toggle_node_completion(roadmap_id, node_id).await;
-                                roadmap_resource.restart();
+            if !read_only || node.mastery_score.is_some() {
+                div { class: "mt-6 pt-6 border-t border-white/10 shrink-0 space-y-3",
+                    if let Some(score) = node.mastery_score {
+                        div { class: "text-center text-xs text-green-400 font-medium",
+                            "✓ Verified by mastery quiz · {score}%"
+                        }
+                    }
+                    if !node.is_completed && !read_only {
+                        button {
+                            disabled: quiz_busy(),
+                            class: "w-full py-3 rounded-lg font-bold text-sm border border-teal-500/30 text-teal-300 hover:bg-teal-500/10 transition disabled:opacity-50",
+                            onclick: start_quiz,
+                            if quiz_busy() && !quiz_open {
+                                "Writing your quiz..."
+                            } else {
+                                "Prove it — Take Mastery Quiz"
                             }
-                        });
-                    },
-                    if node.is_completed {
-                        "Mark as Incomplete"
-                    } else {
-                        "Complete Skill"
+                        }
+                    }
+                    if can_self_complete && !read_only {
+                        button {
+                            class: "{button_class}",
+                            onclick: move |_| {
+                                spawn({
+                                    let session_token = session_token.clone();
+                                    let roadmap_id = roadmap_id.clone();
+                                    let node_id = node.id.clone();
+                                    async move {
+                                        let _ = toggle_node_completion(session_token, roadmap_id, node_id).await;
+                                        roadmap_resource.restart();
+                                    }
+                                });
+                            },
+                            if node.is_completed {
+                                "Mark as Incomplete"
+                            } else {
+                                "Complete Skill"
+                            }
+                        }
                     }
                 }
             }
@@ -584,6 +807,9 @@ fn ResourceCard(resource: LearningResource) -> Element {
                 span { class: "text-[10px] font-bold text-teal-500 uppercase tracking-wide",
                     "{resource.resource_type}"
                 }
+                if let Some(minutes) = resource.estimated_minutes {
+                    span { class: "text-[10px] text-gray-500", "{minutes} min" }
+                }
             }
             h4 { class: "font-medium text-gray-200 text-sm mb-1 group-hover:text-teal-300 transition-colors",
                 "{resource.title}"
@@ -602,8 +828,544 @@ fn ResourceCard(resource: LearningResource) -> Element {
     }
 }
 
+/// A step's resources with feedback controls. Keyed by node, so switching steps reloads the
+/// feedback.
+#[component]
+fn NodeResources(
+    resources: Vec<LearningResource>,
+    roadmap_id: String,
+    node_id: String,
+    session_token: String,
+) -> Element {
+    let token_for_load = session_token.clone();
+    let roadmap_for_load = roadmap_id.clone();
+    let node_for_load = node_id.clone();
+    let mut feedback = use_signal(Vec::<ResourceFeedback>::new);
+    let mut error = use_signal(|| None::<String>);
+
+    use_future(move || {
+        let session_token = token_for_load.clone();
+        let roadmap_id = roadmap_for_load.clone();
+        let node_id = node_for_load.clone();
+        async move {
+            match get_resource_feedback(session_token, roadmap_id, node_id).await {
+                Ok(loaded) => feedback.set(loaded),
+                Err(e) => error.set(Some(format!("Couldn't load feedback: {e}"))),
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "space-y-3",
+            if let Some(err) = error() {
+                p { class: "text-xs text-red-400", "{err}" }
+            }
+            for (index, resource) in resources.iter().enumerate() {
+                div { key: "{index}",
+                    ResourceCard { resource: resource.clone() }
+                    if let Some(state) = feedback().into_iter().find(|f| f.resource_index == index && f.in_catalog) {
+                        ResourceFeedbackBar {
+                            state,
+                            on_toggle: {
+                                let session_token = session_token.clone();
+                                let roadmap_id = roadmap_id.clone();
+                                let node_id = node_id.clone();
+                                move |kind: ResourceFeedbackKind| {
+                                    let session_token = session_token.clone();
+                                    let roadmap_id = roadmap_id.clone();
+                                    let node_id = node_id.clone();
+                                    spawn(async move {
+                                        match toggle_resource_feedback(session_token, roadmap_id, node_id, index, kind)
+                                            .await
+                                        {
+                                            Ok(updated) => {
+                                                let mut all = feedback.write();
+                                                if let Some(slot) = all.iter_mut().find(|f| f.resource_index == index) {
+                                                    *slot = updated;
+                                                }
+                                                error.set(None);
+                                            }
+                                            Err(e) => error.set(Some(format!("Couldn't save feedback: {e}"))),
+                                        }
+                                    });
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ResourceFeedbackBar(
+    state: ResourceFeedback,
+    on_toggle: EventHandler<ResourceFeedbackKind>,
+) -> Element {
+    rsx! {
+        div { class: "flex flex-wrap gap-1.5 mt-2",
+            for kind in ResourceFeedbackKind::ALL {
+                {
+                    let given = state.mine.contains(&kind);
+                    let count = state.summary.count(kind);
+                    let class = if given {
+                        "px-2 py-1 rounded text-[11px] border bg-teal-500/20 border-teal-500/40 text-teal-300 transition"
+                    } else {
+                        "px-2 py-1 rounded text-[11px] border bg-[#1a1b1e] border-white/10 text-gray-500 hover:text-gray-200 transition"
+                    };
+                    rsx! {
+                        button {
+                            key: "{kind.label()}",
+                            class: "{class}",
+                            onclick: move |_| on_toggle.call(kind),
+                            "{kind.label()}"
+                            if count > 0 {
+                                span { class: "ml-1 text-gray-600", "{count}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RoadmapSchedulePanel(schedule: RoadmapSchedule) -> Element {
+    let status_label = schedule.status.label();
+    let status_class = match schedule.status {
+        PlanStatus::Ahead { .. } => "text-green-400",
+        PlanStatus::OnTrack => "text-teal-400",
+        PlanStatus::Behind { .. } => "text-amber-400",
+    };
+    let remaining = format_hours(schedule.remaining_hours);
+    let total = format_hours(schedule.total_hours);
+
+    rsx! {
+        div {
+            h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-4 flex items-center gap-2",
+                span { "🗓" }
+                "Schedule"
+            }
+            div { class: "p-4 bg-[#1a1b1e] rounded-lg border border-white/5 space-y-1 mb-4",
+                div { class: "flex justify-between text-sm",
+                    span { class: "text-gray-400", "Projected finish" }
+                    span { class: "text-gray-100 font-medium",
+                        "{schedule.projected_finish.format(\"%b %d, %Y\")}"
+                    }
+                }
+                div { class: "flex justify-between text-xs",
+                    span { class: "text-gray-500", "{remaining} of {total} left at {schedule.weekly_hours} h/week" }
+                    span { class: "{status_class}", "{status_label}" }
+                }
+            }
+            ul { class: "space-y-2",
+                for entry in schedule.upcoming.iter().take(5) {
+                    li {
+                        key: "{entry.node_id}",
+                        class: "flex justify-between gap-3 text-xs text-gray-400",
+                        span { class: "truncate", "{entry.skill_name}" }
+                        span { class: "shrink-0 text-gray-500",
+                            "by {entry.ends_on.format(\"%b %d\")}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ExportPanel(roadmap_id: String, session_token: String) -> Element {
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        div {
+            h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-4 flex items-center gap-2",
+                span { "⇩" }
+                "Export"
+            }
+            div { class: "grid grid-cols-3 gap-2",
+                for format in ExportFormat::ALL {
+                    {
+                        let roadmap_id = roadmap_id.clone();
+                        let session_token = session_token.clone();
+                        rsx! {
+                            button {
+                                key: "{format.extension()}",
+                                class: "px-2 py-2 rounded-lg border border-white/10 bg-[#1a1b1e] text-[11px] font-medium text-gray-300 hover:text-white hover:border-teal-500/30 transition",
+                                onclick: move |_| {
+                                    let roadmap_id = roadmap_id.clone();
+                                    let session_token = session_token.clone();
+                                    spawn(async move {
+                                        match export_roadmap(session_token, roadmap_id, format).await {
+                                            Ok(file) => {
+                                                download_text_file(&file.filename, &file.mime, &file.contents);
+                                            }
+                                            Err(e) => error.set(Some(format!("Export failed: {e}"))),
+                                        }
+                                    });
+                                },
+                                "{format.label()}"
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(err) = error() {
+                p { class: "mt-2 text-xs text-red-400", "{err}" }
+            }
+        }
+    }
+}
+
 #[component]
-fn RoadmapOverview(roadmap: Roadmap) -> Element {
+fn CalendarPanel(roadmap_id: String, skill_name: String, session_token: String) -> Element {
+    let mut feed_path = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+    let token_for_load = session_token.clone();
+    use_future(move || {
+        let session_token = token_for_load.clone();
+        async move {
+            if let Ok(path) = get_calendar_feed_path(session_token).await {
+                feed_path.set(path);
+            }
+        }
+    });
+
+    let feed_url = feed_path().map(|path| format!("{}{}", page_origin().unwrap_or_default(), path));
+    let token_for_export = session_token.clone();
+    let token_for_create = session_token.clone();
+    let token_for_rotate = session_token.clone();
+    let token_for_revoke = session_token;
+    let filename = format!("{}.ics", file_slug(&skill_name));
+
+    rsx! {
+        div {
+            h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-4 flex items-center gap-2",
+                span { "📅" }
+                "Calendar"
+            }
+            div { class: "space-y-3",
+                button {
+                    class: "w-full px-3 py-2 rounded-lg border border-white/10 bg-[#1a1b1e] text-xs font-medium text-gray-300 hover:text-white hover:border-teal-500/30 transition",
+                    onclick: move |_| {
+                        let session_token = token_for_export.clone();
+                        let roadmap_id = roadmap_id.clone();
+                        let filename = filename.clone();
+                        spawn(async move {
+                            match export_roadmap_ics(session_token, roadmap_id).await {
+                                Ok(ics) => {
+                                    download_text_file(&filename, "text/calendar", &ics);
+                                }
+                                Err(e) => error.set(Some(format!("Export failed: {e}"))),
+                            }
+                        });
+                    },
+                    "Download .ics"
+                }
+
+                if let Some(url) = feed_url {
+                    div { class: "space-y-2",
+                        p { class: "text-[11px] text-gray-500",
+                            "Subscribe to this URL to keep all your roadmaps in your calendar:"
+                        }
+                        input {
+                            readonly: true,
+                            class: "w-full px-3 py-2 bg-[#050505] text-gray-300 text-[11px] font-mono border border-white/10 rounded-lg outline-none",
+                            value: "{url}",
+                        }
+                        div { class: "flex gap-2",
+                            button {
+                                class: "flex-1 px-3 py-1.5 rounded-md text-[11px] text-gray-400 bg-white/5 hover:bg-white/10 transition",
+                                onclick: move |_| {
+                                    let session_token = token_for_rotate.clone();
+                                    spawn(async move {
+                                        match rotate_calendar_feed(session_token).await {
+                                            Ok(path) => feed_path.set(Some(path)),
+                                            Err(e) => error.set(Some(format!("Couldn't reset the feed: {e}"))),
+                                        }
+                                    });
+                                },
+                                "New URL"
+                            }
+                            button {
+                                class: "flex-1 px-3 py-1.5 rounded-md text-[11px] text-red-400 bg-red-500/10 hover:bg-red-500/20 transition",
+                                onclick: move |_| {
+                                    let session_token = token_for_revoke.clone();
+                                    spawn(async move {
+                                        match revoke_calendar_feed(session_token).await {
+                                            Ok(()) => feed_path.set(None),
+                                            Err(e) => error.set(Some(format!("Couldn't revoke the feed: {e}"))),
+                                        }
+                                    });
+                                },
+                                "Revoke"
+                            }
+                        }
+                    }
+                } else {
+                    button {
+                        class: "w-full px-3 py-2 rounded-lg border border-white/10 bg-[#1a1b1e] text-xs font-medium text-gray-300 hover:text-white hover:border-teal-500/30 transition",
+                        onclick: move |_| {
+                            let session_token = token_for_create.clone();
+                            spawn(async move {
+                                match rotate_calendar_feed(session_token).await {
+                                    Ok(path) => feed_path.set(Some(path)),
+                                    Err(e) => error.set(Some(format!("Couldn't create the feed: {e}"))),
+                                }
+                            });
+                        },
+                        "Create calendar feed"
+                    }
+                }
+
+                if let Some(err) = error() {
+                    p { class: "text-xs text-red-400", "{err}" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SharePanel(roadmap_id: String, session_token: String) -> Element {
+    let mut links = use_signal(Vec::<ShareLink>::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut expires_in_days = use_signal(|| None::<u32>);
+    let mut include_progress = use_signal(|| false);
+    let mut include_owner = use_signal(|| false);
+
+    let roadmap_id_for_load = roadmap_id.clone();
+    let token_for_load = session_token.clone();
+    use_future(move || {
+        let roadmap_id = roadmap_id_for_load.clone();
+        let session_token = token_for_load.clone();
+        async move {
+            if let Ok(existing) = list_share_links(session_token, roadmap_id).await {
+                links.set(existing);
+            }
+        }
+    });
+
+    let origin = page_origin().unwrap_or_default();
+    let now = Utc::now();
+    let roadmap_id_for_create = roadmap_id.clone();
+    let token_for_create = session_token.clone();
+    let toggle_class = |on: bool| {
+        if on {
+            "bg-teal-500/20 border-teal-500/40 text-teal-300"
+        } else {
+            "bg-[#1a1b1e] border-white/10 text-gray-400 hover:text-gray-200"
+        }
+    };
+    let progress_class = toggle_class(include_progress());
+    let owner_class = toggle_class(include_owner());
+
+    rsx! {
+        div {
+            h3 { class: "text-xs font-bold text-gray-500 uppercase tracking-widest mb-4 flex items-center gap-2",
+                span { "🔗" }
+                "Share"
+            }
+            div { class: "space-y-3",
+                select {
+                    class: "w-full px-3 py-2 bg-[#050505] text-gray-300 text-xs border border-white/10 rounded-lg outline-none",
+                    onchange: move |e| expires_in_days.set(e.value().parse().ok()),
+                    option { value: "never", "Never expires" }
+                    option { value: "1", "Expires in 1 day" }
+                    option { value: "7", "Expires in 7 days" }
+                    option { value: "30", "Expires in 30 days" }
+                }
+                div { class: "grid grid-cols-2 gap-2",
+                    button {
+                        class: "px-2 py-2 rounded-lg border text-[11px] font-medium transition {progress_class}",
+                        onclick: move |_| include_progress.set(!include_progress()),
+                        "Show my progress"
+                    }
+                    button {
+                        class: "px-2 py-2 rounded-lg border text-[11px] font-medium transition {owner_class}",
+                        onclick: move |_| include_owner.set(!include_owner()),
+                        "Show my name"
+                    }
+                }
+                button {
+                    class: "w-full px-3 py-2 rounded-lg border border-white/10 bg-[#1a1b1e] text-xs font-medium text-gray-300 hover:text-white hover:border-teal-500/30 transition",
+                    onclick: move |_| {
+                        let roadmap_id = roadmap_id_for_create.clone();
+                        let session_token = token_for_create.clone();
+                        spawn(async move {
+                            let created = create_share_link(
+                                session_token,
+                                roadmap_id,
+                                expires_in_days(),
+                                include_progress(),
+                                include_owner(),
+                            )
+                            .await;
+                            match created {
+                                Ok(link) => {
+                                    links.write().retain(|l| l.is_active(Utc::now()));
+                                    links.write().push(link);
+                                }
+                                Err(e) => error.set(Some(format!("Couldn't create the link: {e}"))),
+                            }
+                        });
+                    },
+                    "Create share link"
+                }
+
+                for link in links() {
+                    {
+                        let url = format!("{origin}/share/{}", link.token);
+                        let status = match link.expires_at {
+                            Some(at) if at <= now => "Expired".to_string(),
+                            Some(at) => format!("Expires {}", at.format("%b %-d, %Y")),
+                            None => "Never expires".to_string(),
+                        };
+                        let visibility = match (link.include_progress, link.include_owner) {
+                            (true, true) => "progress and name visible",
+                            (true, false) => "progress visible",
+                            (false, true) => "name visible",
+                            (false, false) => "roadmap only",
+                        };
+                        let roadmap_id = roadmap_id.clone();
+                        let session_token = session_token.clone();
+                        let token = link.token.clone();
+                        rsx! {
+                            div { key: "{link.token}", class: "p-3 bg-white/5 border border-white/10 rounded-lg space-y-2",
+                                input {
+                                    readonly: true,
+                                    class: "w-full px-3 py-2 bg-[#050505] text-gray-300 text-[11px] font-mono border border-white/10 rounded-lg outline-none",
+                                    value: "{url}",
+                                }
+                                div { class: "flex items-center justify-between gap-2",
+                                    span { class: "text-[11px] text-gray-500", "{status} · {visibility}" }
+                                    button {
+                                        class: "px-3 py-1 rounded-md text-[11px] text-red-400 bg-red-500/10 hover:bg-red-500/20 transition",
+                                        onclick: move |_| {
+                                            let roadmap_id = roadmap_id.clone();
+                                            let session_token = session_token.clone();
+                                            let token = token.clone();
+                                            spawn(async move {
+                                                match revoke_share_link(session_token, roadmap_id, token.clone()).await {
+                                                    Ok(()) => links.write().retain(|l| l.token != token),
+                                                    Err(e) => error.set(Some(format!("Couldn't revoke the link: {e}"))),
+                                                }
+                                            });
+                                        },
+                                        "Revoke"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(err) = error() {
+                    p { class: "text-xs text-red-400", "{err}" }
+                }
+            }
+        }
+    }
+}
+
+/// Copies the roadmap into the viewer's account and opens the copy. Viewers without an
+/// account are pointed at sign-up instead.
+#[component]
+fn CloneRoadmapButton(source: CloneSource, session_token: String) -> Element {
+    let nav = navigator();
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let signed_in = !session_token.is_empty();
+    let label = match (&source, busy()) {
+        (_, true) => "Copying...",
+        (CloneSource::Owned { .. }, false) => "Make a copy",
+        (CloneSource::Shared { .. }, false) => "Clone to my roadmaps",
+    };
+
+    rsx! {
+        div {
+            if signed_in {
+                button {
+                    disabled: busy(),
+                    class: "w-full px-3 py-2 rounded-lg border border-white/10 bg-[#1a1b1e] text-xs font-medium text-gray-300 hover:text-white hover:border-teal-500/30 transition disabled:opacity-50",
+                    onclick: move |_| {
+                        let source = source.clone();
+                        let session_token = session_token.clone();
+                        spawn(async move {
+                            busy.set(true);
+                            error.set(None);
+                            match clone_roadmap(session_token, source).await {
+                                Ok(id) => {
+                                    nav.push(Route::RoadmapView { id });
+                                }
+                                Err(e) => error.set(Some(format!("Couldn't copy the roadmap: {e}"))),
+                            }
+                            busy.set(false);
+                        });
+                    },
+                    "{label}"
+                }
+            } else {
+                Link {
+                    to: Route::Signup {},
+                    class: "block w-full px-3 py-2 rounded-lg border border-teal-500/30 text-center text-xs font-medium text-teal-300 hover:bg-teal-500/10 transition",
+                    "Sign up to clone this roadmap"
+                }
+            }
+            if let Some(err) = error() {
+                p { class: "mt-2 text-xs text-red-400", "{err}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn RoadmapOverview(
+    roadmap: Roadmap,
+    roadmap_id: String,
+    session_token: String,
+    roadmap_resource: Resource<Result<Roadmap, ServerFnError>>,
+    weekly_hours: Option<f32>,
+    share_token: Option<String>,
+    show_progress: bool,
+    owner_name: Option<String>,
+) -> Element {
+    let read_only = share_token.is_some();
+    let schedule = weekly_hours.map(|h| roadmap.plan_schedule(h, Utc::now()));
+    let calendar_roadmap_id = roadmap_id.clone();
+    let calendar_session_token = session_token.clone();
+    let export_roadmap_id = roadmap_id.clone();
+    let export_session_token = session_token.clone();
+    let share_roadmap_id = roadmap_id.clone();
+    let share_session_token = session_token.clone();
+    let clone_source = match share_token {
+        Some(token) => CloneSource::Shared { token },
+        None => CloneSource::Owned {
+            roadmap_id: roadmap_id.clone(),
+        },
+    };
+    let clone_session_token = session_token.clone();
+    let cloned_from = roadmap.origin.as_ref().map(|origin| {
+        let verb = if origin.from_assignment {
+            "Assigned by your team"
+        } else {
+            "Cloned"
+        };
+        format!(
+            "{verb} from “{}” on {}",
+            origin.skill_name,
+            origin.cloned_at.format("%b %-d, %Y")
+        )
+    });
+    let require_quiz = roadmap.require_mastery_quiz;
+    let toggle_class = if require_quiz {
+        "bg-teal-500/20 border-teal-500/40 text-teal-300"
+    } else {
+        "bg-[#1a1b1e] border-white/10 text-gray-400 hover:text-gray-200"
+    };
     let completed = roadmap.nodes.iter().filter(|n| n.is_completed).count();
     let total = roadmap.nodes.len();
     let progress = if total > 0 {
@@ -616,50 +1378,84 @@ fn RoadmapOverview(roadmap: Roadmap) -> Element {
         div { class: "h-full flex flex-col",
             // Sticky Header Section (Progress Circle)
             div { class: "p-8 pb-6 shrink-0 flex flex-col items-center text-center border-b border-white/5 bg-[#0b0c0e]",
-                div { class: "relative w-32 h-32 mb-6",
-                    // Circular Progress SVG
-                    svg {
-                        class: "w-full h-full -rotate-90",
-                        view_box: "0 0 100 100",
-                        circle {
-                            class: "text-[#1a1b1e] stroke-current",
-                            stroke_width: "8",
-                            cx: "50",
-                            cy: "50",
-                            r: "40",
-                            fill: "none",
-                        }
-                        circle {
-                            class: "text-teal-500 stroke-current transition-all duration-1000 ease-out",
-                            stroke_width: "8",
-                            stroke_linecap: "round",
-                            cx: "50",
-                            cy: "50",
-                            r: "40",
-                            fill: "none",
-                            stroke_dasharray: "251.2",
-                            stroke_dashoffset: format!("{}", 251.2 - (251.2 * progress as f32 / 100.0)),
-                        }
-                    }
-                    div { class: "absolute inset-0 flex flex-col items-center justify-center",
-                        span { class: "text-2xl font-bold text-white", "{progress}%" }
+                if show_progress {
+                    div { class: "relative w-32 h-32 mb-6",
+                        // Circular Progress SVG
+                        svg {
+                            class: "w-full h-full -rotate-90",
+                            view_box: "0 0 100 100",
+                            circle {
+                                class: "text-[#1a1b1e] stroke-current",
+                                stroke_width: "8",
+                                cx: "50",
+                                cy: "50",
+                                r: "40",
+                                fill: "none",
+                            }
+                            circle {
+                                class: "text-teal-500 stroke-current transition-all duration-1000 ease-out",
+                                stroke_width: "8",
+                                stroke_linecap: "round",
+                                cx: "50",
+                                cy: "50",
+                                r: "40",
+                                fill: "none",
+                                stroke_dasharray: "251.2",
+                                stroke_dashoffset: format!("{}", 251.2 - (251.2 * progress as f32 / 100.0)),
+                            }
+                        }
+                        div { class: "absolute inset-0 flex flex-col items-center justify-center",
+                            span { class: "text-2xl font-bold text-white", "{progress}%" }
+                        }
                     }
                 }
 
                 h2 { class: "text-xl font-bold text-gray-100 mb-2", "Roadmap Overview" }
+                if let Some(name) = owner_name {
+                    p { class: "text-xs text-gray-500", "Shared by {name}" }
+                }
+                if let Some(cloned_from) = cloned_from {
+                    p { class: "text-xs text-gray-500", "{cloned_from}" }
+                }
 
-                // Stats Grid
-                div { class: "grid grid-cols-2 gap-3 w-full mt-4",
-                    div { class: "p-3 bg-[#1a1b1e] rounded-lg border border-white/5",
-                        div { class: "text-xl font-bold text-teal-400", "{completed}" }
-                        div { class: "text-[10px] text-gray-500 uppercase tracking-wider",
-                            "Completed"
+                if show_progress {
+                    // Stats Grid
+                    div { class: "grid grid-cols-2 gap-3 w-full mt-4",
+                        div { class: "p-3 bg-[#1a1b1e] rounded-lg border border-white/5",
+                            div { class: "text-xl font-bold text-teal-400", "{completed}" }
+                            div { class: "text-[10px] text-gray-500 uppercase tracking-wider",
+                                "Completed"
+                            }
+                        }
+                        div { class: "p-3 bg-[#1a1b1e] rounded-lg border border-white/5",
+                            div { class: "text-xl font-bold text-gray-300", "{total - completed}" }
+                            div { class: "text-[10px] text-gray-500 uppercase tracking-wider",
+                                "Remaining"
+                            }
                         }
                     }
-                    div { class: "p-3 bg-[#1a1b1e] rounded-lg border border-white/5",
-                        div { class: "text-xl font-bold text-gray-300", "{total - completed}" }
-                        div { class: "text-[10px] text-gray-500 uppercase tracking-wider",
-                            "Remaining"
+                }
+
+                if !read_only {
+                    button {
+                        class: "w-full mt-3 px-3 py-2 rounded-lg border text-xs font-medium transition {toggle_class}",
+                        title: "When on, skills can only be completed by passing a short quiz on the linked material",
+                        onclick: move |_| {
+                            let session_token = session_token.clone();
+                            let roadmap_id = roadmap_id.clone();
+                            spawn(async move {
+                                if set_mastery_quiz_required(session_token, roadmap_id, !require_quiz)
+                                    .await
+                                    .is_ok()
+                                {
+                                    roadmap_resource.restart();
+                                }
+                            });
+                        },
+                        if require_quiz {
+                            "Mastery quizzes required ✓"
+                        } else {
+                            "Require mastery quizzes"
                         }
                     }
                 }
@@ -668,6 +1464,31 @@ fn RoadmapOverview(roadmap: Roadmap) -> Element {
             // Scrollable Details Section
             div { class: "flex-1 overflow-y-auto custom-scroll p-8 space-y-8",
 
+                // Schedule Section
+                if let Some(schedule) = schedule {
+                    RoadmapSchedulePanel { schedule }
+                }
+
+                if !read_only {
+                    CalendarPanel {
+                        roadmap_id: calendar_roadmap_id,
+                        skill_name: roadmap.skill_name.clone(),
+                        session_token: calendar_session_token,
+                    }
+
+                    ExportPanel {
+                        roadmap_id: export_roadmap_id,
+                        session_token: export_session_token,
+                    }
+
+                    SharePanel {
+                        roadmap_id: share_roadmap_id,
+                        session_token: share_session_token,
+                    }
+                }
+
+                CloneRoadmapButton { source: clone_source, session_token: clone_session_token }
+
                 // Learning Outcomes Section
                 if !roadmap.learning_outcomes.is_empty() {
                     div {
@@ -1,4 +1,4 @@
-use crate::{server_functions::signup_user, Route};
+use crate::{Route, server_functions::signup_user};
 use dioxus::prelude::*;
 
 #[component]
@@ -0,0 +1,98 @@
+//! Ranking the skills a learner could start next.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    RECOMMENDATION_IN_PROGRESS_FACTOR, RECOMMENDATION_READINESS_WEIGHT,
+    RECOMMENDATION_SIMILARITY_WEIGHT, RECOMMENDATION_TEAM_SATURATION, RECOMMENDATION_TEAM_WEIGHT,
+    models::{SkillRecommendation, Taxonomy},
+    utils::topic_label,
+};
+
+/// What is known about a learner, with every skill already mapped to a corpus topic.
+#[derive(Debug, Default)]
+pub struct LearnerSignals {
+    /// Topics the learner has learned or finished a roadmap step on.
+    pub known: HashSet<String>,
+    /// Topics on roadmaps the learner is still working through; still suggested, lower down.
+    pub in_progress: HashSet<String>,
+    /// Best similarity of each topic's courses to the learner's skills, 0.0-1.0.
+    pub similarity: HashMap<String, f32>,
+    /// Assignments across the learner's teams and organizations covering each topic.
+    pub team_demand: HashMap<String, usize>,
+}
+
+/// Topics the learner hasn't finished and either has every prerequisite for or has been
+/// asked to learn by a team, best first.
+pub fn recommend(
+    taxonomy: &Taxonomy,
+    signals: &LearnerSignals,
+    limit: usize,
+) -> Vec<SkillRecommendation> {
+    let mut ranked: Vec<(SkillRecommendation, usize)> = taxonomy
+        .topics
+        .iter()
+        .filter(|t| t.course_count > 0)
+        .filter(|t| !signals.known.contains(&t.topic))
+        .filter_map(|t| {
+            let known_prerequisites: Vec<String> = t
+                .prerequisites
+                .iter()
+                .filter(|p| signals.known.contains(*p))
+                .cloned()
+                .collect();
+            let readiness = if t.prerequisites.is_empty() {
+                1.0
+            } else {
+                known_prerequisites.len() as f32 / t.prerequisites.len() as f32
+            };
+            let team_demand = signals.team_demand.get(&t.topic).copied().unwrap_or(0);
+            if readiness < 1.0 && team_demand == 0 {
+                return None;
+            }
+
+            let similarity = signals
+                .similarity
+                .get(&t.topic)
+                .copied()
+                .unwrap_or(0.0)
+                .clamp(0.0, 1.0);
+            let demand = team_demand.min(RECOMMENDATION_TEAM_SATURATION) as f32
+                / RECOMMENDATION_TEAM_SATURATION as f32;
+            let in_progress = signals.in_progress.contains(&t.topic);
+            let mut score = RECOMMENDATION_READINESS_WEIGHT * readiness
+                + RECOMMENDATION_SIMILARITY_WEIGHT * similarity
+                + RECOMMENDATION_TEAM_WEIGHT * demand;
+            if in_progress {
+                score *= RECOMMENDATION_IN_PROGRESS_FACTOR;
+            }
+
+            Some((
+                SkillRecommendation {
+                    topic: t.topic.clone(),
+                    skill_name: topic_label(&t.topic),
+                    score,
+                    readiness,
+                    similarity,
+                    team_demand,
+                    in_progress,
+                    known_prerequisites,
+                },
+                t.course_count,
+            ))
+        })
+        .collect();
+
+    // Ties go to the better-taught topic.
+    ranked.sort_by(|(a, a_courses), (b, b_courses)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b_courses.cmp(a_courses))
+            .then(a.topic.cmp(&b.topic))
+    });
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(recommendation, _)| recommendation)
+        .collect()
+}
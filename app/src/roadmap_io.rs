@@ -0,0 +1,702 @@
+//! Rendering roadmaps into shareable files, and reading them back in.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    ROADMAP_EXPORT_VERSION,
+    models::{ExportFormat, ExportedFile, ExportedNode, Roadmap, RoadmapExport, RoadmapNode},
+    utils::{file_slug, format_hours, platform_for_url},
+};
+
+pub const EXPORT_FORMAT_NAME: &str = "skillforge-roadmap";
+/// Most effort an imported step may claim, in hours; anything above is taken as a typo.
+const MAX_IMPORTED_STEP_HOURS: f32 = 10_000.0;
+
+pub fn export_roadmap(
+    roadmap: &Roadmap,
+    format: ExportFormat,
+    now: DateTime<Utc>,
+) -> Result<ExportedFile> {
+    let contents = match format {
+        ExportFormat::Markdown => to_markdown(roadmap),
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&RoadmapExport::from_roadmap(roadmap, now))?
+        }
+        ExportFormat::Html => to_html(roadmap, now),
+    };
+    Ok(ExportedFile {
+        filename: format!("{}.{}", file_slug(&roadmap.skill_name), format.extension()),
+        mime: format.mime().to_string(),
+        contents,
+    })
+}
+
+impl RoadmapExport {
+    pub fn from_roadmap(roadmap: &Roadmap, now: DateTime<Utc>) -> Self {
+        RoadmapExport {
+            format: EXPORT_FORMAT_NAME.to_string(),
+            format_version: ROADMAP_EXPORT_VERSION,
+            exported_at: now,
+            skill_name: roadmap.skill_name.clone(),
+            learning_outcomes: roadmap.learning_outcomes.clone(),
+            skills_prev_known: roadmap.skills_prev_known.clone(),
+            require_mastery_quiz: roadmap.require_mastery_quiz,
+            nodes: roadmap
+                .topological_nodes()
+                .into_iter()
+                .map(|n| ExportedNode {
+                    key: n.id.clone(),
+                    skill_name: n.skill_name.clone(),
+                    description: n.description.clone(),
+                    prerequisites: n.prerequisites.clone(),
+                    prev: n.prev_node_id.clone(),
+                    next: n.next_node_id.clone(),
+                    estimated_hours: n.estimated_hours,
+                    is_completed: n.is_completed,
+                    resources: n.resources.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Turns the exported nodes back into `RoadmapNode`s with fresh IDs, rewriting every
+    /// key reference. Fails on repeated keys and on references to keys that aren't in the
+    /// export. Completion carries over so an export reads back as it was; mastery scores,
+    /// which the export doesn't hold, don't.
+    pub fn into_nodes(self) -> Result<Vec<RoadmapNode>> {
+        let mut ids: HashMap<&str, String> = HashMap::new();
+        for node in &self.nodes {
+            if ids
+                .insert(node.key.as_str(), Uuid::new_v4().to_string())
+                .is_some()
+            {
+                anyhow::bail!("More than one step has the key '{}'", node.key);
+            }
+        }
+        let remap = |node: &ExportedNode, key: &str| {
+            ids.get(key).cloned().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "'{}' refers to a step that is not in the roadmap ('{key}')",
+                    node.skill_name
+                )
+            })
+        };
+
+        self.nodes
+            .iter()
+            .map(|n| {
+                Ok(RoadmapNode {
+                    id: ids[n.key.as_str()].clone(),
+                    skill_name: n.skill_name.clone(),
+                    description: n.description.clone(),
+                    resources: n.resources.clone(),
+                    prerequisites: n
+                        .prerequisites
+                        .iter()
+                        .map(|p| remap(n, p))
+                        .collect::<Result<_>>()?,
+                    is_completed: n.is_completed,
+                    mastery_score: None,
+                    estimated_hours: n.estimated_hours,
+                    prev_node_id: n.prev.as_deref().map(|p| remap(n, p)).transpose()?,
+                    next_node_id: n.next.as_deref().map(|p| remap(n, p)).transpose()?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn node_names(roadmap: &Roadmap) -> HashMap<&str, &str> {
+    roadmap
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.skill_name.as_str()))
+        .collect()
+}
+
+fn resource_meta(resource: &crate::models::LearningResource) -> String {
+    let mut meta = vec![resource.platform.clone(), resource.resource_type.clone()];
+    if let Some(minutes) = resource.estimated_minutes {
+        meta.push(format!("{minutes} min"));
+    }
+    meta.retain(|m| !m.trim().is_empty());
+    meta.join(" · ")
+}
+
+/// One `##` section per node, in dependency order, with the node's resources as a
+/// checklist underneath.
+pub fn to_markdown(roadmap: &Roadmap) -> String {
+    let names = node_names(roadmap);
+    let mut out = format!("# {}\n\n", roadmap.skill_name);
+
+    if !roadmap.learning_outcomes.is_empty() {
+        out.push_str("**Learning outcomes**\n\n");
+        for outcome in &roadmap.learning_outcomes {
+            out.push_str(&format!("- {outcome}\n"));
+        }
+        out.push('\n');
+    }
+    if !roadmap.skills_prev_known.is_empty() {
+        out.push_str(&format!(
+            "**Assumes:** {}\n\n",
+            roadmap.skills_prev_known.join(", ")
+        ));
+    }
+
+    for (idx, node) in roadmap.topological_nodes().into_iter().enumerate() {
+        let done = if node.is_completed { " ✅" } else { "" };
+        out.push_str(&format!("## {}. {}{}\n\n", idx + 1, node.skill_name, done));
+        if !node.description.trim().is_empty() {
+            out.push_str(&format!("{}\n\n", node.description.trim()));
+        }
+
+        let prereqs: Vec<&str> = node
+            .prerequisites
+            .iter()
+            .filter_map(|p| names.get(p.as_str()).copied())
+            .collect();
+        let mut facts = vec![format!("Effort: {}", format_hours(node.effort_hours()))];
+        if !prereqs.is_empty() {
+            facts.push(format!("After: {}", prereqs.join(", ")));
+        }
+        out.push_str(&format!("_{}_\n\n", facts.join(" · ")));
+
+        for resource in &node.resources {
+            let check = if node.is_completed { "x" } else { " " };
+            let title = match resource.url.as_deref().filter(|u| !u.is_empty()) {
+                Some(url) => format!("[{}]({})", resource.title, url),
+                None => resource.title.clone(),
+            };
+            let meta = resource_meta(resource);
+            if meta.is_empty() {
+                out.push_str(&format!("- [{check}] {title}\n"));
+            } else {
+                out.push_str(&format!("- [{check}] {title} — {meta}\n"));
+            }
+        }
+        if !node.resources.is_empty() {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn is_web_url(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+    })
+}
+
+const PRINT_CSS: &str = "\
+body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;max-width:780px;margin:2rem auto;padding:0 1rem;color:#1a1a1a;line-height:1.5}\
+h1{margin-bottom:.25rem}\
+.meta{color:#666;font-size:.85rem}\
+section.node{border-top:1px solid #ddd;padding:1rem 0;break-inside:avoid}\
+section.node h2{font-size:1.1rem;margin:0 0 .25rem}\
+.done{color:#1a7f37}\
+ul.resources{list-style:none;padding-left:0}\
+ul.resources li::before{content:'☐ ';}\
+ul.resources li.done::before{content:'☑ ';}\
+a{color:#0b6bcb}\
+@media print{a{color:inherit;text-decoration:none}a[href]::after{content:' (' attr(href) ')';font-size:.8em;color:#666}}";
+
+/// A single HTML file with inline styles, laid out for printing.
+pub fn to_html(roadmap: &Roadmap, now: DateTime<Utc>) -> String {
+    let names = node_names(roadmap);
+    let title = html_escape(&roadmap.skill_name);
+    let completed = roadmap.nodes.iter().filter(|n| n.is_completed).count();
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{PRINT_CSS}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"meta\">{completed} of {} steps complete · exported {}</p>\n",
+        roadmap.nodes.len(),
+        now.format("%b %d, %Y")
+    );
+
+    if !roadmap.learning_outcomes.is_empty() {
+        out.push_str("<h3>Learning outcomes</h3>\n<ul>\n");
+        for outcome in &roadmap.learning_outcomes {
+            out.push_str(&format!("<li>{}</li>\n", html_escape(outcome)));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    for (idx, node) in roadmap.topological_nodes().into_iter().enumerate() {
+        let done_class = if node.is_completed { " done" } else { "" };
+        out.push_str(&format!(
+            "<section class=\"node\">\n<h2 class=\"{}\">{}. {}{}</h2>\n",
+            done_class.trim(),
+            idx + 1,
+            html_escape(&node.skill_name),
+            if node.is_completed { " ✓" } else { "" }
+        ));
+
+        let prereqs: Vec<String> = node
+            .prerequisites
+            .iter()
+            .filter_map(|p| names.get(p.as_str()))
+            .map(|n| html_escape(n))
+            .collect();
+        let mut meta = format!("Effort: {}", format_hours(node.effort_hours()));
+        if !prereqs.is_empty() {
+            meta.push_str(&format!(" · After: {}", prereqs.join(", ")));
+        }
+        out.push_str(&format!("<p class=\"meta\">{meta}</p>\n"));
+        if !node.description.trim().is_empty() {
+            out.push_str(&format!(
+                "<p>{}</p>\n",
+                html_escape(node.description.trim())
+            ));
+        }
+
+        if !node.resources.is_empty() {
+            out.push_str("<ul class=\"resources\">\n");
+            for resource in &node.resources {
+                let title = html_escape(&resource.title);
+                // Only web links are clickable; anything else (javascript:, data:, ...) is
+                // shown as text so an imported roadmap can't plant script in the page.
+                let link = match resource
+                    .url
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|u| !u.is_empty())
+                {
+                    Some(url) if is_web_url(url) => {
+                        format!("<a href=\"{}\">{}</a>", html_escape(url), title)
+                    }
+                    Some(url) => format!("{title} ({})", html_escape(url)),
+                    None => title,
+                };
+                let meta = resource_meta(resource);
+                let meta = if meta.is_empty() {
+                    String::new()
+                } else {
+                    format!(" <span class=\"meta\">— {}</span>", html_escape(&meta))
+                };
+                out.push_str(&format!(
+                    "<li class=\"{}\">{link}{meta}</li>\n",
+                    done_class.trim()
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// A roadmap read from an import file, ready to be checked and saved.
+#[derive(Debug, Clone)]
+pub struct ImportedRoadmap {
+    pub skill_name: String,
+    pub learning_outcomes: Vec<String>,
+    pub skills_prev_known: Vec<String>,
+    pub require_mastery_quiz: bool,
+    pub nodes: Vec<RoadmapNode>,
+}
+
+/// Reads either the JSON export format or a Markdown outline, chosen by content.
+pub fn parse_import(contents: &str) -> Result<ImportedRoadmap> {
+    let trimmed = contents.trim_start();
+    let imported = if trimmed.starts_with('{') {
+        parse_json_import(trimmed)?
+    } else {
+        parse_markdown_import(contents)?
+    };
+    if imported.nodes.is_empty() {
+        anyhow::bail!("The file does not contain any roadmap steps");
+    }
+    validate_prerequisites(&imported.nodes)?;
+    validate_effort(&imported.nodes)?;
+    Ok(imported)
+}
+
+/// Checks that every effort estimate is a number of hours scheduling can work with.
+fn validate_effort(nodes: &[RoadmapNode]) -> Result<()> {
+    for node in nodes {
+        if let Some(hours) = node.estimated_hours
+            && !(hours.is_finite() && (0.0..=MAX_IMPORTED_STEP_HOURS).contains(&hours))
+        {
+            anyhow::bail!(
+                "'{}' has an effort of {hours} hours; expected 0 to {MAX_IMPORTED_STEP_HOURS}",
+                node.skill_name
+            );
+        }
+        for resource in &node.resources {
+            if let Some(minutes) = resource.estimated_minutes
+                && minutes as f32 > MAX_IMPORTED_STEP_HOURS * 60.0
+            {
+                anyhow::bail!(
+                    "'{}' in '{}' runs for {minutes} minutes, more than a step can take",
+                    resource.title,
+                    node.skill_name
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_json_import(contents: &str) -> Result<ImportedRoadmap> {
+    let export: RoadmapExport = serde_json::from_str(contents)
+        .map_err(|e| anyhow::anyhow!("Not a valid roadmap export: {e}"))?;
+    if export.format != EXPORT_FORMAT_NAME {
+        anyhow::bail!("Unknown export format '{}'", export.format);
+    }
+    if export.format_version > ROADMAP_EXPORT_VERSION {
+        anyhow::bail!(
+            "Export format version {} is newer than this server supports ({})",
+            export.format_version,
+            ROADMAP_EXPORT_VERSION
+        );
+    }
+    Ok(ImportedRoadmap {
+        skill_name: export.skill_name.clone(),
+        learning_outcomes: export.learning_outcomes.clone(),
+        skills_prev_known: export.skills_prev_known.clone(),
+        require_mastery_quiz: export.require_mastery_quiz,
+        nodes: export.into_nodes()?,
+    })
+}
+
+struct OutlineNode {
+    name: String,
+    description: Vec<String>,
+    after: Vec<String>,
+    hours: Option<f32>,
+    resources: Vec<crate::models::LearningResource>,
+}
+
+impl OutlineNode {
+    fn new(name: String) -> Self {
+        OutlineNode {
+            name,
+            description: Vec::new(),
+            after: Vec::new(),
+            hours: None,
+            resources: Vec::new(),
+        }
+    }
+}
+
+/// Strips export decorations ("3. ", "✅") from a heading or bullet used as a node name.
+fn clean_node_name(raw: &str) -> String {
+    let name = raw.trim().trim_end_matches('✅').trim();
+    let without_number = name
+        .split_once(". ")
+        .filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        .map(|(_, rest)| rest)
+        .unwrap_or(name);
+    strip_checkbox(without_number).trim().to_string()
+}
+
+fn strip_checkbox(text: &str) -> &str {
+    for prefix in ["[ ] ", "[x] ", "[X] "] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            return rest;
+        }
+    }
+    text
+}
+
+/// Splits a list line into (indent, item text), or `None` if it is not a bullet.
+fn bullet(line: &str) -> Option<(usize, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let trimmed = line.trim_start();
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+        .map(|item| (indent, strip_checkbox(item.trim())))
+}
+
+/// Reads a resource from a bullet such as `[Title](url) — YouTube · Video · 12 min`,
+/// `Title https://...` or a bare URL.
+fn parse_resource(item: &str) -> crate::models::LearningResource {
+    let (main, meta) = match item.split_once(" — ") {
+        Some((main, meta)) => (main.trim(), Some(meta.trim())),
+        None => (item.trim(), None),
+    };
+
+    let (title, url) = if let Some(rest) = main.strip_prefix('[') {
+        match rest.split_once("](") {
+            Some((title, tail)) => (
+                title.to_string(),
+                tail.split_once(')').map(|(url, _)| url.trim().to_string()),
+            ),
+            None => (main.to_string(), None),
+        }
+    } else if let Some(pos) = main.find("http://").or_else(|| main.find("https://")) {
+        let url = main[pos..]
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_string();
+        let title = main[..pos].trim().trim_end_matches([':', '-', '(']).trim();
+        let title = if title.is_empty() {
+            url.clone()
+        } else {
+            title.to_string()
+        };
+        (title, Some(url))
+    } else {
+        (main.to_string(), None)
+    };
+
+    let mut platform = String::new();
+    let mut resource_type = String::new();
+    let mut estimated_minutes = None;
+    for part in meta.into_iter().flat_map(|m| m.split(" · ")).map(str::trim) {
+        if let Some(minutes) = part
+            .strip_suffix(" min")
+            .and_then(|m| m.trim().parse().ok())
+        {
+            estimated_minutes = Some(minutes);
+        } else if platform.is_empty() {
+            platform = part.to_string();
+        } else if resource_type.is_empty() {
+            resource_type = part.to_string();
+        }
+    }
+    if platform.is_empty() {
+        platform = platform_for_url(url.as_deref().unwrap_or_default());
+    }
+    if resource_type.is_empty() {
+        resource_type = "Link".to_string();
+    }
+
+    crate::models::LearningResource {
+        title,
+        platform,
+        url,
+        resource_type,
+        estimated_minutes,
+    }
+}
+
+/// Parses the facts line written by `to_markdown` (`_Effort: 1.5 h · After: A, B_`) into
+/// the effort in hours and the names of the steps it comes after.
+fn parse_facts(line: &str) -> Option<(Option<f32>, Vec<String>)> {
+    let inner = line.trim().trim_matches('_');
+    if !inner.starts_with("Effort:") && !inner.starts_with("After:") {
+        return None;
+    }
+    let mut hours = None;
+    let mut after = Vec::new();
+    for part in inner.split(" · ") {
+        if let Some(effort) = part.strip_prefix("Effort:") {
+            let effort = effort.trim();
+            hours = if let Some(h) = effort.strip_suffix(" h") {
+                h.trim().parse().ok()
+            } else if let Some(m) = effort.strip_suffix(" min") {
+                m.trim().parse::<f32>().ok().map(|m| m / 60.0)
+            } else {
+                None
+            };
+        } else if let Some(names) = part.strip_prefix("After:") {
+            after = names
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+    }
+    Some((hours, after))
+}
+
+/// Headings (`##` and deeper) become nodes and the bullets under them become resources.
+/// Documents without such headings are read as a plain outline: top-level bullets are
+/// nodes and their nested bullets are resources. Steps depend on the ones listed in an
+/// `After:` line, or, if the document has none, on the step before them. Fails when an
+/// `After:` line names a step the document doesn't have.
+fn parse_markdown_import(contents: &str) -> Result<ImportedRoadmap> {
+    let has_node_headings = contents.lines().any(|l| l.trim_start().starts_with("## "));
+
+    let mut skill_name = String::new();
+    let mut learning_outcomes = Vec::new();
+    let mut skills_prev_known = Vec::new();
+    let mut outline: Vec<OutlineNode> = Vec::new();
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(title) = trimmed.strip_prefix("# ") {
+            if skill_name.is_empty() {
+                skill_name = title.trim().to_string();
+            }
+            continue;
+        }
+        if trimmed.starts_with("##") && has_node_headings {
+            let name = clean_node_name(trimmed.trim_start_matches('#'));
+            if !name.is_empty() {
+                outline.push(OutlineNode::new(name));
+            }
+            continue;
+        }
+        if let Some(assumes) = trimmed.strip_prefix("**Assumes:**") {
+            skills_prev_known = assumes
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+            continue;
+        }
+
+        match (bullet(line), outline.last_mut()) {
+            (Some((indent, item)), _) if !has_node_headings && indent == 0 => {
+                let name = clean_node_name(item);
+                if !name.is_empty() {
+                    outline.push(OutlineNode::new(name));
+                }
+            }
+            (Some((_, item)), Some(node)) => node.resources.push(parse_resource(item)),
+            (Some((_, item)), None) if has_node_headings => {
+                learning_outcomes.push(item.to_string());
+            }
+            (None, Some(node)) => match parse_facts(trimmed) {
+                Some((hours, after)) => {
+                    node.hours = hours;
+                    node.after = after;
+                }
+                None => node.description.push(trimmed.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    let ids: Vec<String> = outline.iter().map(|_| Uuid::new_v4().to_string()).collect();
+    let by_name: HashMap<String, String> = outline
+        .iter()
+        .zip(&ids)
+        .map(|(n, id)| (n.name.to_lowercase(), id.clone()))
+        .collect();
+    let explicit_order = outline.iter().any(|n| !n.after.is_empty());
+
+    let nodes = outline
+        .into_iter()
+        .enumerate()
+        .map(|(i, n)| {
+            let prerequisites = if explicit_order {
+                n.after
+                    .iter()
+                    .map(|name| {
+                        by_name.get(&name.to_lowercase()).cloned().ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "'{}' comes after '{name}', which is not a step in the roadmap",
+                                n.name
+                            )
+                        })
+                    })
+                    .collect::<Result<_>>()?
+            } else {
+                i.checked_sub(1)
+                    .map(|p| ids[p].clone())
+                    .into_iter()
+                    .collect()
+            };
+            Ok(RoadmapNode {
+                id: ids[i].clone(),
+                skill_name: n.name,
+                description: n.description.join("\n"),
+                resources: n.resources,
+                prerequisites,
+                is_completed: false,
+                mastery_score: None,
+                estimated_hours: n.hours,
+                prev_node_id: i.checked_sub(1).map(|p| ids[p].clone()),
+                next_node_id: ids.get(i + 1).cloned(),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(ImportedRoadmap {
+        skill_name: if skill_name.is_empty() {
+            "Imported roadmap".to_string()
+        } else {
+            skill_name
+        },
+        learning_outcomes,
+        skills_prev_known,
+        require_mastery_quiz: false,
+        nodes,
+    })
+}
+
+/// Checks that the prerequisite graph only points at nodes in the roadmap, has no
+/// self-references and no cycles.
+pub fn validate_prerequisites(nodes: &[RoadmapNode]) -> Result<()> {
+    let names: HashMap<&str, &str> = nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.skill_name.as_str()))
+        .collect();
+    if names.len() != nodes.len() {
+        anyhow::bail!("Roadmap steps must have unique IDs");
+    }
+
+    for node in nodes {
+        for prereq in &node.prerequisites {
+            if prereq == &node.id {
+                anyhow::bail!("'{}' lists itself as a prerequisite", node.skill_name);
+            }
+            if !names.contains_key(prereq.as_str()) {
+                anyhow::bail!(
+                    "'{}' depends on a step that is not in the roadmap",
+                    node.skill_name
+                );
+            }
+        }
+    }
+
+    // Kahn's algorithm: anything left unplaced sits on a cycle.
+    let mut remaining: HashMap<&str, usize> = nodes
+        .iter()
+        .map(|n| {
+            let distinct: HashSet<&String> = n.prerequisites.iter().collect();
+            (n.id.as_str(), distinct.len())
+        })
+        .collect();
+    let mut ready: Vec<&str> = remaining
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    let mut placed = 0;
+    while let Some(id) = ready.pop() {
+        placed += 1;
+        for node in nodes
+            .iter()
+            .filter(|n| n.prerequisites.iter().any(|p| p == id))
+        {
+            let count = remaining
+                .get_mut(node.id.as_str())
+                .expect("node is indexed");
+            *count -= 1;
+            if *count == 0 {
+                ready.push(node.id.as_str());
+            }
+        }
+    }
+    if placed < nodes.len() {
+        let stuck: Vec<&str> = nodes
+            .iter()
+            .filter(|n| remaining[n.id.as_str()] > 0)
+            .map(|n| n.skill_name.as_str())
+            .collect();
+        anyhow::bail!("Prerequisites form a cycle between: {}", stuck.join(", "));
+    }
+    Ok(())
+}
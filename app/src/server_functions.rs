@@ -3,32 +3,40 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Days, Duration, Utc};
 use dioxus::prelude::*;
 use std::collections::HashMap;
+use std::env;
 use std::str::FromStr;
-use std::{env, fs::File, io::BufReader};
 
 #[cfg(feature = "server")]
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
 
 #[cfg(feature = "server")]
-use surrealdb::{engine::local::RocksDb, RecordId, Surreal};
+use surrealdb::{RecordId, Surreal};
 
 #[cfg(feature = "server")]
-use bcrypt::{hash, verify, DEFAULT_COST};
+use bcrypt::{DEFAULT_COST, hash, verify};
 
 #[cfg(feature = "server")]
 use uuid::Uuid;
 
 #[cfg(feature = "server")]
-use base64::{engine::general_purpose, Engine as _};
+use base64::{Engine as _, engine::general_purpose};
 
 #[cfg(feature = "server")]
 use rand::Rng;
 
+#[cfg(feature = "server")]
+use course_schema::{Course, CourseReader};
+
 use crate::models::*;
-use crate::{LOAD_AND_EMBED_JSON, SESSION_DURATION_DAYS};
+use crate::{
+    ADMIN_PAGE_SIZE, CATALOG_PAGE_SIZE, CATALOG_SEARCH_CANDIDATES, LOAD_AND_EMBED_JSON,
+    MASTERY_PASS_SCORE, MASTERY_QUIZ_QUESTIONS, NODE_COMPLETION_SKILL_LEVEL, RECOMMENDATION_LIMIT,
+    RECOMMENDATION_NEIGHBOURS, RETRIEVAL_CANDIDATES, RETRIEVAL_QUALITY_WEIGHT,
+    RETRIEVAL_RESULTS_PER_QUERY, REVIEW_QUEUE_LIMIT, SESSION_DURATION_DAYS,
+};
 
 #[cfg(feature = "server")]
-const MODEL: EmbeddingModel = EmbeddingModel::ModernBertEmbedLarge;
+const MODEL: EmbeddingModel = course_store::EMBEDDING_MODEL;
 const LLM_MODEL: &str = "nvidia/nemotron-3-nano-30b-a3b:free";
 
 #[cfg(feature = "server")]
@@ -51,68 +59,76 @@ impl<T, E: std::fmt::Display> IntoServerError<T> for Result<T, E> {
 async fn get_db() -> Result<&'static Surreal<surrealdb::engine::local::Db>> {
     DB_INSTANCE
         .get_or_try_init(|| async {
-            let db = Surreal::new::<RocksDb>("skillforge")
-                .await
-                .context("Failed to connect to Database")?;
-
-            db.use_ns("main").use_db("main").await?;
-
-
-    if LOAD_AND_EMBED_JSON {
-                let file = File::open("../final_data.json")
-            .context("Failed to read file '../final_data.json' ")?;
-        let reader = BufReader::new(file);
-        let collection: JsonDataCollection =
-            serde_json::from_reader(reader).context("Couldn't parse data properly")?;
-        let mut model = TextEmbedding::try_new(InitOptions::new(MODEL))?;
-        let data_len = collection.data.len();
-        for (i, data) in collection.data.into_iter().enumerate() {
-            println!("Processing and storing: {i} / {data_len}");
-            let str_to_embed = format!(
-                "Title: {}, topic: {}, description: {}, content: {}, Skill Path: {}, Prerequisites: {}, level: {}, Topic Size : {}",
-                data.title,
-                data.topic,
-                data.description,
-                data.content,
-                data.skill_path,
-                data.prerequisite_topics.join(", "),
-                data.level,
-                data.ctype
-            );
-            let embedding_batch = model.embed(vec![str_to_embed], None)?;
-            let embedding = embedding_batch
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("Empty embedding returned"))?;
-            let data_to_insert = CoursesDataWithEmbeddings {
-                id: None,
-                title: data.title,
-                description: data.description,
-                topic: data.topic,
-                prerequisite_topics: data.prerequisite_topics,
-                channel_name: data.channel_name,
-                published_date: data.published_date,
-                skill_path: data.skill_path,
-                level: data.level,
-                ctype: data.ctype,
-                content: data.content,
-                embedding,
-                url: String::new(), // URL are added by a different module, as it was added in the later versions...
-            };
-            let res: Option<CoursesDataWithEmbeddings> = db.create("courses").content(data_to_insert).await?;
-            match res {
-                Some(_) => {}
-                None => println!("Failed creating the entry."),
+            let db = course_store::open("skillforge").await?;
+            // One vote of each kind per user and course, however the rows get written.
+            db.query(
+                "DEFINE INDEX IF NOT EXISTS resource_feedback_vote ON resource_feedback \
+                 FIELDS user_id, course_id, kind UNIQUE",
+            )
+            .await?
+            .check()?;
+
+            if LOAD_AND_EMBED_JSON {
+                let courses = CourseReader::open("../final_data.json")
+                    .context("Couldn't load '../final_data.json'")?;
+                if let Some(layout) = courses.migrated_from() {
+                    println!(
+                        "Migrating course data from the legacy '{}' layout",
+                        layout.key()
+                    );
+                }
+                course_store::ingest(&db, courses).await?;
+                println!("Data embedding and storage successfull");
             }
-        }
 
-        println!("Data embedding and storage successfull");
-    }
+            let stored: Option<Taxonomy> = db.select(taxonomy_id()).await?;
+            if LOAD_AND_EMBED_JSON || stored.is_none() {
+                rebuild_taxonomy(&db).await?;
+            }
             Ok(db)
         })
         .await
 }
 
+#[cfg(feature = "server")]
+fn course_embedding_text(course: &CoursesDataWithEmbeddings) -> String {
+    course_store::embedding_text(&Course::from(course))
+}
+
+#[cfg(feature = "server")]
+fn taxonomy_id() -> RecordId {
+    RecordId::from_table_key("taxonomy", "current")
+}
+
+/// Rebuilds the skill-path hierarchy and topic graph from the active courses. Takes the
+/// connection directly because it also runs while `get_db` is still initialising.
+#[cfg(feature = "server")]
+async fn rebuild_taxonomy(db: &Surreal<surrealdb::engine::local::Db>) -> Result<Taxonomy> {
+    let mut result = db
+        .query(
+            "SELECT skill_path, topic, prerequisite_topics FROM courses WHERE deactivated != true",
+        )
+        .await?;
+    let courses: Vec<crate::taxonomy::CourseTaxonomyFields> = result.take(0)?;
+    let taxonomy = crate::taxonomy::build_taxonomy(&courses, Utc::now());
+    db.query("UPSERT $id CONTENT $taxonomy")
+        .bind(("id", taxonomy_id()))
+        .bind(("taxonomy", taxonomy.clone()))
+        .await?
+        .check()?;
+    Ok(taxonomy)
+}
+
+#[cfg(feature = "server")]
+async fn load_taxonomy() -> Result<Taxonomy, ServerFnError> {
+    let db = get_db().await?;
+    let stored: Option<Taxonomy> = db.select(taxonomy_id()).await.into_server_error()?;
+    match stored {
+        Some(taxonomy) => Ok(taxonomy),
+        None => rebuild_taxonomy(db).await.into_server_error(),
+    }
+}
+
 // SESSION FUNCTIONS
 #[cfg(feature = "server")]
 fn generate_session_token() -> String {
@@ -169,6 +185,9 @@ async fn get_user_from_session(session_token: String) -> Result<Option<User>, Se
             .await
             .into_server_error()?
             .ok_or(ServerFnError::new("User not found"))?;
+        if user.locked {
+            return Ok(None);
+        }
         let user = User::from(user);
         Ok(Some(user))
     } else {
@@ -176,6 +195,20 @@ async fn get_user_from_session(session_token: String) -> Result<Option<User>, Se
     }
 }
 
+#[cfg(feature = "server")]
+async fn session_user(session_token: String) -> Result<(User, RecordId), ServerFnError> {
+    let user = get_user_from_session(session_token)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Invalid or expired session"))?;
+    let user_id: RecordId = user
+        .id
+        .clone()
+        .ok_or(ServerFnError::new("User ID not found"))?
+        .parse()
+        .into_server_error()?;
+    Ok((user, user_id))
+}
+
 #[server]
 pub async fn delete_session(session_token: String) -> Result<(), ServerFnError> {
     let db = get_db().await.into_server_error()?;
@@ -209,6 +242,8 @@ pub async fn signup_user(
         name,
         skills_learned: Vec::new(),
         preferences: UserPreferences::default(),
+        role: UserRole::default(),
+        locked: false,
         created_at: Utc::now(),
     });
 
@@ -228,6 +263,15 @@ pub async fn signup_user(
     Ok(user.id.unwrap_or_else(String::new))
 }
 
+/// Usernames listed in `SKILLFORGE_ADMINS` (comma separated) are promoted to admin when
+/// they sign in, which is how the first admin gets in.
+#[cfg(feature = "server")]
+fn is_bootstrap_admin(username: &str) -> bool {
+    env::var("SKILLFORGE_ADMINS")
+        .map(|admins| admins.split(',').any(|a| a.trim() == username))
+        .unwrap_or(false)
+}
+
 #[server]
 pub async fn login_user(username: String, password: String) -> Result<String, ServerFnError> {
     let db = get_db().await?;
@@ -240,6 +284,18 @@ pub async fn login_user(username: String, password: String) -> Result<String, Se
         .into_server_error()?;
     if let Some(user) = users.first() {
         if verify(password.as_bytes(), &user.password_hash).into_server_error()? {
+            if user.locked {
+                return Err(ServerFnError::new("This account has been locked"));
+            }
+            if user.role != UserRole::Admin && is_bootstrap_admin(&user.username) {
+                if let Some(id) = user.id.clone() {
+                    db.query("UPDATE $id SET role = $role")
+                        .bind(("id", id))
+                        .bind(("role", UserRole::Admin))
+                        .await
+                        .into_server_error()?;
+                }
+            }
             let user = User::from(user.to_owned());
             let user_id = user.id.ok_or(ServerFnError::new("User has no ID"))?;
             let session_token = create_session(user_id).await?;
@@ -351,16 +407,13 @@ pub async fn get_user_roadmaps(session_token: String) -> Result<Vec<Roadmap>, Se
 }
 
 #[server]
-pub async fn get_roadmap(roadmap_id: String) -> Result<Roadmap, ServerFnError> {
-    let db = get_db().await?;
-    let id = RecordId::from_str(&roadmap_id)
-        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
-    let roadmap_db: RoadmapDB = db
-        .select(id)
-        .await
-        .into_server_error()?
-        .ok_or_else(|| ServerFnError::new("Roadmap not found"))?;
-    Ok(Roadmap::from(roadmap_db))
+pub async fn get_roadmap(
+    session_token: String,
+    roadmap_id: String,
+) -> Result<Roadmap, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    let (_, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    Ok(Roadmap::from(roadmap))
 }
 
 #[server]
@@ -371,25 +424,103 @@ pub async fn generate_questions(
     let user: User = get_user_data(session_token).await?;
     let prompt = format!(
         "Generate 10 questions to evaluate a user's learning preferences and existing knowledge for learning {}. \n\
-        User's existing skills: {:?}\n\
+        User's existing skills (with current proficiency):\n{}\n\
         User's preferences: {:?}\n\n\
         Generate:\n\
         - 5 preference questions (learning style, time commitment, content type preferences)\n\
-        - 5 knowledge evaluation questions (to test existing knowledge)\n\n\
+        - 5 knowledge evaluation questions (to test existing knowledge, pitched at the user's current proficiency)\n\n\
         Format strictly as JSON array with: question_text, question_type (MCQ/MSQ/TrueFalse/OneWord), options (array of strings, empty for OneWord)",
         skill_name,
-        user.skills_learned,
+        skills_for_prompt(&user),
         user.preferences
     );
 
-    let questions = call_openrouter_for_questions(&prompt).await?;
-    Ok(questions)
+    match call_openrouter_for_questions(&prompt).await {
+        Ok(questions) => Ok(questions),
+        Err(e) => {
+            let user_id = user.id.as_deref().and_then(|id| id.parse().ok());
+            Err(generation_failed(
+                user_id,
+                "questions",
+                &skill_name,
+                ServerFnError::new(e.to_string()),
+            )
+            .await)
+        }
+    }
+}
+
+/// Records a failed generation for the admin console and hands the error back. Problems
+/// writing the record are ignored so they never mask the original failure.
+#[cfg(feature = "server")]
+async fn generation_failed(
+    user_id: Option<RecordId>,
+    job: &str,
+    subject: &str,
+    error: ServerFnError,
+) -> ServerFnError {
+    if let Ok(db) = get_db().await {
+        let failure = GenerationFailureDB {
+            id: None,
+            user_id,
+            job: job.to_string(),
+            subject: subject.to_string(),
+            error: error.to_string(),
+            occurred_at: Utc::now(),
+        };
+        let _: Result<Option<GenerationFailureDB>, _> =
+            db.create("generation_failures").content(failure).await;
+    }
+    error
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct SkillGapAnalysis {
     pub current_knowledge: Vec<String>,
     pub future_mastery: Vec<String>,
+    #[serde(default, deserialize_with = "lenient_scores")]
+    pub knowledge_scores: HashMap<String, u8>,
+}
+
+/// Reads the model's `knowledge_scores` without trusting their shape: numbers and numeric
+/// strings are rounded and clamped to 0-100, and anything else is skipped, so a bad score
+/// can't fail roadmap generation.
+fn lenient_scores<'de, D>(deserializer: D) -> Result<HashMap<String, u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let Some(scores) = value.as_object() else {
+        return Ok(HashMap::new());
+    };
+    Ok(scores
+        .iter()
+        .filter_map(|(skill, score)| {
+            let score = match score {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => s.trim().trim_end_matches('%').parse().ok(),
+                _ => None,
+            }?;
+            score
+                .is_finite()
+                .then(|| (skill.clone(), score.clamp(0.0, 100.0).round() as u8))
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+fn skills_for_prompt(user: &User) -> String {
+    if user.skills_learned.is_empty() {
+        return "None recorded".to_string();
+    }
+    let now = Utc::now();
+    user.skills_learned
+        .iter()
+        .map(|s| format!("- {}", s.prompt_summary(now)))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(feature = "server")]
@@ -398,7 +529,7 @@ pub async fn get_user_skill_info_for_roadmap(
     responses: &[QuestionResponse], // Use slice &[T] instead of &Vec<T>
     skill_name: &str,
     roadmap: &Vec<RoadmapNode>,
-) -> Result<(Vec<String>, Vec<String>)> {
+) -> Result<SkillGapAnalysis> {
     let client = reqwest::Client::new();
     let api_key = std::env::var("OPENROUTER_API_KEY").context("API Key missing")?;
 
@@ -408,7 +539,7 @@ You are an expert Educational Analyst for 'SkillForge'. Your job is to analyze a
 INPUT DATA:
 1. Target Skill: The main subject the user wants to learn.
 2. Quiz Performance: A list of questions the user answered, you are to evaluate correctness.
-3. User's existing skills (maybe incorrect): A list of skills that the user already knows, based on their profile. This may be inaccurate so in case of confilct with quiz performance's results, you should favour the outcome of quiz performce's evaluation result.
+3. User's existing skills (maybe incorrect): A list of skills that the user already knows, based on their profile, each with a proficiency level out of 100 that has already been decayed for time since last practice. This may be inaccurate so in case of confilct with quiz performance's results, you should favour the outcome of quiz performce's evaluation result.
 4. Nodes of the roadmap that the user is going to follow to learn the new skill.
 
 YOUR TASK:
@@ -418,18 +549,23 @@ YOUR TASK:
    -Note, you are to add only the name of the skill (like python, accounts, etc.), and nothing else
 2. Generate 'future_mastery'. This should be a comprehensive list of high-level learning outcomes one would achieve after completing a full roadmap for the 'Target Skill'.
    - This list should ONLY include both the things they missed AND are in the given roadmap that they are going to follow. It should only be the name of the skill (like python, math, accounting, etc.), and not a full statement
+3. Generate 'knowledge_scores'. For every skill in 'current_knowledge', give a score from 0 to 100 reflecting how well the quiz answers demonstrate it.
 
 OUTPUT FORMAT:
 Return strictly a JSON object. Do not include markdown formatting (like ```json).
 {
     "current_knowledge": ["List of concepts the user definitely knows based on correct answers"],
-    "future_mastery": ["List of all key skills/outcomes provided by a complete roadmap for this topic"]
+    "future_mastery": ["List of all key skills/outcomes provided by a complete roadmap for this topic"],
+    "knowledge_scores": {"skill name from current_knowledge": 75}
 }
 "#;
 
     let user_prompt = format!(
-        "Target Skill: {}\n\nQuiz Performance:{:?}\n\nUser Skills:{:?}\n\nRoadmap:{:?}",
-        skill_name, responses, user.skills_learned, roadmap
+        "Target Skill: {}\n\nQuiz Performance:{:?}\n\nUser Skills:\n{}\n\nRoadmap:{:?}",
+        skill_name,
+        responses,
+        skills_for_prompt(user),
+        roadmap
     );
 
     let body = serde_json::json!({
@@ -465,7 +601,7 @@ Return strictly a JSON object. Do not include markdown formatting (like ```json)
     let analysis: SkillGapAnalysis =
         serde_json::from_str(&content).context(format!("Failed to parse LLM JSON: {}", content))?;
 
-    Ok((analysis.current_knowledge, analysis.future_mastery))
+    Ok(analysis)
 }
 
 #[server]
@@ -480,23 +616,40 @@ pub async fn generate_roadmap(
         .id
         .clone()
         .ok_or(ServerFnError::new("User ID not found"))?;
-    let query_variations = generate_rag_queries(&skill_name, &user, &responses)
-        .await
-        .into_server_error()?;
-    let relevant_resources = search_vector_db_multi_query(&query_variations).await?;
-    let roadmap_nodes =
-        generate_roadmap_with_llm(&skill_name, &user, &responses, &relevant_resources).await?;
-    let (skills_prev_known, learning_outcomes): (Vec<String>, Vec<String>) =
-        get_user_skill_info_for_roadmap(&user, &responses, &skill_name, &roadmap_nodes).await?;
+    let generated: Result<_, ServerFnError> = async {
+        let query_variations = generate_rag_queries(&skill_name, &user, &responses)
+            .await
+            .into_server_error()?;
+        let relevant_resources = search_vector_db_multi_query(&query_variations).await?;
+        let roadmap_nodes =
+            generate_roadmap_with_llm(&skill_name, &user, &responses, &relevant_resources).await?;
+        let analysis =
+            get_user_skill_info_for_roadmap(&user, &responses, &skill_name, &roadmap_nodes).await?;
+        Ok((roadmap_nodes, analysis))
+    }
+    .await;
 
     let user_id: RecordId = user_id.parse().into_server_error()?;
+    let (roadmap_nodes, analysis) = match generated {
+        Ok(generated) => generated,
+        Err(e) => {
+            return Err(generation_failed(Some(user_id), "roadmap", &skill_name, e).await);
+        }
+    };
+    for (skill, score) in &analysis.knowledge_scores {
+        record_skill_evidence(&user_id, skill, *score, ProficiencySource::Quiz).await?;
+    }
+
     let roadmap = RoadmapDB {
         id: None,
         user_id,
-        learning_outcomes,
-        skills_prev_known,
+        learning_outcomes: analysis.future_mastery,
+        skills_prev_known: analysis.current_knowledge,
         skill_name,
         nodes: roadmap_nodes,
+        require_mastery_quiz: false,
+        share_links: Vec::new(),
+        origin: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
@@ -556,8 +709,11 @@ EXAMPLE OUTPUT:
 "#;
 
     let user_prompt = format!(
-        "Skill to learn: {}\nUser Knowledge Context: {:?}\nUser Preferences: {:?}\nUser Skills: {:?}",
-        skill_name, responses, user.preferences, user.skills_learned
+        "Skill to learn: {}\nUser Knowledge Context: {:?}\nUser Preferences: {:?}\nUser Skills (current proficiency):\n{}",
+        skill_name,
+        responses,
+        user.preferences,
+        skills_for_prompt(user)
     );
     let body = serde_json::json!({
         "model": LLM_MODEL,
@@ -594,6 +750,18 @@ EXAMPLE OUTPUT:
     Ok(queries)
 }
 
+#[cfg(feature = "server")]
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
 #[cfg(feature = "server")]
 async fn search_vector_db_multi_query(queries: &[String]) -> Result<Vec<CoursesDataClean>> {
     let db = get_db().await?;
@@ -606,10 +774,20 @@ async fn search_vector_db_multi_query(queries: &[String]) -> Result<Vec<CoursesD
             .next()
             .ok_or_else(|| anyhow::anyhow!("Empty embedding returned"))?;
         let mut result = db
-            .query("SELECT * FROM courses WHERE embedding <|10,400|> $embedding LIMIT 5")
-            .bind(("embedding", embedding))
+            .query(format!(
+                "SELECT * FROM courses WHERE embedding <|{RETRIEVAL_CANDIDATES},400|> $embedding"
+            ))
+            .bind(("embedding", embedding.clone()))
             .await?;
         let mut courses: Vec<CoursesDataWithEmbeddings> = result.take(0)?;
+        courses.retain(|c| !c.deactivated);
+        // Learner feedback nudges well-liked courses up and broken or disliked ones down.
+        let ranking = |c: &CoursesDataWithEmbeddings| {
+            cosine_similarity(&embedding, &c.embedding)
+                + RETRIEVAL_QUALITY_WEIGHT * c.feedback.quality_score()
+        };
+        courses.sort_by(|a, b| ranking(b).total_cmp(&ranking(a)));
+        courses.truncate(RETRIEVAL_RESULTS_PER_QUERY);
         all_results.append(&mut courses);
     }
     let results: Vec<CoursesDataClean> = all_results
@@ -633,7 +811,7 @@ async fn generate_roadmap_with_llm(
     let prompt = format!(
         "Create a detailed learning roadmap for '{skill_name}'.\n\n\
 User Profile:\n\
-- Existing skills: {:?}\n\
+- Existing skills with current proficiency (skip or shorten nodes the user is already Advanced/Expert in, and add refreshers for skills marked as getting rusty):\n{}\n\
 - Learning preferences: {:?}\n\
 - Question responses(For you to evaluate user's knowledge and preferences): {:?}\n\n\
 Available Resources:\n\
@@ -648,17 +826,21 @@ Return ONLY valid JSON in this exact shape:\n\
 IMPORTANT LINKING RULES:\n\
 - `prerequisites` must be an array of OTHER NODE `skill_name` strings (not IDs).\n\
 - `prev_node_id` and `next_node_id` must be the adjacent node's `skill_name` (or null).\n\n\
+EFFORT ESTIMATES:\n\
+- `estimated_minutes` is the time to watch/read a resource. Use the resource's `duration_minutes` when it has one.\n\
+- `estimated_hours` is the total study time for the node, including practice, for a learner at the user's level.\n\n\
 Each node must match:\n\
 {{\n\
   \"skill_name\": \"...\",\n\
   \"description\": \"...\",\n\
-  \"resources\": [{{\"title\":\"...\",\"platform\":\"...\",\"url\":...,\"resource_type\":\"...\"}}],\n\
+  \"resources\": [{{\"title\":\"...\",\"platform\":\"...\",\"url\":...,\"resource_type\":\"...\",\"estimated_minutes\":30}}],\n\
   \"prerequisites\": [\"...\"],\n\
+  \"estimated_hours\": 4.5,\n\
   \"prev_node_id\": null,\n\
   \"next_node_id\": null,\n\
   \"is_completed\": false\n\
 }}",
-        user.skills_learned,
+        skills_for_prompt(user),
         user.preferences,
         responses,
         resources_json
@@ -669,12 +851,34 @@ Each node must match:\n\
         nodes: Vec<RoadmapNode>,
     }
 
+    let system_prompt = "You are a JSON-only API. Return ONLY valid JSON with top-level object \
+{\"nodes\": [...]} and nothing else. No markdown. No commentary.";
     let mut nodes_out: RoadmapNodesOut =
-        serde_json::from_str(&call_openrouter_for_roadmap(&prompt).await?).into_server_error()?;
+        serde_json::from_str(&call_openrouter(system_prompt, &prompt).await?)
+            .into_server_error()?;
 
     for node in &mut nodes_out.nodes {
         node.id = Uuid::new_v4().to_string();
         node.is_completed = false;
+        node.mastery_score = None;
+
+        // Course metadata beats the LLM's guess wherever the resource is in the corpus.
+        for resource in &mut node.resources {
+            let known = resources
+                .iter()
+                .find(|c| {
+                    c.title == resource.title
+                        || resource
+                            .url
+                            .as_deref()
+                            .is_some_and(|u| !u.is_empty() && u == c.url)
+                })
+                .and_then(|c| c.duration_minutes);
+            if known.is_some() {
+                resource.estimated_minutes = known;
+            }
+        }
+        node.estimated_hours = node.estimated_hours.filter(|h| h.is_finite() && *h > 0.0);
     }
 
     let name_to_id: HashMap<String, String> = nodes_out
@@ -704,13 +908,28 @@ Each node must match:\n\
             .map(|s| map_ref(&s));
     }
 
+    // The corpus knows which topics build on which; hold the LLM's ordering to that.
+    match load_taxonomy().await {
+        Ok(taxonomy) => {
+            let unresolved = crate::taxonomy::repair_prerequisites(&mut nodes_out.nodes, &taxonomy);
+            for issue in unresolved {
+                println!(
+                    "Roadmap for '{skill_name}': '{}' should build on '{}' ({} needs {}) but comes before it",
+                    issue.skill_name,
+                    issue.requires_skill_name,
+                    issue.topic,
+                    issue.prerequisite_topic
+                );
+            }
+        }
+        Err(e) => println!("Skipping taxonomy prerequisite check: {e}"),
+    }
+
     Ok(nodes_out.nodes)
 }
 
 #[cfg(feature = "server")]
 async fn call_openrouter_for_questions(prompt: &str) -> Result<Vec<Question>> {
-    let client = reqwest::Client::new();
-    let api_key = env::var("OPENROUTER_API_KEY").expect("OPENROUTER_API_KEY must be set");
     let sys_prompt = "You are an educational assessment expert that generates personalized learning evaluation questions. \
         Your goal is to understand both HOW the user prefers to learn and WHAT they already know.\n\n\
         RESPONSE FORMAT RULES:\n\
@@ -744,34 +963,8 @@ async fn call_openrouter_for_questions(prompt: &str) -> Result<Vec<Question>> {
           ]\n\
         }\n\n\
         Make questions conversational, relevant to the specific skill, and ensure options are realistic and well-balanced.c";
-    let body = serde_json::json!({
-        "model": LLM_MODEL,
-        "messages": [
-            {
-               "role": "system",
-                "content": sys_prompt
-            },
-            {
-                "role": "user",
-                "content": prompt
-            }
-        ],
-        "response_format": { "type": "json_object" }
-    });
-
-    let response = client
-        .post("https://openrouter.ai/api/v1/chat/completions")
-        .header("Authorization", format!("Bearer {api_key}"))
-        .header("Content-Type", "application/json")
-        .json(&body)
-        .send()
-        .await?;
-
-    let json: serde_json::Value = response.json().await?;
-    let content = json["choices"][0]["message"]["content"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("No response content"))?;
-    let parsed: serde_json::Value = serde_json::from_str(content)?;
+    let content = call_openrouter(sys_prompt, prompt).await?;
+    let parsed: serde_json::Value = serde_json::from_str(&content)?;
     let questions_array = parsed["questions"]
         .as_array()
         .ok_or_else(|| anyhow::anyhow!("No questions array"))?;
@@ -781,12 +974,7 @@ async fn call_openrouter_for_questions(prompt: &str) -> Result<Vec<Question>> {
         questions.push(Question {
             id: Uuid::new_v4().to_string(),
             question_text: q["question_text"].as_str().unwrap_or("").to_string(),
-            question_type: match q["question_type"].as_str() {
-                Some("MCQ") => QuestionType::Mcq,
-                Some("MSQ") => QuestionType::Msq,
-                Some("TrueFalse") => QuestionType::TrueFalse,
-                _ => QuestionType::OneWord,
-            },
+            question_type: parse_question_type(q["question_type"].as_str()),
             options: q["options"]
                 .as_array()
                 .map(|arr| {
@@ -803,24 +991,30 @@ async fn call_openrouter_for_questions(prompt: &str) -> Result<Vec<Question>> {
 }
 
 #[cfg(feature = "server")]
-async fn call_openrouter_for_roadmap(prompt: &str) -> Result<String> {
+fn parse_question_type(raw: Option<&str>) -> QuestionType {
+    match raw {
+        Some("MCQ") => QuestionType::Mcq,
+        Some("MSQ") => QuestionType::Msq,
+        Some("TrueFalse") => QuestionType::TrueFalse,
+        _ => QuestionType::OneWord,
+    }
+}
+
+/// Sends a system + user prompt pair to OpenRouter and returns the JSON it answers with. The
+/// system prompt carries the schema the answer must follow.
+#[cfg(feature = "server")]
+async fn call_openrouter(sys_prompt: &str, user_prompt: &str) -> Result<String> {
     let client = reqwest::Client::new();
-    let api_key = env::var("OPENROUTER_API_KEY").expect("OPENROUTER_API_KEY must be set");
-    let system_prompt = "You are a JSON-only API. Return ONLY valid JSON with top-level object \
-{\"nodes\": [...]} and nothing else. No markdown. No commentary.";
+    let api_key = env::var("OPENROUTER_API_KEY").context("OPENROUTER_API_KEY must be set")?;
 
     let body = serde_json::json!({
         "model": LLM_MODEL,
         "messages": [
-            {
-                "role": "system",
-                "content": system_prompt
-            },
-            {
-                "role": "user",
-                "content": prompt
-            }
-        ]
+            { "role": "system", "content": sys_prompt },
+            { "role": "user", "content": user_prompt }
+        ],
+        "temperature": 0.3,
+        "response_format": { "type": "json_object" }
     });
 
     let response = client
@@ -831,35 +1025,44 @@ async fn call_openrouter_for_roadmap(prompt: &str) -> Result<String> {
         .send()
         .await?;
 
+    if !response.status().is_success() {
+        let error_text = response.text().await?;
+        return Err(anyhow::anyhow!("LLM API Error: {}", error_text));
+    }
+
     let json: serde_json::Value = response.json().await?;
     let content = json["choices"][0]["message"]["content"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("No response content"))?;
 
-    Ok(content.to_string())
+    Ok(clean_json_response(content))
 }
 
 #[server]
 pub async fn toggle_node_completion(
+    session_token: String,
     roadmap_id: String,
     node_id: String,
 ) -> Result<(), ServerFnError> {
     let db = get_db().await?;
-    let id = RecordId::from_str(&roadmap_id).into_server_error()?;
-
-    let mut roadmap: RoadmapDB = db
-        .select(&id)
-        .await
-        .into_server_error()?
-        .ok_or_else(|| ServerFnError::new("Roadmap not found"))?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, mut roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
 
-    let user_id = roadmap.user_id.clone();
+    let require_mastery_quiz = roadmap.require_mastery_quiz;
     let mut skill_target_name = String::new();
     let mut is_completed_now = false;
 
     // 2. Toggle the node status in memory
     if let Some(node) = roadmap.nodes.iter_mut().find(|n| n.id == node_id) {
+        if !node.is_completed && require_mastery_quiz {
+            return Err(ServerFnError::new(
+                "This roadmap requires passing the mastery quiz to complete a step",
+            ));
+        }
         node.is_completed = !node.is_completed;
+        if !node.is_completed {
+            node.mastery_score = None;
+        }
         is_completed_now = node.is_completed;
         skill_target_name = node.skill_name.clone();
     } else {
@@ -870,106 +1073,2973 @@ pub async fn toggle_node_completion(
 
     let _: Option<RoadmapDB> = db.update(id).content(roadmap).await.into_server_error()?;
 
-    db.query("UPDATE $uid SET skills_learned = skills_learned[WHERE skillname != $target]")
+    let evidence = is_completed_now.then_some((
+        NODE_COMPLETION_SKILL_LEVEL,
+        ProficiencySource::NodeCompletion,
+    ));
+    sync_node_skill(&user_id, &skill_target_name, evidence).await?;
+    sync_review_cards(&user_id).await
+}
+
+/// Keeps `skills_learned` in step with a node's completion state. `None` removes the skill,
+/// `Some` records it as learned now with the given evidence.
+#[cfg(feature = "server")]
+async fn sync_node_skill(
+    user_id: &RecordId,
+    skill_name: &str,
+    evidence: Option<(u8, ProficiencySource)>,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut user: UserDB = db
+        .select(user_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("User not found"))?;
+    let now = Utc::now();
+    let previous = user
+        .skills_learned
+        .iter()
+        .position(|s| s.is_named(skill_name))
+        .map(|pos| user.skills_learned.remove(pos));
+
+    if let Some((level, source)) = evidence {
+        // Re-learning starts a fresh entry (so it shows up in today's activity), but keeps
+        // whatever proficiency the user had already built up for the skill.
+        let mut skill = UserSkills::new(skill_name, 0, source, now);
+        if let Some(previous) = previous {
+            skill.skill_level = previous.current_level(now);
+        }
+        skill.record_evidence(level, source, now);
+        user.skills_learned.push(skill);
+    }
+
+    db.query("UPDATE $uid SET skills_learned = $skills")
         .bind(("uid", user_id.clone()))
-        .bind(("target", skill_target_name.clone()))
+        .bind(("skills", user.skills_learned))
         .await
         .into_server_error()?;
 
-    if is_completed_now {
-        let new_skill = UserSkills {
-            skillname: skill_target_name,
-            date_learnt: Utc::now(),
-        };
+    Ok(())
+}
 
-        db.query("UPDATE $uid SET skills_learned += $skill")
-            .bind(("uid", user_id))
-            .bind(("skill", new_skill))
-            .await
-            .into_server_error()?;
+#[cfg(feature = "server")]
+async fn record_skill_evidence(
+    user_id: &RecordId,
+    skill_name: &str,
+    level: u8,
+    source: ProficiencySource,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut user: UserDB = db
+        .select(user_id.clone())
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("User not found"))?;
+
+    let now = Utc::now();
+    match user
+        .skills_learned
+        .iter_mut()
+        .find(|s| s.is_named(skill_name))
+    {
+        Some(skill) => skill.record_evidence(level, source, now),
+        // Evidence of a skill not learnt here is knowledge the user already had.
+        None => user
+            .skills_learned
+            .push(UserSkills::prior_knowledge(skill_name, level, source, now)),
     }
 
+    db.query("UPDATE $uid SET skills_learned = $skills")
+        .bind(("uid", user_id.clone()))
+        .bind(("skills", user.skills_learned))
+        .await
+        .into_server_error()?;
     Ok(())
 }
 
 #[server]
-pub async fn delete_roadmap(roadmap_id: String) -> Result<(), ServerFnError> {
-    let db = get_db().await?;
-
-    let id: RecordId = RecordId::from_str(&roadmap_id)
-        .map_err(|e| ServerFnError::new(format!("Invalid roadmap ID: {}", e)))?;
-
-    let deleted: Option<RoadmapDB> = db.delete(id).await.into_server_error()?;
+pub async fn get_stale_skills(session_token: String) -> Result<Vec<UserSkills>, ServerFnError> {
+    let (user, _) = session_user(session_token).await?;
+    let now = Utc::now();
+    let mut stale: Vec<UserSkills> = user
+        .skills_learned
+        .into_iter()
+        .filter(|s| s.is_stale(now))
+        .collect();
+    stale.sort_by_key(|s| s.last_practiced_at());
+    Ok(stale)
+}
 
-    if deleted.is_none() {
-        return Err(ServerFnError::new("Roadmap not found or already deleted"));
+/// Loads a roadmap and checks that it belongs to `user_id`.
+#[cfg(feature = "server")]
+async fn owned_roadmap(
+    roadmap_id: &str,
+    user_id: &RecordId,
+) -> Result<(RecordId, RoadmapDB), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let id = RecordId::from_str(roadmap_id)
+        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let roadmap: RoadmapDB = db
+        .select(&id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Roadmap not found"))?;
+    if &roadmap.user_id != user_id {
+        return Err(ServerFnError::new("You do not have access to this roadmap"));
     }
-
-    Ok(())
+    Ok((id, roadmap))
 }
 
 #[server]
-pub async fn get_progress_report(
-    days: u16,
+pub async fn set_mastery_quiz_required(
     session_token: String,
-) -> Result<Option<HashMap<DateTime<Utc>, u8>>, ServerFnError> {
-    #[derive(serde::Deserialize)]
-    struct UserQueryResult {
-        skills_learned: Option<Vec<UserSkills>>,
-    }
+    roadmap_id: String,
+    required: bool,
+) -> Result<(), ServerFnError> {
     let db = get_db().await?;
-    let mut result = db
-        .query("SELECT * FROM sessions WHERE session_token = $session_token")
-        .bind(("session_token", session_token))
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, _) = owned_roadmap(&roadmap_id, &user_id).await?;
+
+    db.query("UPDATE $id SET require_mastery_quiz = $required, updated_at = $now")
+        .bind(("id", id))
+        .bind(("required", required))
+        .bind(("now", Utc::now()))
         .await
         .into_server_error()?;
+    Ok(())
+}
 
-    let sessions: Vec<Session> = result.take(0).into_server_error()?;
+/// Pulls the course records behind a node's resources, so quiz questions are grounded in
+/// the material the learner was actually pointed at.
+#[cfg(feature = "server")]
+async fn linked_course_material(node: &RoadmapNode) -> Result<Vec<CoursesDataClean>> {
+    let db = get_db().await?;
+    let titles: Vec<String> = node.resources.iter().map(|r| r.title.clone()).collect();
+    let urls: Vec<String> = node
+        .resources
+        .iter()
+        .filter_map(|r| r.url.clone())
+        .filter(|u| !u.is_empty())
+        .collect();
 
-    if let Some(session) = sessions.first() {
-        if session.expires_at < Utc::now() {
-            return Ok(None);
-        }
-        let user_id: RecordId = session.user_id.clone();
-        let curr_datetime: DateTime<Utc> = Utc::now();
-        let start_datetime: DateTime<Utc> = curr_datetime - Days::new(days as u64);
-        // Fetch the record wrapped in the struct
-        let result: Vec<UserQueryResult> = db
-            .query("SELECT skills_learned FROM $user_id")
-            .bind(("user_id", user_id))
-            .await
-            .into_server_error()?
-            .take(0)
-            .into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM courses WHERE (title IN $titles OR url IN $urls) AND deactivated != true LIMIT 5")
+        .bind(("titles", titles))
+        .bind(("urls", urls))
+        .await?;
+    let courses: Vec<CoursesDataWithEmbeddings> = result.take(0)?;
+    if !courses.is_empty() {
+        return Ok(courses.into_iter().map(CoursesDataClean::from).collect());
+    }
 
-        // Unwrap the first result and then the skills vector
-        let all_skills = result.into_iter().next().and_then(|r| r.skills_learned);
-        if all_skills.clone().is_none_or(|x| x.is_empty()) {
+    // Resources the LLM invented won't be in the corpus; fall back to the closest courses.
+    let query = format!("{}: {}", node.skill_name, node.description);
+    let mut nearest = search_vector_db_multi_query(&[query]).await?;
+    nearest.truncate(3);
+    Ok(nearest)
+}
+
+#[cfg(feature = "server")]
+async fn generate_mastery_questions(
+    node: &RoadmapNode,
+    material: &[CoursesDataClean],
+) -> Result<(Vec<Question>, Vec<QuizAnswerKey>)> {
+    let sys_prompt = format!(
+        "You are an assessment author for SkillForge. Write a short mastery quiz that checks whether \
+a learner has genuinely learned one step of their roadmap.\n\n\
+RULES:\n\
+- Write exactly {MASTERY_QUIZ_QUESTIONS} questions.\n\
+- Base every question on the step description and the course material provided. Test understanding, not trivia.\n\
+- Use a mix of question types: MCQ (4 options, one correct), MSQ (4-5 options, several correct), TrueFalse (options 'True' and 'False'), OneWord (empty options).\n\
+- `correct_answers` must be copied verbatim from `options`. For OneWord, list every acceptable short answer.\n\n\
+OUTPUT: Return ONLY valid JSON, no markdown:\n\
+{{\"questions\": [{{\"question_text\": \"...\", \"question_type\": \"MCQ\" | \"MSQ\" | \"TrueFalse\" | \"OneWord\", \"options\": [\"...\"], \"correct_answers\": [\"...\"]}}]}}"
+    );
+
+    let material_json = serde_json::to_string_pretty(
+        &material
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "title": c.title,
+                    "description": c.description,
+                    "content": c.content.chars().take(1500).collect::<String>(),
+                    "topic": c.topic,
+                })
+            })
+            .collect::<Vec<_>>(),
+    )?;
+    let user_prompt = format!(
+        "Roadmap step: {}\nDescription: {}\n\nCourse material:\n{}",
+        node.skill_name, node.description, material_json
+    );
+
+    let content = call_openrouter(&sys_prompt, &user_prompt).await?;
+    let parsed: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse quiz JSON: {} | Content: {}", e, content))?;
+    let questions_array = parsed["questions"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("No questions array"))?;
+
+    let to_strings = |v: &serde_json::Value| -> Vec<String> {
+        v.as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // A quiz is only stored if every question can be graded; a half-usable one would pass or
+    // fail learners on the wrong answers.
+    if questions_array.len() != MASTERY_QUIZ_QUESTIONS {
+        return Err(anyhow::anyhow!(
+            "LLM wrote {} quiz questions instead of {MASTERY_QUIZ_QUESTIONS}",
+            questions_array.len()
+        ));
+    }
+    let mut questions = Vec::new();
+    let mut answer_keys = Vec::new();
+    for q in questions_array {
+        let question = Question {
+            id: Uuid::new_v4().to_string(),
+            question_text: q["question_text"].as_str().unwrap_or("").to_string(),
+            question_type: parse_question_type(q["question_type"].as_str()),
+            options: to_strings(&q["options"]),
+        };
+        let correct_answers = to_strings(&q["correct_answers"]);
+        // Compared the way `grade_quiz` compares answers.
+        let is_option = |answer: &String| {
+            question
+                .options
+                .iter()
+                .any(|o| o.trim().to_lowercase() == answer.trim().to_lowercase())
+        };
+        let answerable = match question.question_type {
+            QuestionType::OneWord => !correct_answers.is_empty(),
+            QuestionType::Msq => {
+                !correct_answers.is_empty() && correct_answers.iter().all(is_option)
+            }
+            QuestionType::Mcq | QuestionType::TrueFalse => {
+                correct_answers.len() == 1 && is_option(&correct_answers[0])
+            }
+        };
+        if question.question_text.trim().is_empty() || !answerable {
+            return Err(anyhow::anyhow!(
+                "LLM wrote a quiz question that can't be graded: {q}"
+            ));
+        }
+        answer_keys.push(QuizAnswerKey {
+            question_id: question.id.clone(),
+            question_type: question.question_type.clone(),
+            correct_answers,
+        });
+        questions.push(question);
+    }
+
+    Ok((questions, answer_keys))
+}
+
+#[cfg(feature = "server")]
+fn grade_quiz(answer_keys: &[QuizAnswerKey], responses: &[QuestionResponse]) -> QuizResult {
+    use std::collections::HashSet;
+
+    let normalize = |s: &String| s.trim().to_lowercase();
+    let mut incorrect_question_ids = Vec::new();
+
+    for key in answer_keys {
+        let given: HashSet<String> = responses
+            .iter()
+            .find(|r| r.question_id == key.question_id)
+            .map(|r| {
+                r.answer
+                    .iter()
+                    .map(normalize)
+                    .filter(|a| !a.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let expected: HashSet<String> = key.correct_answers.iter().map(normalize).collect();
+
+        let is_correct = match key.question_type {
+            QuestionType::OneWord => given.iter().any(|g| expected.contains(g)),
+            _ => !given.is_empty() && given == expected,
+        };
+        if !is_correct {
+            incorrect_question_ids.push(key.question_id.clone());
+        }
+    }
+
+    let total = answer_keys.len();
+    let correct = total - incorrect_question_ids.len();
+    let score = if total > 0 {
+        ((correct * 100) / total) as u8
+    } else {
+        0
+    };
+
+    QuizResult {
+        score,
+        correct,
+        total,
+        passed: score >= MASTERY_PASS_SCORE,
+        incorrect_question_ids,
+    }
+}
+
+#[server]
+pub async fn generate_node_quiz(
+    session_token: String,
+    roadmap_id: String,
+    node_id: String,
+) -> Result<NodeQuiz, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    let node = roadmap
+        .nodes
+        .iter()
+        .find(|n| n.id == node_id)
+        .ok_or_else(|| ServerFnError::new("Node not found in roadmap"))?;
+
+    let quiz = create_node_quiz(user_id, id, node).await?;
+    Ok(NodeQuiz::from(quiz))
+}
+
+/// Writes a new quiz for `node`, grounded in its linked course material, and stores it
+/// with its answer key.
+#[cfg(feature = "server")]
+async fn create_node_quiz(
+    user_id: RecordId,
+    roadmap_id: RecordId,
+    node: &RoadmapNode,
+) -> Result<NodeQuizDB, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let generated: Result<_, ServerFnError> = async {
+        let material = linked_course_material(node).await.into_server_error()?;
+        generate_mastery_questions(node, &material)
+            .await
+            .into_server_error()
+    }
+    .await;
+    let (questions, answer_keys) = match generated {
+        Ok(generated) => generated,
+        Err(e) => {
+            return Err(generation_failed(Some(user_id), "quiz", &node.skill_name, e).await);
+        }
+    };
+
+    let quiz = NodeQuizDB {
+        id: None,
+        user_id,
+        roadmap_id,
+        node_id: node.id.clone(),
+        questions,
+        answer_keys,
+        created_at: Utc::now(),
+        consumed_at: None,
+    };
+    db.create("node_quizzes")
+        .content(quiz)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Failed to store quiz"))
+}
+
+/// Marks a quiz as submitted, failing if it already was, so a retry needs a fresh quiz.
+#[cfg(feature = "server")]
+async fn consume_quiz(quiz_id: &RecordId) -> Result<(), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    // Conditional, so two concurrent submissions can't both get through.
+    let mut result = db
+        .query("UPDATE $id SET consumed_at = $now WHERE consumed_at = NONE RETURN AFTER")
+        .bind(("id", quiz_id.clone()))
+        .bind(("now", Utc::now()))
+        .await
+        .into_server_error()?;
+    let consumed: Option<NodeQuizDB> = result.take(0).into_server_error()?;
+    if consumed.is_none() {
+        return Err(ServerFnError::new(
+            "This quiz has already been submitted; take a fresh one",
+        ));
+    }
+    Ok(())
+}
+
+#[server]
+pub async fn submit_node_quiz(
+    session_token: String,
+    quiz_id: String,
+    responses: Vec<QuestionResponse>,
+) -> Result<QuizResult, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let quiz_id = RecordId::from_str(&quiz_id).into_server_error()?;
+    let quiz: NodeQuizDB = db
+        .select(&quiz_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Quiz not found"))?;
+    if quiz.user_id != user_id {
+        return Err(ServerFnError::new("You do not have access to this quiz"));
+    }
+    consume_quiz(&quiz_id).await?;
+
+    let result = grade_quiz(&quiz.answer_keys, &responses);
+
+    let attempt = QuizAttempt {
+        id: None,
+        quiz_id,
+        user_id: user_id.clone(),
+        roadmap_id: quiz.roadmap_id.clone(),
+        node_id: quiz.node_id.clone(),
+        score: result.score,
+        passed: result.passed,
+        submitted_at: Utc::now(),
+    };
+    let _: Option<QuizAttempt> = db
+        .create("quiz_attempts")
+        .content(attempt)
+        .await
+        .into_server_error()?;
+
+    if result.passed {
+        let mut roadmap: RoadmapDB = db
+            .select(&quiz.roadmap_id)
+            .await
+            .into_server_error()?
+            .ok_or_else(|| ServerFnError::new("Roadmap not found"))?;
+        let node = roadmap
+            .nodes
+            .iter_mut()
+            .find(|n| n.id == quiz.node_id)
+            .ok_or_else(|| ServerFnError::new("Node not found in roadmap"))?;
+        node.is_completed = true;
+        node.mastery_score = Some(node.mastery_score.unwrap_or(0).max(result.score));
+        let skill_name = node.skill_name.clone();
+        roadmap.updated_at = Utc::now();
+
+        let _: Option<RoadmapDB> = db
+            .update(quiz.roadmap_id.clone())
+            .content(roadmap)
+            .await
+            .into_server_error()?;
+        sync_node_skill(
+            &user_id,
+            &skill_name,
+            Some((result.score, ProficiencySource::Quiz)),
+        )
+        .await?;
+        sync_review_cards(&user_id).await?;
+    }
+
+    Ok(result)
+}
+
+/// Brings the user's review cards in line with their roadmaps: every completed node gets a
+/// card, and cards for nodes that were un-completed or deleted are dropped. Run wherever
+/// completion changes.
+#[cfg(feature = "server")]
+async fn sync_review_cards(user_id: &RecordId) -> Result<(), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    // Whatever is left no longer matches a completed node.
+    for orphan in backfill_review_cards(user_id).await? {
+        if let Some(id) = orphan.id {
+            let _: Option<ReviewCardDB> = db.delete(id).await.into_server_error()?;
+        }
+    }
+    Ok(())
+}
+
+/// Gives every completed node of the user's roadmaps a review card, first due one interval
+/// after it was learnt, and returns the cards that match no completed node. Only ever
+/// adds cards, so reading the queue can run it to cover steps completed before reviews
+/// existed.
+#[cfg(feature = "server")]
+async fn backfill_review_cards(user_id: &RecordId) -> Result<Vec<ReviewCardDB>, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let user: UserDB = db
+        .select(user_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("User not found"))?;
+
+    let mut result = db
+        .query("SELECT * FROM roadmaps WHERE user_id = $user_id")
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+    let roadmaps: Vec<RoadmapDB> = result.take(0).into_server_error()?;
+
+    let mut result = db
+        .query("SELECT * FROM reviews WHERE user_id = $user_id")
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+    let mut existing: Vec<ReviewCardDB> = result.take(0).into_server_error()?;
+
+    for roadmap in &roadmaps {
+        let Some(roadmap_id) = roadmap.id.clone() else {
+            continue;
+        };
+        for node in roadmap.nodes.iter().filter(|n| n.is_completed) {
+            let position = existing
+                .iter()
+                .position(|c| c.roadmap_id == roadmap_id && c.node_id == node.id);
+            if let Some(pos) = position {
+                existing.remove(pos);
+                continue;
+            }
+
+            // skills_learned holds the completion timestamp; fall back to the roadmap's.
+            let completed_at = user
+                .skills_learned
+                .iter()
+                .find(|s| s.is_named(&node.skill_name))
+                .and_then(|s| s.date_learnt)
+                .unwrap_or(roadmap.updated_at);
+            let card = ReviewCardDB {
+                id: None,
+                user_id: user_id.clone(),
+                roadmap_id: roadmap_id.clone(),
+                node_id: node.id.clone(),
+                skill_name: node.skill_name.clone(),
+                schedule: ReviewSchedule::starting_from(completed_at),
+                last_quiz_id: None,
+                created_at: Utc::now(),
+            };
+            let _: Option<ReviewCardDB> = db
+                .create("reviews")
+                .content(card)
+                .await
+                .into_server_error()?;
+        }
+    }
+    Ok(existing)
+}
+
+#[server]
+pub async fn get_due_reviews(session_token: String) -> Result<Vec<ReviewItem>, ServerFnError> {
+    #[derive(serde::Deserialize)]
+    struct RoadmapTitle {
+        id: RecordId,
+        skill_name: String,
+    }
+
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let now = Utc::now();
+    backfill_review_cards(&user_id).await?;
+
+    let mut result = db
+        .query("SELECT * FROM reviews WHERE user_id = $user_id")
+        .query("SELECT id, skill_name FROM roadmaps WHERE user_id = $user_id")
+        .bind(("user_id", user_id))
+        .await
+        .into_server_error()?;
+    let cards: Vec<ReviewCardDB> = result.take(0).into_server_error()?;
+    let titles: HashMap<String, String> = result
+        .take::<Vec<RoadmapTitle>>(1)
+        .into_server_error()?
+        .into_iter()
+        .map(|r| (r.id.to_string(), r.skill_name))
+        .collect();
+
+    let mut due: Vec<ReviewItem> = cards
+        .into_iter()
+        .filter(|card| card.schedule.is_due(now))
+        .map(|card| {
+            let roadmap_id = card.roadmap_id.to_string();
+            ReviewItem {
+                review_id: card.id.map(|r| r.to_string()).unwrap_or_default(),
+                roadmap_title: titles.get(&roadmap_id).cloned().unwrap_or_default(),
+                roadmap_id,
+                node_id: card.node_id,
+                skill_name: card.skill_name,
+                schedule: card.schedule,
+            }
+        })
+        .collect();
+    due.sort_by_key(|r| r.schedule.due_at);
+    due.truncate(REVIEW_QUEUE_LIMIT);
+    Ok(due)
+}
+
+#[cfg(feature = "server")]
+async fn owned_review_card(
+    review_id: &str,
+    user_id: &RecordId,
+) -> Result<(RecordId, ReviewCardDB), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let id = RecordId::from_str(review_id)
+        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let card: ReviewCardDB = db
+        .select(&id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Review not found"))?;
+    if &card.user_id != user_id {
+        return Err(ServerFnError::new("You do not have access to this review"));
+    }
+    Ok((id, card))
+}
+
+/// Hands back review questions for a card. The latest quiz for the node is reused only if it
+/// was never submitted, e.g. a review that was opened and abandoned; otherwise, including
+/// the mastery quiz the node was completed with, a fresh one is written.
+#[server]
+pub async fn start_review(
+    session_token: String,
+    review_id: String,
+) -> Result<NodeQuiz, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (_, card) = owned_review_card(&review_id, &user_id).await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM node_quizzes WHERE user_id = $user_id AND roadmap_id = $roadmap_id \
+             AND node_id = $node_id ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(("user_id", user_id.clone()))
+        .bind(("roadmap_id", card.roadmap_id.clone()))
+        .bind(("node_id", card.node_id.clone()))
+        .await
+        .into_server_error()?;
+    let latest: Option<NodeQuizDB> = result.take(0).into_server_error()?;
+
+    if let Some(quiz) = latest
+        && quiz.id.is_some()
+        && quiz.consumed_at.is_none()
+    {
+        return Ok(NodeQuiz::from(quiz));
+    }
+
+    let roadmap: RoadmapDB = db
+        .select(&card.roadmap_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Roadmap not found"))?;
+    let node = roadmap
+        .nodes
+        .iter()
+        .find(|n| n.id == card.node_id)
+        .ok_or_else(|| ServerFnError::new("Node not found in roadmap"))?;
+    let quiz = create_node_quiz(user_id, card.roadmap_id, node).await?;
+    Ok(NodeQuiz::from(quiz))
+}
+
+#[server]
+pub async fn submit_review(
+    session_token: String,
+    review_id: String,
+    quiz_id: String,
+    responses: Vec<QuestionResponse>,
+) -> Result<ReviewOutcome, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, mut card) = owned_review_card(&review_id, &user_id).await?;
+
+    let quiz_id = RecordId::from_str(&quiz_id).into_server_error()?;
+    let quiz: NodeQuizDB = db
+        .select(&quiz_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Quiz not found"))?;
+    // Copies of a roadmap share node ids, so the roadmap has to match too.
+    if quiz.user_id != user_id || quiz.roadmap_id != card.roadmap_id || quiz.node_id != card.node_id
+    {
+        return Err(ServerFnError::new(
+            "This quiz does not belong to the review",
+        ));
+    }
+    consume_quiz(&quiz_id).await?;
+
+    let result = grade_quiz(&quiz.answer_keys, &responses);
+    let now = Utc::now();
+
+    let attempt = QuizAttempt {
+        id: None,
+        quiz_id: quiz_id.clone(),
+        user_id: user_id.clone(),
+        roadmap_id: quiz.roadmap_id.clone(),
+        node_id: quiz.node_id.clone(),
+        score: result.score,
+        passed: result.passed,
+        submitted_at: now,
+    };
+    let _: Option<QuizAttempt> = db
+        .create("quiz_attempts")
+        .content(attempt)
+        .await
+        .into_server_error()?;
+
+    card.schedule
+        .record_review(ReviewSchedule::quality_from_score(result.score), now);
+    card.last_quiz_id = Some(quiz_id);
+    let skill_name = card.skill_name.clone();
+    let outcome = ReviewOutcome {
+        result,
+        next_due: card.schedule.due_at,
+        interval_days: card.schedule.interval_days,
+    };
+
+    let _: Option<ReviewCardDB> = db.update(id).content(card).await.into_server_error()?;
+    record_skill_evidence(
+        &user_id,
+        &skill_name,
+        outcome.result.score,
+        ProficiencySource::Quiz,
+    )
+    .await?;
+
+    Ok(outcome)
+}
+
+/// Escapes a value for an iCalendar TEXT property (RFC 5545 §3.3.11).
+#[cfg(feature = "server")]
+fn ics_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line at 75 octets, as iCalendar requires, without splitting a character.
+#[cfg(feature = "server")]
+fn ics_fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// Renders one all-day event per scheduled (i.e. not yet completed) node. Completed nodes
+/// drop out of the calendar. The roadmaps share one weekly budget, each starting where the
+/// one before it ends, from the last time any of them changed rather than from `now`, so
+/// refetching doesn't move events. SEQUENCE is that same time, so it moves with DTSTART.
+#[cfg(feature = "server")]
+fn build_ics(roadmaps: &[Roadmap], weekly_hours: f32, now: DateTime<Utc>) -> String {
+    let stamp = now.format("%Y%m%dT%H%M%SZ");
+    let anchor = roadmaps.iter().map(|r| r.updated_at).max().unwrap_or(now);
+    let mut cursor = anchor;
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//SkillForge//Roadmap Schedule//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:SkillForge".to_string(),
+    ];
+
+    for roadmap in roadmaps {
+        let roadmap_id = roadmap.id.clone().unwrap_or_default();
+        let schedule = roadmap.plan_schedule(weekly_hours, cursor);
+        cursor = schedule.projected_finish;
+        for entry in &schedule.upcoming {
+            let Some(node) = roadmap.nodes.iter().find(|n| n.id == entry.node_id) else {
+                continue;
+            };
+            let mut description = format!(
+                "{}\n\nEstimated effort: {:.1} h",
+                node.description, entry.hours
+            );
+            if !node.resources.is_empty() {
+                description.push_str("\n\nResources:");
+                for resource in &node.resources {
+                    match resource.url.as_deref().filter(|u| !u.is_empty()) {
+                        Some(url) => {
+                            description.push_str(&format!("\n- {}: {}", resource.title, url))
+                        }
+                        None => description.push_str(&format!("\n- {}", resource.title)),
+                    }
+                }
+            }
+            // All-day events; DTEND is exclusive, so a node finishing today ends tomorrow.
+            let start = entry.starts_on.date_naive();
+            let end = entry
+                .ends_on
+                .date_naive()
+                .max(start)
+                .checked_add_days(Days::new(1))
+                .unwrap_or(chrono::NaiveDate::MAX);
+
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!(
+                "UID:{}-{}@skillforge",
+                roadmap_id.replace(':', "-"),
+                node.id
+            ));
+            lines.push(format!("DTSTAMP:{stamp}"));
+            lines.push(format!(
+                "LAST-MODIFIED:{}",
+                roadmap.updated_at.format("%Y%m%dT%H%M%SZ")
+            ));
+            lines.push(format!("SEQUENCE:{}", anchor.timestamp()));
+            lines.push(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+            lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+            lines.push(format!(
+                "SUMMARY:{}",
+                ics_escape(&format!("{} — {}", roadmap.skill_name, node.skill_name))
+            ));
+            lines.push(format!("DESCRIPTION:{}", ics_escape(&description)));
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|l| ics_fold(l)).collect()
+}
+
+#[server]
+pub async fn export_roadmap_ics(
+    session_token: String,
+    roadmap_id: String,
+) -> Result<String, ServerFnError> {
+    let (user, user_id) = session_user(session_token).await?;
+    let (_, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    Ok(build_ics(
+        &[Roadmap::from(roadmap)],
+        user.preferences.weekly_hours(),
+        Utc::now(),
+    ))
+}
+
+#[server]
+pub async fn export_roadmap(
+    session_token: String,
+    roadmap_id: String,
+    format: ExportFormat,
+) -> Result<ExportedFile, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    let (_, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    crate::roadmap_io::export_roadmap(&Roadmap::from(roadmap), format, Utc::now())
+        .into_server_error()
+}
+
+/// The active course a resource points at, matched by URL or title.
+#[cfg(feature = "server")]
+async fn course_for_resource(
+    resource: &LearningResource,
+) -> Result<Option<CoursesDataWithEmbeddings>> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM courses WHERE (string::lowercase(title) = $title \
+             OR (url != '' AND url = $url)) AND deactivated != true LIMIT 1",
+        )
+        .bind(("title", resource.title.trim().to_lowercase()))
+        .bind(("url", resource.url.clone().unwrap_or_default()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Fills in missing details of an imported resource from the matching course, if the
+/// title or URL is in the corpus.
+#[cfg(feature = "server")]
+async fn resolve_resource(resource: &mut LearningResource) -> Result<()> {
+    if let Some(course) = course_for_resource(resource).await? {
+        if resource.url.as_deref().is_none_or(str::is_empty) && !course.url.is_empty() {
+            resource.url = Some(course.url);
+        }
+        // Only fill in what the import didn't say, from where the course actually lives.
+        if resource.platform.is_empty() || resource.platform == "Web" {
+            let platform =
+                crate::utils::platform_for_url(resource.url.as_deref().unwrap_or_default());
+            if !platform.is_empty() {
+                resource.platform = platform;
+            }
+        }
+        if resource.resource_type == "Link" {
+            resource.resource_type = course.ctype;
+        }
+        resource.estimated_minutes = resource.estimated_minutes.or(course.duration_minutes);
+        resource.title = course.title;
+    }
+    Ok(())
+}
+
+/// Creates a roadmap from a JSON export or a Markdown outline and returns its ID.
+#[server]
+pub async fn import_roadmap(
+    session_token: String,
+    contents: String,
+) -> Result<String, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+
+    let mut imported = crate::roadmap_io::parse_import(&contents).into_server_error()?;
+    for node in &mut imported.nodes {
+        for resource in &mut node.resources {
+            resolve_resource(resource).await.into_server_error()?;
+        }
+    }
+
+    let roadmap = RoadmapDB {
+        id: None,
+        user_id,
+        skills_prev_known: imported.skills_prev_known,
+        learning_outcomes: imported.learning_outcomes,
+        skill_name: imported.skill_name,
+        nodes: imported.nodes,
+        require_mastery_quiz: imported.require_mastery_quiz,
+        share_links: Vec::new(),
+        origin: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    let created: RoadmapDB = db
+        .create("roadmaps")
+        .content(roadmap)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Failed to save imported roadmap"))?;
+    sync_review_cards(&created.user_id).await?;
+
+    Ok(created.id.map(|r| r.to_string()).unwrap_or_default())
+}
+
+#[cfg(feature = "server")]
+async fn calendar_feed_for(user_id: &RecordId) -> Result<Option<CalendarFeedDB>, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM calendar_feeds WHERE user_id = $user_id LIMIT 1")
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+    result.take(0).into_server_error()
+}
+
+/// Path of the user's calendar feed, if they have one.
+#[server]
+pub async fn get_calendar_feed_path(
+    session_token: String,
+) -> Result<Option<String>, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    Ok(calendar_feed_for(&user_id)
+        .await?
+        .map(|feed| format!("/api/calendar/{}", feed.token)))
+}
+
+/// Issues a new feed token, invalidating any previous one, and returns the feed path.
+#[server]
+pub async fn rotate_calendar_feed(session_token: String) -> Result<String, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+
+    db.query("DELETE calendar_feeds WHERE user_id = $user_id")
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+
+    let feed = CalendarFeedDB {
+        id: None,
+        user_id,
+        token: generate_session_token(),
+        created_at: Utc::now(),
+    };
+    let created: CalendarFeedDB = db
+        .create("calendar_feeds")
+        .content(feed)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Failed to create calendar feed"))?;
+    Ok(format!("/api/calendar/{}", created.token))
+}
+
+#[server]
+pub async fn revoke_calendar_feed(session_token: String) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    db.query("DELETE calendar_feeds WHERE user_id = $user_id")
+        .bind(("user_id", user_id))
+        .await
+        .into_server_error()?;
+    Ok(())
+}
+
+/// Subscribable iCalendar feed covering all of a user's roadmaps. It is rebuilt on every
+/// fetch, so it always reflects current progress and preferences.
+#[get("/api/calendar/{token}")]
+pub async fn calendar_feed(
+    token: String,
+) -> Result<dioxus::fullstack::response::Response, ServerFnError> {
+    use dioxus::fullstack::{body::Body, http::header, response::Response};
+
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM calendar_feeds WHERE token = $token LIMIT 1")
+        .bind(("token", token))
+        .await
+        .into_server_error()?;
+    let feed: CalendarFeedDB = result
+        .take::<Option<CalendarFeedDB>>(0)
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Calendar feed not found"))?;
+
+    let user: UserDB = db
+        .select(&feed.user_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("User not found"))?;
+    let user = User::from(user);
+
+    let mut result = db
+        .query("SELECT * FROM roadmaps WHERE user_id = $user_id ORDER BY created_at ASC")
+        .bind(("user_id", feed.user_id))
+        .await
+        .into_server_error()?;
+    let roadmaps: Vec<RoadmapDB> = result.take(0).into_server_error()?;
+    let roadmaps: Vec<Roadmap> = roadmaps.into_iter().map(Roadmap::from).collect();
+
+    let ics = build_ics(&roadmaps, user.preferences.weekly_hours(), Utc::now());
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .header(
+            header::CONTENT_DISPOSITION,
+            "inline; filename=\"skillforge.ics\"",
+        )
+        .body(Body::from(ics))
+        .into_server_error()
+}
+
+/// Creates a public, read-only link to one of the user's roadmaps. Progress and the
+/// owner's name stay hidden from viewers unless opted in.
+#[server]
+pub async fn create_share_link(
+    session_token: String,
+    roadmap_id: String,
+    expires_in_days: Option<u32>,
+    include_progress: bool,
+    include_owner: bool,
+) -> Result<ShareLink, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+
+    let now = Utc::now();
+    let link = ShareLink {
+        token: generate_session_token(),
+        created_at: now,
+        expires_at: expires_in_days.map(|days| now + Duration::days(i64::from(days))),
+        include_progress,
+        include_owner,
+    };
+    // Expired links can never be used again, so drop them while we're rewriting the list.
+    let mut share_links: Vec<ShareLink> = roadmap
+        .share_links
+        .into_iter()
+        .filter(|l| l.is_active(now))
+        .collect();
+    share_links.push(link.clone());
+
+    db.query("UPDATE $id SET share_links = $share_links")
+        .bind(("id", id))
+        .bind(("share_links", share_links))
+        .await
+        .into_server_error()?;
+    Ok(link)
+}
+
+#[server]
+pub async fn list_share_links(
+    session_token: String,
+    roadmap_id: String,
+) -> Result<Vec<ShareLink>, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    let (_, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    Ok(roadmap.share_links)
+}
+
+#[server]
+pub async fn revoke_share_link(
+    session_token: String,
+    roadmap_id: String,
+    token: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+
+    let share_links: Vec<ShareLink> = roadmap
+        .share_links
+        .into_iter()
+        .filter(|l| l.token != token)
+        .collect();
+    db.query("UPDATE $id SET share_links = $share_links")
+        .bind(("id", id))
+        .bind(("share_links", share_links))
+        .await
+        .into_server_error()?;
+    Ok(())
+}
+
+/// Looks up the roadmap behind a share token, along with the link itself. Fails for unknown,
+/// revoked and expired tokens alike.
+#[cfg(feature = "server")]
+async fn shared_roadmap_record(token: &str) -> Result<(RoadmapDB, ShareLink), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM roadmaps WHERE $token INSIDE share_links.token LIMIT 1")
+        .bind(("token", token.to_string()))
+        .await
+        .into_server_error()?;
+    let roadmap: RoadmapDB = result
+        .take::<Option<RoadmapDB>>(0)
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Shared roadmap not found"))?;
+    let link = roadmap
+        .share_links
+        .iter()
+        .find(|l| l.token == token)
+        .filter(|l| l.is_active(Utc::now()))
+        .cloned()
+        .ok_or_else(|| ServerFnError::new("This share link has expired"))?;
+    Ok((roadmap, link))
+}
+
+/// Resolves a share token to a read-only copy of the roadmap. No session is required; the
+/// roadmap's id, owner and origin are always stripped so the token can't be used to reach
+/// anything beyond what was shared.
+#[server]
+pub async fn get_shared_roadmap(token: String) -> Result<SharedRoadmap, ServerFnError> {
+    let db = get_db().await?;
+    let (roadmap, link) = shared_roadmap_record(&token).await?;
+
+    let owner_name = if link.include_owner {
+        let owner: Option<UserDB> = db.select(&roadmap.user_id).await.into_server_error()?;
+        owner.map(|u| u.name)
+    } else {
+        None
+    };
+
+    let mut roadmap = Roadmap::from(roadmap);
+    roadmap.id = None;
+    roadmap.user_id = String::new();
+    roadmap.origin = None;
+    if !link.include_progress {
+        roadmap.skills_prev_known.clear();
+        for node in &mut roadmap.nodes {
+            node.is_completed = false;
+            node.mastery_score = None;
+        }
+    }
+
+    Ok(SharedRoadmap {
+        roadmap,
+        owner_name,
+        include_progress: link.include_progress,
+    })
+}
+
+/// Saves a fresh copy of `source` for `user_id`, with progress reset and `origin` recorded.
+#[cfg(feature = "server")]
+async fn save_roadmap_copy(
+    source: RoadmapDB,
+    user_id: RecordId,
+    origin: RoadmapOriginDB,
+) -> Result<RecordId, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let now = Utc::now();
+    let nodes = source
+        .nodes
+        .into_iter()
+        .map(|node| RoadmapNode {
+            is_completed: false,
+            mastery_score: None,
+            ..node
+        })
+        .collect();
+    let roadmap = RoadmapDB {
+        id: None,
+        user_id,
+        skills_prev_known: source.skills_prev_known,
+        learning_outcomes: source.learning_outcomes,
+        skill_name: source.skill_name,
+        nodes,
+        require_mastery_quiz: source.require_mastery_quiz,
+        share_links: Vec::new(),
+        origin: Some(origin),
+        created_at: now,
+        updated_at: now,
+    };
+    let created: RoadmapDB = db
+        .create("roadmaps")
+        .content(roadmap)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Failed to save the copied roadmap"))?;
+    created
+        .id
+        .ok_or_else(|| ServerFnError::new("Copied roadmap has no id"))
+}
+
+/// Copies a roadmap the caller owns, or one shared with them, into their account. The copy
+/// starts with no progress and remembers its origin.
+#[server]
+pub async fn clone_roadmap(
+    session_token: String,
+    source: CloneSource,
+) -> Result<String, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+
+    // A shared original is only ever reached through its token, never by id.
+    let (source_id, mut source, share_token) = match source {
+        CloneSource::Owned { roadmap_id } => {
+            let (id, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+            (Some(id), roadmap, None)
+        }
+        CloneSource::Shared { token } => {
+            let (roadmap, _) = shared_roadmap_record(&token).await?;
+            (None, roadmap, Some(token))
+        }
+    };
+    // The skills the owner already knew describe them, not whoever is making the copy.
+    if source.user_id != user_id {
+        source.skills_prev_known.clear();
+    }
+
+    let origin = RoadmapOriginDB {
+        roadmap_id: source_id,
+        share_token,
+        skill_name: source.skill_name.clone(),
+        assignment_id: None,
+        source_updated_at: source.updated_at,
+        cloned_at: Utc::now(),
+    };
+    let id = save_roadmap_copy(source, user_id, origin).await?;
+    Ok(id.to_string())
+}
+
+#[cfg(feature = "server")]
+async fn org_membership(
+    org_id: &RecordId,
+    user_id: &RecordId,
+) -> Result<Option<OrgMembershipDB>, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM org_members WHERE org_id = $org_id AND user_id = $user_id LIMIT 1")
+        .bind(("org_id", org_id.clone()))
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+    result.take(0).into_server_error()
+}
+
+/// Fails unless `user_id` holds at least `role` in the organization.
+#[cfg(feature = "server")]
+async fn require_org_role(
+    org_id: &RecordId,
+    user_id: &RecordId,
+    role: OrgRole,
+) -> Result<OrgMembershipDB, ServerFnError> {
+    let membership = org_membership(org_id, user_id)
+        .await?
+        .ok_or_else(|| ServerFnError::new("You are not a member of this organization"))?;
+    if membership.role < role {
+        return Err(ServerFnError::new(format!(
+            "Only a {} or above can do that",
+            role.label().to_lowercase()
+        )));
+    }
+    Ok(membership)
+}
+
+#[cfg(feature = "server")]
+async fn org_owner_count(org_id: &RecordId) -> Result<usize, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM org_members WHERE org_id = $org_id")
+        .bind(("org_id", org_id.clone()))
+        .await
+        .into_server_error()?;
+    let memberships: Vec<OrgMembershipDB> = result.take(0).into_server_error()?;
+    Ok(memberships
+        .iter()
+        .filter(|m| m.role == OrgRole::Owner)
+        .count())
+}
+
+/// Loads a team the caller is allowed to manage.
+#[cfg(feature = "server")]
+async fn managed_team(
+    team_id: &str,
+    user_id: &RecordId,
+) -> Result<(RecordId, TeamDB), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let id =
+        RecordId::from_str(team_id).map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let team: TeamDB = db
+        .select(&id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Team not found"))?;
+    require_org_role(&team.org_id, user_id, OrgRole::Manager).await?;
+    Ok((id, team))
+}
+
+#[cfg(feature = "server")]
+async fn team_assignments(team_id: &RecordId) -> Result<Vec<TeamAssignmentDB>, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM team_assignments WHERE team_id = $team_id ORDER BY assigned_at ASC")
+        .bind(("team_id", team_id.clone()))
+        .await
+        .into_server_error()?;
+    result.take(0).into_server_error()
+}
+
+/// Makes sure every team member has their own copy of each roadmap assigned to the team.
+/// A member who deleted their copy gets a new one the next time the team changes.
+#[cfg(feature = "server")]
+async fn sync_team_assignments(team_id: &RecordId, team: &TeamDB) -> Result<(), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+
+    for assignment in team_assignments(team_id).await? {
+        let Some(assignment_id) = assignment.id.clone() else {
+            continue;
+        };
+        // Existing copies carry on if the template has since been deleted.
+        let template: Option<RoadmapDB> = db
+            .select(&assignment.template_roadmap_id)
+            .await
+            .into_server_error()?;
+        let Some(template) = template else {
+            continue;
+        };
+
+        let mut result = db
+            .query("SELECT VALUE user_id FROM roadmaps WHERE origin.assignment_id = $assignment_id")
+            .bind(("assignment_id", assignment_id.clone()))
+            .await
+            .into_server_error()?;
+        let assigned: Vec<RecordId> = result.take(0).into_server_error()?;
+
+        for member_id in &team.member_ids {
+            if assigned.contains(member_id) {
+                continue;
+            }
+            let mut copy = template.clone();
+            copy.skills_prev_known.clear();
+            let origin = RoadmapOriginDB {
+                // The assignment links the copy to its template; the copy itself doesn't.
+                roadmap_id: None,
+                share_token: None,
+                skill_name: template.skill_name.clone(),
+                assignment_id: Some(assignment_id.clone()),
+                source_updated_at: template.updated_at,
+                cloned_at: Utc::now(),
+            };
+            save_roadmap_copy(copy, member_id.clone(), origin).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+async fn organization_view(
+    org_id: RecordId,
+    org: OrganizationDB,
+    my_role: OrgRole,
+    viewer: &RecordId,
+) -> Result<Organization, ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let mut result = db
+        .query("SELECT * FROM org_members WHERE org_id = $org_id ORDER BY joined_at ASC")
+        .query("SELECT * FROM teams WHERE org_id = $org_id ORDER BY created_at ASC")
+        .bind(("org_id", org_id.clone()))
+        .await
+        .into_server_error()?;
+    let memberships: Vec<OrgMembershipDB> = result.take(0).into_server_error()?;
+    let teams: Vec<TeamDB> = result.take(1).into_server_error()?;
+
+    let mut members = Vec::new();
+    for membership in memberships {
+        let user: Option<UserDB> = db.select(&membership.user_id).await.into_server_error()?;
+        if let Some(user) = user {
+            members.push(OrgMember {
+                user_id: membership.user_id.to_string(),
+                username: user.username,
+                name: user.name,
+                role: membership.role,
+            });
+        }
+    }
+
+    let mut team_views = Vec::new();
+    for team in teams {
+        let Some(team_id) = team.id else {
+            continue;
+        };
+        let assignments = team_assignments(&team_id).await?;
+        team_views.push(Team {
+            id: team_id.to_string(),
+            name: team.name,
+            member_ids: team.member_ids.iter().map(|id| id.to_string()).collect(),
+            assignments: assignments
+                .into_iter()
+                .map(|a| TeamAssignment::for_viewer(a, viewer))
+                .collect(),
+        });
+    }
+
+    Ok(Organization {
+        id: org_id.to_string(),
+        name: org.name,
+        my_role,
+        members,
+        teams: team_views,
+        created_at: org.created_at,
+    })
+}
+
+/// Every organization the user belongs to, with its members and teams.
+#[server]
+pub async fn get_organizations(session_token: String) -> Result<Vec<Organization>, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let mut result = db
+        .query("SELECT * FROM org_members WHERE user_id = $user_id ORDER BY joined_at ASC")
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+    let memberships: Vec<OrgMembershipDB> = result.take(0).into_server_error()?;
+
+    let mut organizations = Vec::new();
+    for membership in memberships {
+        let org: Option<OrganizationDB> =
+            db.select(&membership.org_id).await.into_server_error()?;
+        if let Some(org) = org {
+            organizations
+                .push(organization_view(membership.org_id, org, membership.role, &user_id).await?);
+        }
+    }
+    Ok(organizations)
+}
+
+/// Creates an organization with the caller as its owner.
+#[server]
+pub async fn create_organization(
+    session_token: String,
+    name: String,
+) -> Result<String, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(ServerFnError::new("Organization name can't be empty"));
+    }
+
+    let org: OrganizationDB = db
+        .create("organizations")
+        .content(OrganizationDB {
+            id: None,
+            name,
+            created_at: Utc::now(),
+        })
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Failed to create organization"))?;
+    let org_id = org
+        .id
+        .ok_or_else(|| ServerFnError::new("Organization has no id"))?;
+
+    let _: Option<OrgMembershipDB> = db
+        .create("org_members")
+        .content(OrgMembershipDB {
+            id: None,
+            org_id: org_id.clone(),
+            user_id,
+            role: OrgRole::Owner,
+            joined_at: Utc::now(),
+        })
+        .await
+        .into_server_error()?;
+    Ok(org_id.to_string())
+}
+
+/// Adds an existing user to the organization. Managers can add members; only owners can
+/// hand out the manager or owner role.
+#[server]
+pub async fn add_org_member(
+    session_token: String,
+    org_id: String,
+    username: String,
+    role: OrgRole,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let org_id =
+        RecordId::from_str(&org_id).map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let caller = require_org_role(&org_id, &user_id, OrgRole::Manager).await?;
+    if role > OrgRole::Member && caller.role < OrgRole::Owner {
+        return Err(ServerFnError::new(
+            "Only owners can add managers or other owners",
+        ));
+    }
+
+    let mut result = db
+        .query("SELECT * FROM users WHERE username = $username LIMIT 1")
+        .bind(("username", username.trim().to_string()))
+        .await
+        .into_server_error()?;
+    let user: UserDB = result
+        .take::<Option<UserDB>>(0)
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("No user with that username"))?;
+    let member_id = user
+        .id
+        .ok_or_else(|| ServerFnError::new("User has no ID"))?;
+    if org_membership(&org_id, &member_id).await?.is_some() {
+        return Err(ServerFnError::new("That user is already a member"));
+    }
+
+    let _: Option<OrgMembershipDB> = db
+        .create("org_members")
+        .content(OrgMembershipDB {
+            id: None,
+            org_id,
+            user_id: member_id,
+            role,
+            joined_at: Utc::now(),
+        })
+        .await
+        .into_server_error()?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_org_member_role(
+    session_token: String,
+    org_id: String,
+    member_id: String,
+    role: OrgRole,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let org_id =
+        RecordId::from_str(&org_id).map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let member_id = RecordId::from_str(&member_id)
+        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    require_org_role(&org_id, &user_id, OrgRole::Owner).await?;
+
+    let target = org_membership(&org_id, &member_id)
+        .await?
+        .ok_or_else(|| ServerFnError::new("That user is not a member"))?;
+    if target.role == OrgRole::Owner
+        && role != OrgRole::Owner
+        && org_owner_count(&org_id).await? == 1
+    {
+        return Err(ServerFnError::new(
+            "An organization needs at least one owner",
+        ));
+    }
+
+    db.query("UPDATE org_members SET role = $role WHERE org_id = $org_id AND user_id = $user_id")
+        .bind(("role", role))
+        .bind(("org_id", org_id))
+        .bind(("user_id", member_id))
+        .await
+        .into_server_error()?;
+    Ok(())
+}
+
+/// Removes someone from the organization and all of its teams. Anyone can leave; owners can
+/// remove anyone and managers can remove plain members. Their roadmap copies stay theirs.
+#[server]
+pub async fn remove_org_member(
+    session_token: String,
+    org_id: String,
+    member_id: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let org_id =
+        RecordId::from_str(&org_id).map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let member_id = RecordId::from_str(&member_id)
+        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let caller = require_org_role(&org_id, &user_id, OrgRole::Member).await?;
+
+    let target = org_membership(&org_id, &member_id)
+        .await?
+        .ok_or_else(|| ServerFnError::new("That user is not a member"))?;
+    let allowed = member_id == user_id
+        || caller.role == OrgRole::Owner
+        || (caller.role == OrgRole::Manager && target.role == OrgRole::Member);
+    if !allowed {
+        return Err(ServerFnError::new("You can't remove this member"));
+    }
+    if target.role == OrgRole::Owner && org_owner_count(&org_id).await? == 1 {
+        return Err(ServerFnError::new(
+            "An organization needs at least one owner",
+        ));
+    }
+
+    db.query("DELETE org_members WHERE org_id = $org_id AND user_id = $user_id")
+        .query("UPDATE teams SET member_ids -= $user_id WHERE org_id = $org_id")
+        .bind(("org_id", org_id))
+        .bind(("user_id", member_id))
+        .await
+        .into_server_error()?;
+    Ok(())
+}
+
+#[server]
+pub async fn create_team(
+    session_token: String,
+    org_id: String,
+    name: String,
+) -> Result<String, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let org_id =
+        RecordId::from_str(&org_id).map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    require_org_role(&org_id, &user_id, OrgRole::Manager).await?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(ServerFnError::new("Team name can't be empty"));
+    }
+
+    let team: TeamDB = db
+        .create("teams")
+        .content(TeamDB {
+            id: None,
+            org_id,
+            name,
+            member_ids: Vec::new(),
+            created_at: Utc::now(),
+        })
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Failed to create team"))?;
+    Ok(team.id.map(|r| r.to_string()).unwrap_or_default())
+}
+
+/// Adds an organization member to a team, or takes them off it. New members immediately
+/// get their own copy of every roadmap assigned to the team.
+#[server]
+pub async fn set_team_membership(
+    session_token: String,
+    team_id: String,
+    member_id: String,
+    is_member: bool,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (team_id, mut team) = managed_team(&team_id, &user_id).await?;
+    let member_id = RecordId::from_str(&member_id)
+        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+
+    if is_member {
+        if org_membership(&team.org_id, &member_id).await?.is_none() {
+            return Err(ServerFnError::new(
+                "Only members of the organization can join its teams",
+            ));
+        }
+        if !team.member_ids.contains(&member_id) {
+            team.member_ids.push(member_id);
+        }
+    } else {
+        team.member_ids.retain(|id| id != &member_id);
+    }
+
+    db.query("UPDATE $id SET member_ids = $member_ids")
+        .bind(("id", team_id.clone()))
+        .bind(("member_ids", team.member_ids.clone()))
+        .await
+        .into_server_error()?;
+    if is_member {
+        sync_team_assignments(&team_id, &team).await?;
+    }
+    Ok(())
+}
+
+/// Publishes one of the caller's roadmaps as a template for the team. Every member gets
+/// their own copy to work through.
+#[server]
+pub async fn assign_team_roadmap(
+    session_token: String,
+    team_id: String,
+    roadmap_id: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (team_id, team) = managed_team(&team_id, &user_id).await?;
+    let (template_id, template) = owned_roadmap(&roadmap_id, &user_id).await?;
+
+    if team_assignments(&team_id)
+        .await?
+        .iter()
+        .any(|a| a.template_roadmap_id == template_id)
+    {
+        return Err(ServerFnError::new(
+            "This roadmap is already assigned to the team",
+        ));
+    }
+
+    let _: Option<TeamAssignmentDB> = db
+        .create("team_assignments")
+        .content(TeamAssignmentDB {
+            id: None,
+            team_id: team_id.clone(),
+            template_roadmap_id: template_id,
+            skill_name: template.skill_name,
+            assigned_by: user_id,
+            assigned_at: Utc::now(),
+        })
+        .await
+        .into_server_error()?;
+    sync_team_assignments(&team_id, &team).await
+}
+
+/// Completion of each assigned roadmap across the team, one row per member.
+#[server]
+pub async fn get_team_progress(
+    session_token: String,
+    team_id: String,
+) -> Result<TeamProgress, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (team_id, team) = managed_team(&team_id, &user_id).await?;
+
+    let mut members = Vec::new();
+    for member_id in &team.member_ids {
+        let user: Option<UserDB> = db.select(member_id).await.into_server_error()?;
+        if let Some(user) = user {
+            members.push((member_id.clone(), user.name));
+        }
+    }
+
+    let mut assignments = Vec::new();
+    for assignment in team_assignments(&team_id).await? {
+        let Some(assignment_id) = assignment.id else {
+            continue;
+        };
+        let mut result = db
+            .query("SELECT * FROM roadmaps WHERE origin.assignment_id = $assignment_id ORDER BY created_at DESC")
+            .bind(("assignment_id", assignment_id.clone()))
+            .await
+            .into_server_error()?;
+        let copies: Vec<RoadmapDB> = result.take(0).into_server_error()?;
+
+        let member_progress = members
+            .iter()
+            .map(|(member_id, name)| {
+                let copy = copies.iter().find(|r| &r.user_id == member_id);
+                MemberProgress {
+                    user_id: member_id.to_string(),
+                    name: name.clone(),
+                    has_copy: copy.is_some(),
+                    completed_steps: copy
+                        .map(|r| r.nodes.iter().filter(|n| n.is_completed).count())
+                        .unwrap_or(0),
+                    total_steps: copy.map(|r| r.nodes.len()).unwrap_or(0),
+                    last_activity: copy
+                        .filter(|r| r.updated_at > r.created_at)
+                        .map(|r| r.updated_at),
+                }
+            })
+            .collect();
+        assignments.push(AssignmentProgress {
+            assignment_id: assignment_id.to_string(),
+            skill_name: assignment.skill_name,
+            members: member_progress,
+        });
+    }
+
+    Ok(TeamProgress {
+        team_id: team_id.to_string(),
+        team_name: team.name,
+        assignments,
+    })
+}
+
+#[server]
+pub async fn delete_roadmap(
+    session_token: String,
+    roadmap_id: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, _) = owned_roadmap(&roadmap_id, &user_id).await?;
+
+    let deleted: Option<RoadmapDB> = db.delete(id).await.into_server_error()?;
+    if deleted.is_none() {
+        return Err(ServerFnError::new("Roadmap not found or already deleted"));
+    }
+    sync_review_cards(&user_id).await
+}
+
+#[server]
+pub async fn get_progress_report(
+    days: u16,
+    session_token: String,
+) -> Result<Option<HashMap<DateTime<Utc>, u8>>, ServerFnError> {
+    #[derive(serde::Deserialize)]
+    struct UserQueryResult {
+        skills_learned: Option<Vec<UserSkills>>,
+    }
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM sessions WHERE session_token = $session_token")
+        .bind(("session_token", session_token))
+        .await
+        .into_server_error()?;
+
+    let sessions: Vec<Session> = result.take(0).into_server_error()?;
+
+    if let Some(session) = sessions.first() {
+        if session.expires_at < Utc::now() {
+            return Ok(None);
+        }
+        let user_id: RecordId = session.user_id.clone();
+        let curr_datetime: DateTime<Utc> = Utc::now();
+        let start_datetime: DateTime<Utc> = curr_datetime - Days::new(days as u64);
+        // Fetch the record wrapped in the struct
+        let result: Vec<UserQueryResult> = db
+            .query("SELECT skills_learned FROM $user_id")
+            .bind(("user_id", user_id))
+            .await
+            .into_server_error()?
+            .take(0)
+            .into_server_error()?;
+
+        // Unwrap the first result and then the skills vector
+        let all_skills = result.into_iter().next().and_then(|r| r.skills_learned);
+        if all_skills.clone().is_none_or(|x| x.is_empty()) {
             return Ok(None);
         }
-        let mut selected_skills: Vec<UserSkills> = all_skills
+        // Prior knowledge has no learnt date, so it never counts as activity.
+        let mut selected_dates: Vec<DateTime<Utc>> = all_skills
             .unwrap()
             .into_iter()
-            .filter(|x| x.date_learnt > start_datetime)
-            .collect();
-        if selected_skills.is_empty() {
-            return Ok(None);
+            .filter_map(|x| x.date_learnt)
+            .filter(|date| *date > start_datetime)
+            .collect();
+        if selected_dates.is_empty() {
+            return Ok(None);
+        }
+        selected_dates.sort();
+        let mut day_wise_progress: HashMap<DateTime<Utc>, u8> = HashMap::new();
+        let mut temp_datetime = start_datetime;
+        while temp_datetime <= curr_datetime {
+            let day_skills: Vec<&DateTime<Utc>> = selected_dates
+                .iter()
+                .filter(|date| date.date_naive() == temp_datetime.date_naive())
+                .collect();
+            day_wise_progress.insert(temp_datetime, day_skills.len() as u8);
+            temp_datetime = temp_datetime
+                .checked_add_signed(Duration::days(1))
+                .expect("Date overflowed when adding a day");
+        }
+        Ok(Some(day_wise_progress))
+    } else {
+        Ok(None)
+    }
+}
+
+// RESOURCE FEEDBACK
+/// A resource on one of the caller's roadmap steps, with the course it points at.
+#[cfg(feature = "server")]
+async fn owned_resource_course(
+    user_id: &RecordId,
+    roadmap_id: &str,
+    node_id: &str,
+    resource_index: usize,
+) -> Result<CoursesDataWithEmbeddings, ServerFnError> {
+    let (_, roadmap) = owned_roadmap(roadmap_id, user_id).await?;
+    let resource = roadmap
+        .nodes
+        .iter()
+        .find(|n| n.id == node_id)
+        .ok_or_else(|| ServerFnError::new("Node not found in roadmap"))?
+        .resources
+        .get(resource_index)
+        .ok_or_else(|| ServerFnError::new("Resource not found"))?;
+    course_for_resource(resource)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Feedback can only be given on catalog courses"))
+}
+
+#[cfg(feature = "server")]
+async fn my_course_feedback(
+    user_id: &RecordId,
+    course_id: &RecordId,
+) -> Result<Vec<ResourceFeedbackDB>, ServerFnError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM resource_feedback WHERE user_id = $user_id AND course_id = $course_id",
+        )
+        .bind(("user_id", user_id.clone()))
+        .bind(("course_id", course_id.clone()))
+        .await
+        .into_server_error()?;
+    result.take(0).into_server_error()
+}
+
+/// Recounts a course's feedback into the summary kept on the course record.
+#[cfg(feature = "server")]
+async fn refresh_course_feedback(
+    course_id: &RecordId,
+) -> Result<CourseFeedbackSummary, ServerFnError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM resource_feedback WHERE course_id = $course_id")
+        .bind(("course_id", course_id.clone()))
+        .await
+        .into_server_error()?;
+    let feedback: Vec<ResourceFeedbackDB> = result.take(0).into_server_error()?;
+    let mut summary = CourseFeedbackSummary::default();
+    for entry in &feedback {
+        summary.add(entry.kind);
+    }
+    db.query("UPDATE $course_id SET feedback = $summary")
+        .bind(("course_id", course_id.clone()))
+        .bind(("summary", summary.clone()))
+        .await
+        .into_server_error()?;
+    Ok(summary)
+}
+
+/// Feedback on each resource of a roadmap step, in resource order.
+#[server]
+pub async fn get_resource_feedback(
+    session_token: String,
+    roadmap_id: String,
+    node_id: String,
+) -> Result<Vec<ResourceFeedback>, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    let (_, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    let node = roadmap
+        .nodes
+        .iter()
+        .find(|n| n.id == node_id)
+        .ok_or_else(|| ServerFnError::new("Node not found in roadmap"))?;
+
+    let mut feedback = Vec::with_capacity(node.resources.len());
+    for (resource_index, resource) in node.resources.iter().enumerate() {
+        let course = course_for_resource(resource).await.into_server_error()?;
+        let Some((course_id, summary)) = course.and_then(|c| Some((c.id?, c.feedback))) else {
+            feedback.push(ResourceFeedback {
+                resource_index,
+                in_catalog: false,
+                mine: Vec::new(),
+                summary: CourseFeedbackSummary::default(),
+            });
+            continue;
+        };
+        let mine = my_course_feedback(&user_id, &course_id)
+            .await?
+            .into_iter()
+            .map(|f| f.kind)
+            .collect();
+        feedback.push(ResourceFeedback {
+            resource_index,
+            in_catalog: true,
+            mine,
+            summary,
+        });
+    }
+    Ok(feedback)
+}
+
+/// Gives or takes back one kind of feedback on a resource. Giving one side of a pair, like
+/// thumbs up, takes back the other.
+#[server]
+pub async fn toggle_resource_feedback(
+    session_token: String,
+    roadmap_id: String,
+    node_id: String,
+    resource_index: usize,
+    kind: ResourceFeedbackKind,
+) -> Result<ResourceFeedback, ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let course = owned_resource_course(&user_id, &roadmap_id, &node_id, resource_index).await?;
+    let course_id = course
+        .id
+        .ok_or_else(|| ServerFnError::new("Course ID not found"))?;
+
+    let existing = my_course_feedback(&user_id, &course_id).await?;
+    let already_given = existing.iter().any(|f| f.kind == kind);
+    for entry in &existing {
+        if entry.kind == kind || Some(entry.kind) == kind.opposite() {
+            if let Some(id) = entry.id.clone() {
+                let _: Option<ResourceFeedbackDB> = db.delete(id).await.into_server_error()?;
+            }
+        }
+    }
+    // The unique index on (user, course, kind) turns a racing second toggle into an error
+    // instead of a double vote.
+    if !already_given {
+        let _: Option<ResourceFeedbackDB> = db
+            .create("resource_feedback")
+            .content(ResourceFeedbackDB {
+                id: None,
+                user_id: user_id.clone(),
+                course_id: course_id.clone(),
+                kind,
+                created_at: Utc::now(),
+            })
+            .await
+            .into_server_error()?;
+    }
+
+    let summary = refresh_course_feedback(&course_id).await?;
+    let mine = my_course_feedback(&user_id, &course_id)
+        .await?
+        .into_iter()
+        .map(|f| f.kind)
+        .collect();
+    Ok(ResourceFeedback {
+        resource_index,
+        in_catalog: true,
+        mine,
+        summary,
+    })
+}
+
+// COURSE CATALOG
+/// A catalog facet, which [`CourseFilters`] narrows and [`CourseFacets`] counts.
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CatalogFacet {
+    Level,
+    Ctype,
+    Channel,
+    SkillPath,
+}
+
+#[cfg(feature = "server")]
+impl CatalogFacet {
+    const ALL: [CatalogFacet; 4] = [
+        CatalogFacet::Level,
+        CatalogFacet::Ctype,
+        CatalogFacet::Channel,
+        CatalogFacet::SkillPath,
+    ];
+
+    /// The value picked for this facet, if any.
+    fn picked(self, filters: &CourseFilters) -> Option<&str> {
+        match self {
+            CatalogFacet::Level => filters.level.as_deref(),
+            CatalogFacet::Ctype => filters.ctype.as_deref(),
+            CatalogFacet::Channel => filters.channel_name.as_deref(),
+            CatalogFacet::SkillPath => filters.skill_path.as_deref(),
+        }
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    }
+
+    /// Parameter the picked value is bound to, lowercased.
+    fn param(self) -> &'static str {
+        match self {
+            CatalogFacet::Level => "level",
+            CatalogFacet::Ctype => "ctype",
+            CatalogFacet::Channel => "channel_name",
+            CatalogFacet::SkillPath => "skill_path",
+        }
+    }
+
+    /// SurrealQL condition a course must meet to match the picked value.
+    fn condition(self) -> &'static str {
+        match self {
+            CatalogFacet::Level => "string::lowercase(string::trim(level)) = $level",
+            CatalogFacet::Ctype => "string::lowercase(string::trim(ctype)) = $ctype",
+            CatalogFacet::Channel => {
+                "string::lowercase(string::trim(channel_name)) = $channel_name"
+            }
+            CatalogFacet::SkillPath => {
+                "$skill_path IN array::map(\
+                     string::split(string::replace(string::lowercase(skill_path), '->', '>'), '>'), \
+                     |$segment| string::trim($segment))"
+            }
+        }
+    }
+
+    /// SurrealQL counting this facet's values over the courses matching `condition`; skill
+    /// paths count each segment once per course.
+    fn count_query(self, condition: &str) -> String {
+        let value = match self {
+            CatalogFacet::Level => "string::trim(level)",
+            CatalogFacet::Ctype => "string::trim(ctype)",
+            CatalogFacet::Channel => "string::trim(channel_name)",
+            CatalogFacet::SkillPath => {
+                return format!(
+                    "SELECT value, count() AS count FROM (\
+                         SELECT array::distinct(array::filter(array::map(\
+                             string::split(string::replace(skill_path, '->', '>'), '>'), \
+                             |$segment| string::trim($segment)), |$segment| $segment != '')) AS value \
+                         FROM courses WHERE {condition}) \
+                     SPLIT value GROUP BY value"
+                );
+            }
+        };
+        format!(
+            "SELECT value, count() AS count FROM (SELECT {value} AS value FROM courses WHERE {condition}) \
+             WHERE value != '' GROUP BY value"
+        )
+    }
+}
+
+/// SurrealQL condition for active courses matching the search and every picked facet but
+/// `except`, so a facet's counts still offer the values its own pick would rule out.
+#[cfg(feature = "server")]
+fn catalog_condition(
+    search: &str,
+    filters: &CourseFilters,
+    except: Option<CatalogFacet>,
+) -> String {
+    let mut conditions = vec!["deactivated != true", search];
+    conditions.extend(
+        CatalogFacet::ALL
+            .into_iter()
+            .filter(|&facet| Some(facet) != except && facet.picked(filters).is_some())
+            .map(CatalogFacet::condition),
+    );
+    conditions.join(" AND ")
+}
+
+/// Most common first, ties by name.
+#[cfg(feature = "server")]
+fn ranked(mut counts: Vec<FacetCount>) -> Vec<FacetCount> {
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    counts
+}
+
+/// Searches the course catalog. An empty query lists courses alphabetically whatever the mode.
+/// Facets are counted over every course the search and the other picked facets leave.
+#[server]
+pub async fn search_courses(
+    query: String,
+    mode: CourseSearchMode,
+    filters: CourseFilters,
+) -> Result<CourseSearchResults, ServerFnError> {
+    let db = get_db().await?;
+    let query = query.trim();
+
+    let semantic = mode == CourseSearchMode::Semantic && !query.is_empty();
+    let (search, order) = if semantic {
+        (
+            format!("embedding <|{CATALOG_SEARCH_CANDIDATES},400|> $embedding"),
+            "ORDER BY distance ASC",
+        )
+    } else {
+        (
+            "($query = '' \
+                 OR string::contains(string::lowercase(title), $query) \
+                 OR string::contains(string::lowercase(description), $query) \
+                 OR string::contains(string::lowercase(topic), $query) \
+                 OR string::contains(string::lowercase(skill_path), $query) \
+                 OR string::contains(string::lowercase(channel_name), $query))"
+                .to_string(),
+            "ORDER BY title ASC",
+        )
+    };
+    let distance = if semantic {
+        ", vector::distance::knn() AS distance"
+    } else {
+        ""
+    };
+
+    let mut statements = vec![format!(
+        "SELECT *{distance} OMIT embedding FROM courses WHERE {} {order} LIMIT $limit",
+        catalog_condition(&search, &filters, None)
+    )];
+    statements.extend(
+        CatalogFacet::ALL
+            .into_iter()
+            .map(|facet| facet.count_query(&catalog_condition(&search, &filters, Some(facet)))),
+    );
+
+    let mut request = db
+        .query(statements.join(";\n"))
+        .bind(("query", query.to_lowercase()))
+        .bind(("limit", CATALOG_PAGE_SIZE));
+    for facet in CatalogFacet::ALL {
+        let picked = facet.picked(&filters).map(str::to_lowercase);
+        request = request.bind((facet.param(), picked));
+    }
+    if semantic {
+        let mut model = TextEmbedding::try_new(InitOptions::new(MODEL)).into_server_error()?;
+        let embedding = model
+            .embed(vec![query], None)
+            .into_server_error()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ServerFnError::new("Empty embedding returned"))?;
+        request = request.bind(("embedding", embedding));
+    }
+    let mut result = request.await.into_server_error()?;
+
+    let courses: Vec<CoursesDataWithEmbeddings> = result.take(0).into_server_error()?;
+    let facets = CourseFacets {
+        levels: ranked(result.take(1).into_server_error()?),
+        ctypes: ranked(result.take(2).into_server_error()?),
+        channels: ranked(result.take(3).into_server_error()?),
+        skill_paths: ranked(result.take(4).into_server_error()?),
+    };
+    Ok(CourseSearchResults {
+        courses: courses.into_iter().map(CatalogCourse::from).collect(),
+        facets,
+    })
+}
+
+/// Adds a catalog course to one of the caller's roadmap nodes as a learning resource.
+#[server]
+pub async fn add_course_to_node(
+    session_token: String,
+    roadmap_id: String,
+    node_id: String,
+    course_id: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (_, user_id) = session_user(session_token).await?;
+    let (id, mut roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+
+    let course_record = RecordId::from_str(&course_id)
+        .map_err(|_| ServerFnError::new("Could not parse RecordID"))?;
+    let course: CoursesDataWithEmbeddings = db
+        .select(&course_record)
+        .await
+        .into_server_error()?
+        .filter(|c: &CoursesDataWithEmbeddings| !c.deactivated)
+        .ok_or_else(|| ServerFnError::new("Course not found"))?;
+    let resource = LearningResource::from(CatalogCourse::from(course));
+
+    let node = roadmap
+        .nodes
+        .iter_mut()
+        .find(|n| n.id == node_id)
+        .ok_or_else(|| ServerFnError::new("Node not found in roadmap"))?;
+    let already_linked = node.resources.iter().any(|r| {
+        r.title.eq_ignore_ascii_case(&resource.title)
+            || (resource.url.is_some() && r.url == resource.url)
+    });
+    if already_linked {
+        return Err(ServerFnError::new("This course is already on that step"));
+    }
+    node.resources.push(resource);
+    roadmap.updated_at = Utc::now();
+
+    let _: Option<RoadmapDB> = db.update(id).content(roadmap).await.into_server_error()?;
+    Ok(())
+}
+
+// SKILL TAXONOMY
+/// The skill-path hierarchy, broadest areas first.
+#[server]
+pub async fn get_skill_taxonomy() -> Result<Vec<SkillPathNode>, ServerFnError> {
+    Ok(load_taxonomy().await?.skill_paths)
+}
+
+/// Topics whose name contains `query`, most taught first.
+#[server]
+pub async fn search_topics(query: String) -> Result<Vec<TopicNode>, ServerFnError> {
+    let needle = crate::taxonomy::normalize_topic(&query);
+    let mut topics: Vec<TopicNode> = load_taxonomy()
+        .await?
+        .topics
+        .into_iter()
+        .filter(|t| t.topic.contains(&needle))
+        .collect();
+    topics.sort_by_key(|t| std::cmp::Reverse(t.course_count));
+    topics.truncate(CATALOG_PAGE_SIZE);
+    Ok(topics)
+}
+
+/// A topic with the topics it builds on and the ones that build on it.
+#[server]
+pub async fn get_topic_detail(topic: String) -> Result<TopicDetail, ServerFnError> {
+    let taxonomy = load_taxonomy().await?;
+    let node = taxonomy
+        .topic(&topic)
+        .ok_or_else(|| ServerFnError::new("Topic not found"))?;
+    Ok(TopicDetail {
+        prerequisites: node
+            .prerequisites
+            .iter()
+            .filter_map(|p| taxonomy.topic(p))
+            .cloned()
+            .collect(),
+        dependents: taxonomy
+            .dependents(&node.topic)
+            .into_iter()
+            .cloned()
+            .collect(),
+        topic: node.clone(),
+    })
+}
+
+/// Steps of a roadmap that come before topics they should build on.
+#[server]
+pub async fn check_roadmap_prerequisites(
+    session_token: String,
+    roadmap_id: String,
+) -> Result<Vec<PrerequisiteIssue>, ServerFnError> {
+    let (_, user_id) = session_user(session_token).await?;
+    let (_, roadmap) = owned_roadmap(&roadmap_id, &user_id).await?;
+    let taxonomy = load_taxonomy().await?;
+    Ok(crate::taxonomy::check_prerequisites(
+        &roadmap.nodes,
+        &taxonomy,
+    ))
+}
+
+// RECOMMENDATIONS
+/// The taxonomy topic a skill or step name is about, or the name in topic form when the
+/// corpus has no match.
+#[cfg(feature = "server")]
+fn skill_topic(taxonomy: &Taxonomy, skill_name: &str) -> String {
+    taxonomy
+        .topic_for_skill(skill_name)
+        .map(|t| t.topic.clone())
+        .unwrap_or_else(|| crate::taxonomy::normalize_topic(skill_name))
+}
+
+/// Best cosine similarity between each topic's courses and `skills`, over the courses
+/// nearest to them.
+#[cfg(feature = "server")]
+async fn topic_similarity(skills: &[String]) -> Result<HashMap<String, f32>, ServerFnError> {
+    if skills.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let db = get_db().await?;
+    let mut model = TextEmbedding::try_new(InitOptions::new(MODEL)).into_server_error()?;
+    let embedding = model
+        .embed(vec![skills.join(", ")], None)
+        .into_server_error()?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ServerFnError::new("Empty embedding returned"))?;
+
+    #[derive(serde::Deserialize)]
+    struct Neighbour {
+        topic: String,
+        hidden: bool,
+        similarity: f32,
+    }
+    let mut result = db
+        .query(format!(
+            "SELECT topic, (deactivated = true) AS hidden, \
+             vector::similarity::cosine(embedding, $embedding) AS similarity FROM courses \
+             WHERE embedding <|{RECOMMENDATION_NEIGHBOURS},400|> $embedding"
+        ))
+        .bind(("embedding", embedding))
+        .await
+        .into_server_error()?;
+    let neighbours: Vec<Neighbour> = result.take(0).into_server_error()?;
+
+    let mut similarity: HashMap<String, f32> = HashMap::new();
+    for neighbour in neighbours.into_iter().filter(|n| !n.hidden) {
+        let best = similarity
+            .entry(crate::taxonomy::normalize_topic(&neighbour.topic))
+            .or_insert(0.0);
+        *best = best.max(neighbour.similarity);
+    }
+    Ok(similarity)
+}
+
+/// Skills the learner is ready to start next, from what they know, what their teams
+/// assign and how close each topic is to their existing skills.
+#[server]
+pub async fn get_recommendations(
+    session_token: String,
+) -> Result<Vec<SkillRecommendation>, ServerFnError> {
+    let db = get_db().await?;
+    let (user, user_id) = session_user(session_token).await?;
+    let taxonomy = load_taxonomy().await?;
+    let mut signals = crate::recommendations::LearnerSignals::default();
+
+    let mut learned: Vec<String> = user
+        .skills_learned
+        .iter()
+        .map(|s| s.skillname.clone())
+        .collect();
+    let mut result = db
+        .query("SELECT * FROM roadmaps WHERE user_id = $user_id")
+        .bind(("user_id", user_id.clone()))
+        .await
+        .into_server_error()?;
+    let roadmaps: Vec<RoadmapDB> = result.take(0).into_server_error()?;
+    for roadmap in &roadmaps {
+        let finished = !roadmap.nodes.is_empty() && roadmap.nodes.iter().all(|n| n.is_completed);
+        if finished {
+            learned.push(roadmap.skill_name.clone());
+        } else {
+            signals
+                .in_progress
+                .insert(skill_topic(&taxonomy, &roadmap.skill_name));
         }
-        selected_skills.sort_by_key(|x| x.date_learnt);
-        let mut day_wise_progress: HashMap<DateTime<Utc>, u8> = HashMap::new();
-        let mut temp_datetime = start_datetime;
-        while temp_datetime <= curr_datetime {
-            let day_skills: Vec<&UserSkills> = selected_skills
-                .iter()
-                .filter(|x| x.date_learnt.date_naive() == temp_datetime.date_naive())
-                .collect();
-            day_wise_progress.insert(temp_datetime, day_skills.len() as u8);
-            temp_datetime = temp_datetime
-                .checked_add_signed(Duration::days(1))
-                .expect("Date overflowed when adding a day");
+        for node in &roadmap.nodes {
+            if node.is_completed {
+                learned.push(node.skill_name.clone());
+            } else {
+                signals
+                    .in_progress
+                    .insert(skill_topic(&taxonomy, &node.skill_name));
+            }
         }
-        Ok(Some(day_wise_progress))
+    }
+    learned.sort();
+    learned.dedup();
+    signals.known = learned.iter().map(|s| skill_topic(&taxonomy, s)).collect();
+    signals.similarity = topic_similarity(&learned).await?;
+
+    // Every team in the learner's organizations counts, not only the ones they're on.
+    let mut result = db
+        .query(
+            "SELECT * FROM teams WHERE member_ids CONTAINS $user_id \
+             OR org_id IN (SELECT VALUE org_id FROM org_members WHERE user_id = $user_id)",
+        )
+        .bind(("user_id", user_id))
+        .await
+        .into_server_error()?;
+    let teams: Vec<TeamDB> = result.take(0).into_server_error()?;
+    for team_id in teams.into_iter().filter_map(|t| t.id) {
+        for assignment in team_assignments(&team_id).await? {
+            let template: Option<RoadmapDB> = db
+                .select(&assignment.template_roadmap_id)
+                .await
+                .into_server_error()?;
+            let mut topics = vec![skill_topic(&taxonomy, &assignment.skill_name)];
+            if let Some(template) = template {
+                topics.extend(
+                    template
+                        .nodes
+                        .iter()
+                        .map(|n| skill_topic(&taxonomy, &n.skill_name)),
+                );
+            }
+            topics.sort();
+            topics.dedup();
+            for topic in topics {
+                *signals.team_demand.entry(topic).or_default() += 1;
+            }
+        }
+    }
+
+    Ok(crate::recommendations::recommend(
+        &taxonomy,
+        &signals,
+        RECOMMENDATION_LIMIT,
+    ))
+}
+
+// ADMIN FUNCTIONS
+
+/// Resolves the session and fails unless it belongs to an admin. Every admin server
+/// function goes through this, so the `/admin` page itself needs no protection.
+#[cfg(feature = "server")]
+async fn admin_user(session_token: String) -> Result<(User, RecordId), ServerFnError> {
+    let (user, user_id) = session_user(session_token).await?;
+    if user.role != UserRole::Admin {
+        return Err(ServerFnError::new("Only admins can do that"));
+    }
+    Ok((user, user_id))
+}
+
+#[cfg(feature = "server")]
+async fn record_audit(
+    admin: &User,
+    admin_id: &RecordId,
+    action: &str,
+    target: &RecordId,
+    details: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await.into_server_error()?;
+    let entry = AuditEntryDB {
+        id: None,
+        admin_id: admin_id.clone(),
+        admin_username: admin.username.clone(),
+        action: action.to_string(),
+        target: target.to_string(),
+        details,
+        created_at: Utc::now(),
+    };
+    let _: Option<AuditEntryDB> = db
+        .create("audit_log")
+        .content(entry)
+        .await
+        .into_server_error()?;
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+fn parse_record_id(id: &str) -> Result<RecordId, ServerFnError> {
+    RecordId::from_str(id).map_err(|_| ServerFnError::new("Could not parse RecordID"))
+}
+
+/// Courses whose title, topic or channel contains `query`. With `missing_url_only`, only
+/// courses that still have no link are returned.
+#[server]
+pub async fn admin_search_courses(
+    session_token: String,
+    query: String,
+    missing_url_only: bool,
+) -> Result<Vec<AdminCourse>, ServerFnError> {
+    let db = get_db().await?;
+    admin_user(session_token).await?;
+    let mut result = db
+        .query(
+            "SELECT * OMIT embedding FROM courses \
+             WHERE ($query = '' OR string::contains(string::lowercase(title), $query) \
+                 OR string::contains(string::lowercase(topic), $query) \
+                 OR string::contains(string::lowercase(channel_name), $query)) \
+             AND ($missing_url_only = false OR url = '') \
+             ORDER BY title ASC LIMIT $limit",
+        )
+        .bind(("query", query.trim().to_lowercase()))
+        .bind(("missing_url_only", missing_url_only))
+        .bind(("limit", ADMIN_PAGE_SIZE))
+        .await
+        .into_server_error()?;
+    let courses: Vec<CoursesDataWithEmbeddings> = result.take(0).into_server_error()?;
+    Ok(courses.into_iter().map(AdminCourse::from).collect())
+}
+
+/// Saves an edited course. The embedding is rebuilt when any of the text it is made from
+/// changes, so search keeps matching what the course now says.
+#[server]
+pub async fn admin_update_course(
+    session_token: String,
+    course: AdminCourse,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (admin, admin_id) = admin_user(session_token).await?;
+    let id = parse_record_id(&course.id)?;
+    let url = course.url.trim().to_string();
+    if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(ServerFnError::new(
+            "URLs must start with http:// or https://",
+        ));
+    }
+
+    let mut record: CoursesDataWithEmbeddings = db
+        .select(&id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Course not found"))?;
+    let before = AdminCourse::from(record.clone());
+    let before_text = course_embedding_text(&record);
+
+    record.title = course.title.trim().to_string();
+    record.description = course.description.trim().to_string();
+    record.channel_name = course.channel_name.trim().to_string();
+    record.skill_path = course.skill_path.trim().to_string();
+    record.level = course.level.trim().to_string();
+    record.ctype = course.ctype.trim().to_string();
+    record.topic = course.topic.trim().to_string();
+    record.url = url;
+    record.duration_minutes = course.duration_minutes;
+
+    let text = course_embedding_text(&record);
+    if text != before_text {
+        let mut model = TextEmbedding::try_new(InitOptions::new(MODEL)).into_server_error()?;
+        record.embedding = model
+            .embed(vec![text], None)
+            .into_server_error()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ServerFnError::new("Empty embedding returned"))?;
+    }
+
+    let after = AdminCourse::from(record.clone());
+    let changed: Vec<&str> = [
+        ("title", before.title != after.title),
+        ("description", before.description != after.description),
+        ("channel", before.channel_name != after.channel_name),
+        ("skill path", before.skill_path != after.skill_path),
+        ("level", before.level != after.level),
+        ("type", before.ctype != after.ctype),
+        ("topic", before.topic != after.topic),
+        ("url", before.url != after.url),
+        (
+            "duration",
+            before.duration_minutes != after.duration_minutes,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, changed)| changed.then_some(field))
+    .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    let _: Option<CoursesDataWithEmbeddings> = db
+        .update(id.clone())
+        .content(record)
+        .await
+        .into_server_error()?;
+    rebuild_taxonomy(db).await.into_server_error()?;
+    record_audit(
+        &admin,
+        &admin_id,
+        "course.update",
+        &id,
+        format!("Changed {}", changed.join(", ")),
+    )
+    .await
+}
+
+/// Hides a course from search and generation, or brings it back.
+#[server]
+pub async fn admin_set_course_active(
+    session_token: String,
+    course_id: String,
+    active: bool,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (admin, admin_id) = admin_user(session_token).await?;
+    let id = parse_record_id(&course_id)?;
+
+    db.query("UPDATE $id SET deactivated = $deactivated")
+        .bind(("id", id.clone()))
+        .bind(("deactivated", !active))
+        .await
+        .into_server_error()?;
+    rebuild_taxonomy(db).await.into_server_error()?;
+    let action = if active {
+        "course.reactivate"
     } else {
-        Ok(None)
+        "course.deactivate"
+    };
+    record_audit(&admin, &admin_id, action, &id, String::new()).await
+}
+
+/// Folds a duplicate course into the one being kept: roadmap resources that point at the
+/// duplicate are repointed, then the duplicate is deleted. Returns how many roadmaps
+/// changed.
+#[server]
+pub async fn admin_merge_courses(
+    session_token: String,
+    keep_id: String,
+    duplicate_id: String,
+) -> Result<usize, ServerFnError> {
+    let db = get_db().await?;
+    let (admin, admin_id) = admin_user(session_token).await?;
+    let keep_id = parse_record_id(&keep_id)?;
+    let duplicate_id = parse_record_id(&duplicate_id)?;
+    if keep_id == duplicate_id {
+        return Err(ServerFnError::new("Can't merge a course into itself"));
+    }
+
+    let keep: CoursesDataWithEmbeddings = db
+        .select(&keep_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Course to keep not found"))?;
+    let duplicate: CoursesDataWithEmbeddings = db
+        .select(&duplicate_id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("Duplicate course not found"))?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM roadmaps \
+             WHERE array::map(array::flatten(nodes.resources.title), \
+                 |$title| string::lowercase(string::trim($title))) CONTAINS $title \
+             OR ($url != '' AND array::flatten(nodes.resources.url) CONTAINS $url)",
+        )
+        .bind(("title", duplicate.title.trim().to_lowercase()))
+        .bind(("url", duplicate.url.clone()))
+        .await
+        .into_server_error()?;
+    let roadmaps: Vec<RoadmapDB> = result.take(0).into_server_error()?;
+
+    let duplicate_title = duplicate.title.trim().to_lowercase();
+    let is_duplicate = |resource: &LearningResource| {
+        resource.title.trim().to_lowercase() == duplicate_title
+            || (!duplicate.url.is_empty()
+                && resource.url.as_deref() == Some(duplicate.url.as_str()))
+    };
+    let mut updated = 0;
+    for mut roadmap in roadmaps {
+        let Some(id) = roadmap.id.clone() else {
+            continue;
+        };
+        let mut changed = false;
+        for resource in roadmap
+            .nodes
+            .iter_mut()
+            .flat_map(|n| n.resources.iter_mut())
+        {
+            if is_duplicate(resource) {
+                resource.title = keep.title.clone();
+                if !keep.url.is_empty() {
+                    resource.url = Some(keep.url.clone());
+                }
+                resource.estimated_minutes = keep.duration_minutes.or(resource.estimated_minutes);
+                changed = true;
+            }
+        }
+        if changed {
+            let _: Option<RoadmapDB> = db.update(id).content(roadmap).await.into_server_error()?;
+            updated += 1;
+        }
+    }
+
+    // A user who already voted on the kept course keeps that vote; their vote on the
+    // duplicate would count twice or contradict it.
+    let mut result = db
+        .query("SELECT * FROM resource_feedback WHERE course_id = $keep_id")
+        .query("SELECT * FROM resource_feedback WHERE course_id = $duplicate_id")
+        .bind(("keep_id", keep_id.clone()))
+        .bind(("duplicate_id", duplicate_id.clone()))
+        .await
+        .into_server_error()?;
+    let kept_feedback: Vec<ResourceFeedbackDB> = result.take(0).into_server_error()?;
+    let moved_feedback: Vec<ResourceFeedbackDB> = result.take(1).into_server_error()?;
+    for entry in moved_feedback {
+        let clashes = kept_feedback.iter().any(|kept| {
+            kept.user_id == entry.user_id
+                && (kept.kind == entry.kind || Some(kept.kind) == entry.kind.opposite())
+        });
+        if clashes && let Some(id) = entry.id {
+            let _: Option<ResourceFeedbackDB> = db.delete(id).await.into_server_error()?;
+        }
+    }
+    db.query("UPDATE resource_feedback SET course_id = $keep_id WHERE course_id = $duplicate_id")
+        .bind(("keep_id", keep_id.clone()))
+        .bind(("duplicate_id", duplicate_id.clone()))
+        .await
+        .into_server_error()?;
+    refresh_course_feedback(&keep_id).await?;
+    let _: Option<CoursesDataWithEmbeddings> =
+        db.delete(duplicate_id.clone()).await.into_server_error()?;
+    rebuild_taxonomy(db).await.into_server_error()?;
+    record_audit(
+        &admin,
+        &admin_id,
+        "course.merge",
+        &keep_id,
+        format!(
+            "Merged \"{}\" ({}) into \"{}\"; {} roadmaps updated",
+            duplicate.title, duplicate_id, keep.title, updated
+        ),
+    )
+    .await?;
+    Ok(updated)
+}
+
+#[server]
+pub async fn admin_list_users(
+    session_token: String,
+    query: String,
+) -> Result<Vec<AdminUser>, ServerFnError> {
+    let db = get_db().await?;
+    admin_user(session_token).await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM users \
+             WHERE $query = '' OR string::contains(string::lowercase(username), $query) \
+                 OR string::contains(string::lowercase(name), $query) \
+             ORDER BY created_at DESC LIMIT $limit",
+        )
+        .bind(("query", query.trim().to_lowercase()))
+        .bind(("limit", ADMIN_PAGE_SIZE))
+        .await
+        .into_server_error()?;
+    let users: Vec<UserDB> = result.take(0).into_server_error()?;
+
+    let mut admin_users = Vec::new();
+    for user in users {
+        let Some(id) = user.id else {
+            continue;
+        };
+        let mut result = db
+            .query("SELECT VALUE id FROM roadmaps WHERE user_id = $user_id")
+            .bind(("user_id", id.clone()))
+            .await
+            .into_server_error()?;
+        let roadmap_ids: Vec<RecordId> = result.take(0).into_server_error()?;
+        admin_users.push(AdminUser {
+            id: id.to_string(),
+            username: user.username,
+            name: user.name,
+            role: user.role,
+            locked: user.locked,
+            roadmap_count: roadmap_ids.len(),
+            created_at: user.created_at,
+        });
+    }
+    Ok(admin_users)
+}
+
+/// Locks or unlocks an account. Locking also ends all of its sessions.
+#[server]
+pub async fn admin_set_user_locked(
+    session_token: String,
+    user_id: String,
+    locked: bool,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (admin, admin_id) = admin_user(session_token).await?;
+    let id = parse_record_id(&user_id)?;
+    if id == admin_id {
+        return Err(ServerFnError::new("You can't lock your own account"));
+    }
+
+    db.query("UPDATE $id SET locked = $locked")
+        .bind(("id", id.clone()))
+        .bind(("locked", locked))
+        .await
+        .into_server_error()?;
+    if locked {
+        db.query("DELETE sessions WHERE user_id = $id")
+            .bind(("id", id.clone()))
+            .await
+            .into_server_error()?;
+    }
+    let action = if locked { "user.lock" } else { "user.unlock" };
+    record_audit(&admin, &admin_id, action, &id, String::new()).await
+}
+
+#[server]
+pub async fn admin_set_user_role(
+    session_token: String,
+    user_id: String,
+    role: UserRole,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (admin, admin_id) = admin_user(session_token).await?;
+    let id = parse_record_id(&user_id)?;
+    if id == admin_id {
+        return Err(ServerFnError::new("You can't change your own role"));
+    }
+
+    db.query("UPDATE $id SET role = $role")
+        .bind(("id", id.clone()))
+        .bind(("role", role))
+        .await
+        .into_server_error()?;
+    record_audit(
+        &admin,
+        &admin_id,
+        "user.role",
+        &id,
+        format!("Set role to {}", role.label()),
+    )
+    .await
+}
+
+/// Deletes an account along with everything it owns. Organizations it belonged to keep
+/// running without it.
+#[server]
+pub async fn admin_delete_user(
+    session_token: String,
+    user_id: String,
+) -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    let (admin, admin_id) = admin_user(session_token).await?;
+    let id = parse_record_id(&user_id)?;
+    if id == admin_id {
+        return Err(ServerFnError::new("You can't delete your own account"));
     }
+    let user: UserDB = db
+        .select(&id)
+        .await
+        .into_server_error()?
+        .ok_or_else(|| ServerFnError::new("User not found"))?;
+
+    // Same rule as `remove_org_member`: no organization may be left without an owner.
+    let mut result = db
+        .query("SELECT * FROM org_members WHERE user_id = $id")
+        .bind(("id", id.clone()))
+        .await
+        .into_server_error()?;
+    let memberships: Vec<OrgMembershipDB> = result.take(0).into_server_error()?;
+    for membership in memberships.iter().filter(|m| m.role == OrgRole::Owner) {
+        if org_owner_count(&membership.org_id).await? == 1 {
+            let org: Option<OrganizationDB> =
+                db.select(&membership.org_id).await.into_server_error()?;
+            return Err(ServerFnError::new(format!(
+                "@{} is the only owner of {}; make someone else an owner first",
+                user.username,
+                org.map(|o| o.name)
+                    .unwrap_or_else(|| "an organization".to_string())
+            )));
+        }
+    }
+
+    let mut result = db
+        .query("SELECT VALUE course_id FROM resource_feedback WHERE user_id = $id")
+        .bind(("id", id.clone()))
+        .await
+        .into_server_error()?;
+    let mut rated: Vec<RecordId> = result.take(0).into_server_error()?;
+    rated.sort_by_key(|c| c.to_string());
+    rated.dedup();
+
+    // Assignments go with their templates; members keep the copies they already have.
+    db.query("DELETE sessions WHERE user_id = $id")
+        .query(
+            "DELETE team_assignments WHERE template_roadmap_id IN \
+             (SELECT VALUE id FROM roadmaps WHERE user_id = $id)",
+        )
+        .query("DELETE roadmaps WHERE user_id = $id")
+        .query("DELETE reviews WHERE user_id = $id")
+        .query("DELETE node_quizzes WHERE user_id = $id")
+        .query("DELETE quiz_attempts WHERE user_id = $id")
+        .query("DELETE calendar_feeds WHERE user_id = $id")
+        .query("DELETE resource_feedback WHERE user_id = $id")
+        .query("DELETE org_members WHERE user_id = $id")
+        .query("UPDATE teams SET member_ids -= $id")
+        .query("DELETE $id")
+        .bind(("id", id.clone()))
+        .await
+        .into_server_error()?;
+    // Their feedback no longer counts towards the courses' summaries.
+    for course_id in &rated {
+        refresh_course_feedback(course_id).await?;
+    }
+    record_audit(
+        &admin,
+        &admin_id,
+        "user.delete",
+        &id,
+        format!("Deleted @{}", user.username),
+    )
+    .await
+}
+
+#[server]
+pub async fn admin_generation_failures(
+    session_token: String,
+) -> Result<Vec<GenerationFailure>, ServerFnError> {
+    let db = get_db().await?;
+    admin_user(session_token).await?;
+    let mut result = db
+        .query("SELECT * FROM generation_failures ORDER BY occurred_at DESC LIMIT $limit")
+        .bind(("limit", ADMIN_PAGE_SIZE))
+        .await
+        .into_server_error()?;
+    let failures: Vec<GenerationFailureDB> = result.take(0).into_server_error()?;
+    Ok(failures.into_iter().map(GenerationFailure::from).collect())
+}
+
+#[server]
+pub async fn admin_audit_log(session_token: String) -> Result<Vec<AuditEntry>, ServerFnError> {
+    let db = get_db().await?;
+    admin_user(session_token).await?;
+    let mut result = db
+        .query("SELECT * FROM audit_log ORDER BY created_at DESC LIMIT $limit")
+        .bind(("limit", ADMIN_PAGE_SIZE))
+        .await
+        .into_server_error()?;
+    let entries: Vec<AuditEntryDB> = result.take(0).into_server_error()?;
+    Ok(entries.into_iter().map(AuditEntry::from).collect())
 }
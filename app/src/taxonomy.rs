@@ -0,0 +1,251 @@
+//! The skill-path hierarchy and topic prerequisite graph behind the course corpus, and the
+//! checks that hold generated roadmaps to it.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::models::{
+    PrerequisiteIssue, RoadmapNode, SkillPathNode, Taxonomy, TopicNode, skill_path_segments,
+};
+
+/// The course fields the taxonomy is built from.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CourseTaxonomyFields {
+    #[serde(default)]
+    pub skill_path: String,
+    #[serde(default)]
+    pub topic: String,
+    #[serde(default)]
+    pub prerequisite_topics: Vec<String>,
+}
+
+/// Puts a topic into the corpus' snake_case form, so `Docker Basics`, `docker-basics` and
+/// `docker_basics` are the same topic.
+pub fn normalize_topic(raw: &str) -> String {
+    let mut topic = String::new();
+    for c in raw.trim().chars() {
+        if c.is_alphanumeric() {
+            topic.extend(c.to_lowercase());
+        } else if !topic.is_empty() && !topic.ends_with('_') {
+            topic.push('_');
+        }
+    }
+    while topic.ends_with('_') {
+        topic.pop();
+    }
+    topic
+}
+
+#[derive(Default)]
+struct PathLevel {
+    name: String,
+    course_count: usize,
+    /// Keyed by lowercased name, so differently cased segments share a level.
+    children: BTreeMap<String, PathLevel>,
+}
+
+fn into_nodes(levels: BTreeMap<String, PathLevel>, parent: &[String]) -> Vec<SkillPathNode> {
+    let mut nodes: Vec<SkillPathNode> = levels
+        .into_values()
+        .map(|level| {
+            let mut path = parent.to_vec();
+            path.push(level.name.clone());
+            SkillPathNode {
+                children: into_nodes(level.children, &path),
+                name: level.name,
+                path,
+                course_count: level.course_count,
+            }
+        })
+        .collect();
+    // Biggest areas first; the map already ordered ties by name.
+    nodes.sort_by_key(|n| std::cmp::Reverse(n.course_count));
+    nodes
+}
+
+pub fn build_taxonomy(courses: &[CourseTaxonomyFields], now: DateTime<Utc>) -> Taxonomy {
+    let mut roots: BTreeMap<String, PathLevel> = BTreeMap::new();
+    let mut topics: BTreeMap<String, (usize, BTreeSet<String>)> = BTreeMap::new();
+
+    for course in courses {
+        let mut level = &mut roots;
+        for segment in skill_path_segments(&course.skill_path) {
+            let name = segment.split_whitespace().collect::<Vec<_>>().join(" ");
+            let entry = level
+                .entry(name.to_lowercase())
+                .or_insert_with(|| PathLevel {
+                    name,
+                    ..PathLevel::default()
+                });
+            entry.course_count += 1;
+            level = &mut entry.children;
+        }
+
+        let topic = normalize_topic(&course.topic);
+        if topic.is_empty() {
+            continue;
+        }
+        let prerequisites: Vec<String> = course
+            .prerequisite_topics
+            .iter()
+            .map(|p| normalize_topic(p))
+            .filter(|p| !p.is_empty() && *p != topic)
+            .collect();
+        let entry = topics.entry(topic).or_default();
+        entry.0 += 1;
+        entry.1.extend(prerequisites);
+    }
+
+    // Prerequisites nobody teaches directly are still part of the graph.
+    let named: Vec<String> = topics
+        .values()
+        .flat_map(|(_, prerequisites)| prerequisites.iter().cloned())
+        .collect();
+    for topic in named {
+        topics.entry(topic).or_default();
+    }
+
+    Taxonomy {
+        skill_paths: into_nodes(roots, &[]),
+        topics: topics
+            .into_iter()
+            .map(|(topic, (course_count, prerequisites))| TopicNode {
+                topic,
+                course_count,
+                prerequisites: prerequisites.into_iter().collect(),
+            })
+            .collect(),
+        built_at: now,
+    }
+}
+
+impl Taxonomy {
+    pub fn topic(&self, topic: &str) -> Option<&TopicNode> {
+        let topic = normalize_topic(topic);
+        self.topics
+            .binary_search_by(|t| t.topic.as_str().cmp(&topic))
+            .ok()
+            .map(|i| &self.topics[i])
+    }
+
+    pub fn dependents(&self, topic: &str) -> Vec<&TopicNode> {
+        let topic = normalize_topic(topic);
+        self.topics
+            .iter()
+            .filter(|t| t.prerequisites.contains(&topic))
+            .collect()
+    }
+
+    /// Every topic `topic` builds on, directly or through other topics.
+    pub fn prerequisite_closure(&self, topic: &str) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut pending: Vec<String> = self
+            .topic(topic)
+            .map(|t| t.prerequisites.clone())
+            .unwrap_or_default();
+        while let Some(next) = pending.pop() {
+            if seen.insert(next.clone()) {
+                if let Some(node) = self.topic(&next) {
+                    pending.extend(node.prerequisites.iter().cloned());
+                }
+            }
+        }
+        seen
+    }
+
+    /// The topic a roadmap step covers: the step's own name if that is a topic, otherwise
+    /// the longest topic spelled out inside it.
+    pub fn topic_for_skill(&self, skill_name: &str) -> Option<&TopicNode> {
+        let skill = normalize_topic(skill_name);
+        if skill.is_empty() {
+            return None;
+        }
+        if let Some(exact) = self.topic(&skill) {
+            return Some(exact);
+        }
+        let padded = format!("_{skill}_");
+        self.topics
+            .iter()
+            .filter(|t| padded.contains(&format!("_{}_", t.topic)))
+            .max_by_key(|t| t.topic.len())
+    }
+}
+
+/// IDs of every step `node_id` depends on, directly or transitively.
+fn ancestors(nodes: &[RoadmapNode], node_id: &str) -> HashSet<String> {
+    let by_id: HashMap<&str, &RoadmapNode> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let mut seen = HashSet::new();
+    let mut pending: Vec<&str> = by_id
+        .get(node_id)
+        .map(|n| n.prerequisites.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    while let Some(id) = pending.pop() {
+        if seen.insert(id.to_string()) {
+            if let Some(node) = by_id.get(id) {
+                pending.extend(node.prerequisites.iter().map(String::as_str));
+            }
+        }
+    }
+    seen
+}
+
+/// Steps whose topic builds on another step's topic without depending on that step.
+pub fn check_prerequisites(nodes: &[RoadmapNode], taxonomy: &Taxonomy) -> Vec<PrerequisiteIssue> {
+    let topics: Vec<Option<&TopicNode>> = nodes
+        .iter()
+        .map(|n| taxonomy.topic_for_skill(&n.skill_name))
+        .collect();
+
+    let mut issues = Vec::new();
+    for (node, topic) in nodes.iter().zip(&topics) {
+        let Some(topic) = topic else { continue };
+        let needed = taxonomy.prerequisite_closure(&topic.topic);
+        if needed.is_empty() {
+            continue;
+        }
+        let depends_on = ancestors(nodes, &node.id);
+        for (other, other_topic) in nodes.iter().zip(&topics) {
+            let Some(other_topic) = other_topic else {
+                continue;
+            };
+            if other.id == node.id
+                || other_topic.topic == topic.topic
+                || !needed.contains(&other_topic.topic)
+                || depends_on.contains(&other.id)
+            {
+                continue;
+            }
+            issues.push(PrerequisiteIssue {
+                node_id: node.id.clone(),
+                skill_name: node.skill_name.clone(),
+                requires_node_id: other.id.clone(),
+                requires_skill_name: other.skill_name.clone(),
+                topic: topic.topic.clone(),
+                prerequisite_topic: other_topic.topic.clone(),
+            });
+        }
+    }
+    issues
+}
+
+/// Adds the prerequisite links the taxonomy calls for. Links that would close a cycle are
+/// left out and returned, since the LLM's order is then at odds with the corpus.
+pub fn repair_prerequisites(
+    nodes: &mut [RoadmapNode],
+    taxonomy: &Taxonomy,
+) -> Vec<PrerequisiteIssue> {
+    let mut unresolved = Vec::new();
+    for issue in check_prerequisites(nodes, taxonomy) {
+        if ancestors(nodes, &issue.requires_node_id).contains(&issue.node_id) {
+            unresolved.push(issue);
+            continue;
+        }
+        if let Some(node) = nodes.iter_mut().find(|n| n.id == issue.node_id) {
+            if !node.prerequisites.contains(&issue.requires_node_id) {
+                node.prerequisites.push(issue.requires_node_id);
+            }
+        }
+    }
+    unresolved
+}
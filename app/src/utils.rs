@@ -56,6 +56,31 @@ pub fn set_session_cookie(token: &str, days: i64) {
     }
 }
 
+/// Hands `contents` to the browser as a file download.
+#[cfg(target_arch = "wasm32")]
+pub fn download_text_file(filename: &str, mime: &str, contents: &str) -> Option<()> {
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlAnchorElement;
+
+    let document = web_sys::window()?.document()?;
+    let anchor = document
+        .create_element("a")
+        .ok()?
+        .dyn_into::<HtmlAnchorElement>()
+        .ok()?;
+    let encoded = js_sys::encode_uri_component(contents);
+    anchor.set_href(&format!("data:{mime};charset=utf-8,{encoded}"));
+    anchor.set_download(filename);
+    anchor.click();
+    Some(())
+}
+
+/// Scheme, host and port of the current page, for building absolute links.
+#[cfg(target_arch = "wasm32")]
+pub fn page_origin() -> Option<String> {
+    web_sys::window()?.location().origin().ok()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn get_session_token() -> Option<String> {
     None
@@ -68,3 +93,92 @@ pub fn set_session_cookie(_token: &str, _days: i64) {}
 pub fn clear_session_token() -> Option<()> {
     None
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn download_text_file(_filename: &str, _mime: &str, _contents: &str) -> Option<()> {
+    None
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn page_origin() -> Option<String> {
+    None
+}
+
+/// Short human label for a study-time estimate, e.g. "45 min" or "3.5 h".
+pub fn format_hours(hours: f32) -> String {
+    if hours < 1.0 {
+        format!("{} min", (hours * 60.0).round() as u32)
+    } else if hours < 10.0 {
+        format!("{:.1} h", hours)
+    } else {
+        format!("{} h", hours.round() as u32)
+    }
+}
+
+/// Lower-case, dash-separated version of `name` that is safe to use as a file name.
+pub fn file_slug(name: &str) -> String {
+    let slug = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "roadmap".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Readable name for a snake_case corpus topic, e.g. `docker_basics` becomes "Docker Basics".
+pub fn topic_label(topic: &str) -> String {
+    topic
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Platform a resource lives on, from its URL's host: a known name such as "YouTube",
+/// "Web" for any other site, or empty when there is no URL.
+pub fn platform_for_url(url: &str) -> String {
+    const PLATFORMS: [(&str, &str); 7] = [
+        ("youtube.com", "YouTube"),
+        ("youtu.be", "YouTube"),
+        ("coursera.org", "Coursera"),
+        ("udemy.com", "Udemy"),
+        ("edx.org", "edX"),
+        ("khanacademy.org", "Khan Academy"),
+        ("github.com", "GitHub"),
+    ];
+
+    let url = url.trim();
+    if url.is_empty() {
+        return String::new();
+    }
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit('@').next().unwrap_or_default();
+    let host = host_port
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    PLATFORMS
+        .iter()
+        .find(|(domain, _)| {
+            host == *domain
+                || host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
+        .map_or("Web", |(_, name)| name)
+        .to_string()
+}
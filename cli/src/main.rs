@@ -0,0 +1,90 @@
+//! `skillforge`: one entry point for the data pipeline and the tools around the app. Paths
+//! are relative to the repository root, which is where it is meant to be run from.
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand};
+use course_schema::CourseReader;
+use std::path::PathBuf;
+use tokio::process::Command as Process;
+
+/// The database the app opens, relative to the repository root.
+const APP_DB_PATH: &str = "app/skillforge";
+
+#[derive(Parser)]
+#[command(
+    name = "skillforge",
+    about = "SkillForge data pipeline and admin tools"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Turn raw course CSVs into LLM-enhanced course records.
+    Process(data_processor::ProcessArgs),
+    /// Merge processed course files, drop duplicates and filter out low-quality records.
+    Merge(json_merger::MergeArgs),
+    /// Embed a course file and add it to the app's database. Stop the app first.
+    Ingest {
+        #[arg(long, default_value = "final_data.json")]
+        input: PathBuf,
+        #[arg(long, default_value = APP_DB_PATH)]
+        db: String,
+    },
+    /// Find URLs for stored courses that are missing one. Stop the app first.
+    Enrich {
+        #[arg(long, default_value = APP_DB_PATH)]
+        db: String,
+        #[arg(long, default_value_t = 50)]
+        concurrency: usize,
+    },
+    /// Start the app with `dx serve`; the admin console is at /admin.
+    ServeAdmin {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+
+    match Cli::parse().command {
+        Command::Process(args) => data_processor::run(args).await,
+        Command::Merge(args) => json_merger::run(args).await,
+        Command::Ingest { input, db } => ingest(input, &db).await,
+        Command::Enrich { db, concurrency } => database_url_enricher::run(&db, concurrency).await,
+        Command::ServeAdmin { port } => serve_admin(port).await,
+    }
+}
+
+async fn ingest(input: PathBuf, db_path: &str) -> Result<()> {
+    let courses = CourseReader::open(&input)
+        .with_context(|| format!("Couldn't load '{}'", input.display()))?;
+    if let Some(layout) = courses.migrated_from() {
+        println!(
+            "Migrating course data from the legacy '{}' layout",
+            layout.key()
+        );
+    }
+    let db = course_store::open(db_path).await?;
+    let stored = course_store::ingest(&db, courses).await?;
+    println!("✅ Stored {stored} courses in {db_path}");
+    Ok(())
+}
+
+async fn serve_admin(port: u16) -> Result<()> {
+    println!("Admin console: http://localhost:{port}/admin");
+    let status = Process::new("dx")
+        .args(["serve", "--platform", "web", "--port", &port.to_string()])
+        .current_dir("app")
+        .status()
+        .await
+        .context("Couldn't start `dx`; install it with `cargo install dioxus-cli`")?;
+    if !status.success() {
+        bail!("dx serve exited with {status}");
+    }
+    Ok(())
+}
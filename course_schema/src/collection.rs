@@ -0,0 +1,153 @@
+use serde::Serialize;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use crate::course::Course;
+use crate::stream::CourseReader;
+
+/// Version written by [`CourseCollection::to_writer_pretty`] and [`crate::CourseWriter`].
+/// Files without a `schema_version` predate it and are read as a [`LegacyLayout`].
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// The wrapper every course file is written in: `{"schema_version": 2, "courses": [...]}`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CourseCollection {
+    pub schema_version: u32,
+    pub courses: Vec<Course>,
+    /// The older layout this collection was migrated from, if any.
+    #[serde(skip)]
+    pub migrated_from: Option<LegacyLayout>,
+}
+
+/// Course files written before the schema was versioned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyLayout {
+    /// `{"videos": [...]}`, written by the processor.
+    Videos,
+    /// `{"Data": [...]}`, written by the merger.
+    MergerData,
+    /// `{"data": [...]}`, hand-curated app datasets such as `dataset_test.json`.
+    AppData,
+}
+
+impl LegacyLayout {
+    pub fn key(self) -> &'static str {
+        match self {
+            LegacyLayout::Videos => "videos",
+            LegacyLayout::MergerData => "Data",
+            LegacyLayout::AppData => "data",
+        }
+    }
+
+    pub(crate) const ALL: [LegacyLayout; 3] = [
+        LegacyLayout::Videos,
+        LegacyLayout::MergerData,
+        LegacyLayout::AppData,
+    ];
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The file is JSON but none of the known wrappers; holds its top-level keys.
+    UnknownLayout(Vec<String>),
+    UnsupportedVersion(u32),
+    /// The file stops being valid JSON at `offset` bytes in.
+    Malformed {
+        offset: u64,
+        message: &'static str,
+    },
+    InvalidRecord {
+        index: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Io(e) => write!(f, "couldn't read course file: {e}"),
+            SchemaError::Json(e) => write!(f, "course file isn't valid JSON: {e}"),
+            SchemaError::UnknownLayout(keys) => write!(
+                f,
+                "unrecognised course file layout (top-level keys: {}); expected `courses` with a `schema_version`, or a legacy `videos`, `Data` or `data` list",
+                keys.join(", ")
+            ),
+            SchemaError::UnsupportedVersion(version) => write!(
+                f,
+                "course file has schema_version {version}, but this build reads version {SCHEMA_VERSION}"
+            ),
+            SchemaError::Malformed { offset, message } => {
+                write!(
+                    f,
+                    "course file isn't valid JSON at byte {offset}: {message}"
+                )
+            }
+            SchemaError::InvalidRecord { index, source } => {
+                write!(f, "course #{index} doesn't match the schema: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SchemaError::Io(e) => Some(e),
+            SchemaError::Json(e) | SchemaError::InvalidRecord { source: e, .. } => Some(e),
+            SchemaError::UnknownLayout(_)
+            | SchemaError::UnsupportedVersion(_)
+            | SchemaError::Malformed { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SchemaError {
+    fn from(e: std::io::Error) -> Self {
+        SchemaError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(e: serde_json::Error) -> Self {
+        SchemaError::Json(e)
+    }
+}
+
+impl CourseCollection {
+    pub fn new(courses: Vec<Course>) -> Self {
+        CourseCollection {
+            schema_version: SCHEMA_VERSION,
+            courses,
+            migrated_from: None,
+        }
+    }
+
+    /// Reads a course file in the current layout or any [`LegacyLayout`], migrating legacy
+    /// records to [`Course`]. Holds every course in memory; use [`CourseReader`] for large
+    /// files.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, SchemaError> {
+        Self::collect(CourseReader::from_json(BufReader::new(reader))?)
+    }
+
+    /// Like [`from_reader`](Self::from_reader), but `.jsonl` files are read as one course
+    /// per line.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, SchemaError> {
+        Self::collect(CourseReader::open(path)?)
+    }
+
+    fn collect<R: BufRead>(mut reader: CourseReader<R>) -> Result<Self, SchemaError> {
+        let courses = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+        Ok(CourseCollection {
+            migrated_from: reader.migrated_from(),
+            ..CourseCollection::new(courses)
+        })
+    }
+
+    pub fn to_writer_pretty<W: Write>(&self, writer: W) -> Result<(), SchemaError> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// One course or video, as written by the pipeline and ingested by the app.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Course {
+    /// ID from the source dataset. Empty for hand-curated records.
+    #[serde(default)]
+    pub video_id: String,
+    pub title: String,
+    pub description: String,
+    pub channel_name: String,
+    #[serde(default)]
+    pub published_date: String,
+    /// ISO 8601 (`PT1H2M`) or clock (`1:02:00`) duration; see [`parse_duration_minutes`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+    /// Areas from broad to narrow, separated by `>`.
+    #[serde(default)]
+    pub skill_path: String,
+    #[serde(default)]
+    pub level: String,
+    /// `macro` for full courses, `micro` for single videos.
+    #[serde(rename = "type", default)]
+    pub ctype: String,
+    #[serde(default)]
+    pub content: String,
+    /// snake_case topic shared with `prerequisite_topics` of other courses.
+    #[serde(default)]
+    pub topic: String,
+    #[serde(default)]
+    pub prerequisite_topics: Vec<String>,
+    /// Filled in by the URL enricher once the course has been located.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Source popularity, kept by the processor and dropped by the merger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub views: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likes: Option<u64>,
+    #[serde(default)]
+    pub enhanced_with_llm: bool,
+}
+
+impl Course {
+    pub fn duration_minutes(&self) -> Option<u32> {
+        self.duration.as_deref().and_then(parse_duration_minutes)
+    }
+}
+
+/// A course as loosely typed upstream output: pre-versioning files and LLM replies, where
+/// anything but the title, description and channel may be missing or `null`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawCourse {
+    pub video_id: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub channel_name: String,
+    pub published_date: Option<String>,
+    pub duration: Option<String>,
+    pub skill_path: Option<String>,
+    pub level: Option<String>,
+    #[serde(rename = "type")]
+    pub ctype: Option<String>,
+    pub content: Option<String>,
+    pub topic: Option<String>,
+    pub prerequisite_topics: Option<Vec<String>>,
+    pub url: Option<String>,
+    pub views: Option<u64>,
+    pub likes: Option<u64>,
+    pub enhanced_with_llm: Option<bool>,
+}
+
+impl From<RawCourse> for Course {
+    fn from(raw: RawCourse) -> Self {
+        Course {
+            video_id: raw.video_id.unwrap_or_default(),
+            title: raw.title,
+            description: raw.description,
+            channel_name: raw.channel_name,
+            published_date: raw.published_date.unwrap_or_default(),
+            duration: raw.duration.filter(|d| !d.trim().is_empty()),
+            skill_path: raw.skill_path.unwrap_or_default(),
+            level: raw.level.unwrap_or_default(),
+            ctype: raw.ctype.unwrap_or_default(),
+            content: raw.content.unwrap_or_default(),
+            topic: raw.topic.unwrap_or_default(),
+            prerequisite_topics: raw.prerequisite_topics.unwrap_or_default(),
+            url: raw.url.filter(|u| !u.trim().is_empty()),
+            views: raw.views,
+            likes: raw.likes,
+            enhanced_with_llm: raw.enhanced_with_llm.unwrap_or(false),
+        }
+    }
+}
+
+/// The fields of a stored course the URL enricher reads and writes. Field names follow the
+/// app's `courses` table, where the source `type` is stored as `ctype`. Generic over the
+/// record ID so this crate stays independent of the database client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredCourseRef<Id> {
+    pub id: Option<Id>,
+    pub title: String,
+    pub channel_name: String,
+    pub ctype: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Parses a video/course duration into whole minutes. Accepts ISO 8601 (`PT1H2M30S`, as
+/// returned by the YouTube API) and clock formats (`1:02:30`, `12:45`). Durations too long
+/// to count in seconds are `None`, like any other unreadable value.
+pub fn parse_duration_minutes(raw: &str) -> Option<u32> {
+    let raw = raw.trim();
+    let seconds: u32 = if let Some(iso) = raw.strip_prefix("PT").or_else(|| raw.strip_prefix("pt"))
+    {
+        let mut total = 0;
+        let mut number = String::new();
+        for c in iso.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            let value: u32 = number.parse().ok()?;
+            number.clear();
+            let seconds = match c.to_ascii_uppercase() {
+                'H' => value.checked_mul(3600)?,
+                'M' => value.checked_mul(60)?,
+                'S' => value,
+                _ => return None,
+            };
+            total = u32::checked_add(total, seconds)?;
+        }
+        total
+    } else {
+        let parts: Vec<u32> = raw
+            .split(':')
+            .map(|p| p.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        match parts.as_slice() {
+            [m, s] => m.checked_mul(60)?.checked_add(*s)?,
+            [h, m, s] => h
+                .checked_mul(3600)?
+                .checked_add(m.checked_mul(60)?)?
+                .checked_add(*s)?,
+            _ => return None,
+        }
+    };
+    (seconds > 0).then(|| seconds.div_ceil(60))
+}
@@ -0,0 +1,13 @@
+//! The course record every SkillForge stage reads and writes.
+//!
+//! The processor, the merger, the URL enricher and the app all go through the types here,
+//! so a field one stage renames shows up as a compile error in the others. Files written
+//! before the schema was versioned are still read and migrated on load.
+
+mod collection;
+mod course;
+mod stream;
+
+pub use collection::{CourseCollection, LegacyLayout, SCHEMA_VERSION, SchemaError};
+pub use course::{Course, RawCourse, StoredCourseRef, parse_duration_minutes};
+pub use stream::{CourseReader, CourseWriter, is_jsonl};
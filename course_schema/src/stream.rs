@@ -0,0 +1,401 @@
+//! Reading and writing course files one record at a time, so a multi-GB scrape never has to
+//! fit in memory.
+//!
+//! Files ending in `.jsonl` or `.ndjson` hold one [`Course`] per line. Anything else is read as
+//! the wrapped `{"schema_version": 2, "courses": [...]}` layout or a [`LegacyLayout`], with the
+//! wrapper scanned by hand and only the current record parsed by serde at any time.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::collection::{LegacyLayout, SCHEMA_VERSION, SchemaError};
+use crate::course::{Course, RawCourse};
+
+/// Whether `path` is read and written as one course per line.
+pub fn is_jsonl(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("jsonl") || e.eq_ignore_ascii_case("ndjson"))
+}
+
+/// Yields the courses of a file in order. A record that doesn't match the schema is an
+/// `Err` item and reading goes on with the next; malformed JSON ends the iteration.
+pub struct CourseReader<R> {
+    scanner: Scanner<R>,
+    state: State,
+    migrated_from: Option<LegacyLayout>,
+    version: Option<u32>,
+    /// Top-level keys passed over, for the error when no course list turns up.
+    keys: Vec<String>,
+    index: usize,
+}
+
+enum State {
+    Jsonl,
+    /// Inside the course list of a wrapped file.
+    Array,
+    Done,
+}
+
+impl CourseReader<BufReader<File>> {
+    /// Opens a course file, choosing the format by its extension.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SchemaError> {
+        let path = path.as_ref();
+        let reader = BufReader::new(File::open(path)?);
+        if is_jsonl(path) {
+            Ok(CourseReader::from_jsonl(reader))
+        } else {
+            CourseReader::from_json(reader)
+        }
+    }
+}
+
+impl<R: BufRead> CourseReader<R> {
+    pub fn from_jsonl(reader: R) -> Self {
+        CourseReader::new(reader, State::Jsonl)
+    }
+
+    /// Reads the wrapper up to the first course, so the layout is known before any record.
+    pub fn from_json(reader: R) -> Result<Self, SchemaError> {
+        let mut this = CourseReader::new(reader, State::Done);
+        if this.scanner.skip_ws()? != Some(b'{') {
+            return Err(SchemaError::UnknownLayout(Vec::new()));
+        }
+        this.scanner.bump();
+        this.read_keys(true)?;
+        Ok(this)
+    }
+
+    fn new(reader: R, state: State) -> Self {
+        CourseReader {
+            scanner: Scanner { reader, offset: 0 },
+            state,
+            migrated_from: None,
+            version: None,
+            keys: Vec::new(),
+            index: 0,
+        }
+    }
+
+    /// The older layout the records are migrated from, if any.
+    pub fn migrated_from(&self) -> Option<LegacyLayout> {
+        self.migrated_from
+    }
+
+    /// Reads top-level keys until the course list starts (when `find_list`) or the wrapper
+    /// closes. Only the first course list counts; later values are skipped.
+    fn read_keys(&mut self, find_list: bool) -> Result<(), SchemaError> {
+        loop {
+            match self.scanner.skip_ws()? {
+                Some(b'}') => {
+                    self.scanner.bump();
+                    self.state = State::Done;
+                    return if find_list || (self.version.is_none() && self.migrated_from.is_none())
+                    {
+                        Err(SchemaError::UnknownLayout(std::mem::take(&mut self.keys)))
+                    } else {
+                        Ok(())
+                    };
+                }
+                Some(b',') => self.scanner.bump(),
+                Some(b'"') => {}
+                _ => return Err(self.scanner.malformed("expected a key")),
+            }
+            let key: String = serde_json::from_slice(&self.scanner.value()?)?;
+            if self.scanner.skip_ws()? != Some(b':') {
+                return Err(self.scanner.malformed("expected ':' after a key"));
+            }
+            self.scanner.bump();
+
+            if key == "schema_version" {
+                let version = serde_json::from_slice::<u32>(&self.scanner.value()?)
+                    .map_err(|_| SchemaError::UnknownLayout(self.keys.clone()))?;
+                if version != SCHEMA_VERSION {
+                    return Err(SchemaError::UnsupportedVersion(version));
+                }
+                self.version = Some(version);
+                continue;
+            }
+            let layout = if key == "courses" {
+                Some(None)
+            } else if self.version.is_none() {
+                LegacyLayout::ALL
+                    .into_iter()
+                    .find(|layout| layout.key() == key)
+                    .map(Some)
+            } else {
+                None
+            };
+            match layout {
+                Some(migrated_from) if find_list => {
+                    if self.scanner.skip_ws()? != Some(b'[') {
+                        return Err(SchemaError::UnknownLayout(vec![key]));
+                    }
+                    self.scanner.bump();
+                    self.keys.push(key);
+                    self.migrated_from = migrated_from;
+                    self.state = State::Array;
+                    return Ok(());
+                }
+                _ => {
+                    self.scanner.value()?;
+                    self.keys.push(key);
+                }
+            }
+        }
+    }
+
+    fn next_in_array(&mut self) -> Option<Result<Course, SchemaError>> {
+        match self.scanner.skip_ws() {
+            Ok(Some(b']')) => {
+                self.scanner.bump();
+                // The rest of the wrapper, which may still hold the schema version.
+                return self.read_keys(false).err().map(Err);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => return Some(Err(self.scanner.malformed("file ends inside the list"))),
+            Err(e) => return Some(Err(e)),
+        }
+        let value = match self.scanner.value() {
+            Ok(value) => value,
+            Err(e) => return Some(Err(e)),
+        };
+        match self.scanner.skip_ws() {
+            Ok(Some(b',')) => self.scanner.bump(),
+            Ok(Some(b']')) => {}
+            Ok(_) => return Some(Err(self.scanner.malformed("expected ',' or ']'"))),
+            Err(e) => return Some(Err(e)),
+        }
+        Some(self.parse(&value))
+    }
+
+    fn next_line(&mut self) -> Option<Result<Course, SchemaError>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.scanner.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => return Some(self.parse(line.as_bytes())),
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+
+    fn parse(&mut self, value: &[u8]) -> Result<Course, SchemaError> {
+        let index = self.index;
+        self.index += 1;
+        let course = if self.migrated_from.is_some() {
+            serde_json::from_slice::<RawCourse>(value).map(Course::from)
+        } else {
+            serde_json::from_slice::<Course>(value)
+        };
+        course.map_err(|source| SchemaError::InvalidRecord { index, source })
+    }
+}
+
+impl<R: BufRead> Iterator for CourseReader<R> {
+    type Item = Result<Course, SchemaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.state {
+            State::Jsonl => self.next_line(),
+            State::Array => self.next_in_array(),
+            State::Done => None,
+        };
+        if let Some(Err(e)) = &item
+            && !matches!(e, SchemaError::InvalidRecord { .. })
+        {
+            self.state = State::Done;
+        }
+        item
+    }
+}
+
+/// Writes courses one at a time in the wrapped layout or as JSONL. Call
+/// [`finish`](CourseWriter::finish) to close the wrapper.
+pub struct CourseWriter<W: Write> {
+    writer: W,
+    jsonl: bool,
+    count: usize,
+}
+
+impl CourseWriter<BufWriter<File>> {
+    /// Creates a course file, choosing the format by its extension.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, SchemaError> {
+        let path = path.as_ref();
+        let writer = BufWriter::new(File::create(path)?);
+        if is_jsonl(path) {
+            Ok(CourseWriter::jsonl(writer))
+        } else {
+            CourseWriter::json(writer)
+        }
+    }
+}
+
+impl<W: Write> CourseWriter<W> {
+    pub fn jsonl(writer: W) -> Self {
+        CourseWriter {
+            writer,
+            jsonl: true,
+            count: 0,
+        }
+    }
+
+    /// The wrapped layout, with one course per line inside it.
+    pub fn json(mut writer: W) -> Result<Self, SchemaError> {
+        write!(
+            writer,
+            "{{\n  \"schema_version\": {SCHEMA_VERSION},\n  \"courses\": ["
+        )?;
+        Ok(CourseWriter {
+            writer,
+            jsonl: false,
+            count: 0,
+        })
+    }
+
+    pub fn write(&mut self, course: &Course) -> Result<(), SchemaError> {
+        if self.jsonl {
+            serde_json::to_writer(&mut self.writer, course)?;
+            self.writer.write_all(b"\n")?;
+        } else {
+            let separator: &[u8] = if self.count == 0 {
+                b"\n    "
+            } else {
+                b",\n    "
+            };
+            self.writer.write_all(separator)?;
+            serde_json::to_writer(&mut self.writer, course)?;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Courses written so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn finish(mut self) -> Result<W, SchemaError> {
+        if !self.jsonl {
+            let close: &[u8] = if self.count == 0 {
+                b"]\n}\n"
+            } else {
+                b"\n  ]\n}\n"
+            };
+            self.writer.write_all(close)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Just enough of a JSON tokenizer to walk the wrapper and cut out one value at a time.
+struct Scanner<R> {
+    reader: R,
+    offset: u64,
+}
+
+impl<R: BufRead> Scanner<R> {
+    fn malformed(&self, message: &'static str) -> SchemaError {
+        SchemaError::Malformed {
+            offset: self.offset,
+            message,
+        }
+    }
+
+    /// The next byte that isn't whitespace, left unread.
+    fn skip_ws(&mut self) -> Result<Option<u8>, SchemaError> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            let Some(&byte) = buf.first() else {
+                return Ok(None);
+            };
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(byte));
+            }
+            self.bump();
+        }
+    }
+
+    fn bump(&mut self) {
+        self.reader.consume(1);
+        self.offset += 1;
+    }
+
+    /// The raw bytes of the next value, whatever its type.
+    fn value(&mut self) -> Result<Vec<u8>, SchemaError> {
+        self.skip_ws()?;
+        let mut out = Vec::new();
+        let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return if depth == 0 && !in_string && !out.is_empty() {
+                    Ok(out)
+                } else {
+                    Err(self.malformed("file ends inside a value"))
+                };
+            }
+            // Where the value ends in this chunk, if it does
+            let mut end = None;
+            for (i, &byte) in buf.iter().enumerate() {
+                if in_string {
+                    if escaped {
+                        escaped = false;
+                    } else if byte == b'\\' {
+                        escaped = true;
+                    } else if byte == b'"' {
+                        in_string = false;
+                        if depth == 0 {
+                            end = Some(i + 1);
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                match byte {
+                    b'"' => in_string = true,
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' if depth == 0 => {
+                        end = Some(i);
+                        break;
+                    }
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            end = Some(i + 1);
+                            break;
+                        }
+                    }
+                    b',' if depth == 0 => {
+                        end = Some(i);
+                        break;
+                    }
+                    _ if depth == 0 && byte.is_ascii_whitespace() => {
+                        end = Some(i);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let taken = end.unwrap_or(buf.len());
+            out.extend_from_slice(&buf[..taken]);
+            self.reader.consume(taken);
+            self.offset += taken as u64;
+            if end.is_some() {
+                return if out.is_empty() {
+                    Err(self.malformed("expected a value"))
+                } else {
+                    Ok(out)
+                };
+            }
+        }
+    }
+}
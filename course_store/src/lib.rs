@@ -0,0 +1,113 @@
+//! The SurrealDB course store behind the app, shared with `skillforge ingest` and the URL
+//! enricher so they all open the same database and write the same rows.
+
+use anyhow::{Context, Result};
+use course_schema::{Course, SchemaError};
+use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+use serde::Serialize;
+use surrealdb::Surreal;
+use surrealdb::engine::local::{Db, RocksDb};
+
+/// Model every course and query embedding comes from; vectors from different models can't be
+/// compared.
+pub const EMBEDDING_MODEL: EmbeddingModel = EmbeddingModel::ModernBertEmbedLarge;
+
+pub const COURSES_TABLE: &str = "courses";
+
+/// Opens the RocksDB store at `path`. RocksDB allows one process at a time, so the app has to
+/// be stopped while the CLI writes to its database.
+pub async fn open(path: &str) -> Result<Surreal<Db>> {
+    let db = Surreal::new::<RocksDb>(path)
+        .await
+        .context("Failed to connect to Database")?;
+    db.use_ns("main").use_db("main").await?;
+    Ok(db)
+}
+
+/// Text a course is embedded from, shared by ingestion and admin edits so both produce
+/// comparable vectors.
+pub fn embedding_text(course: &Course) -> String {
+    format!(
+        "Title: {}, topic: {}, description: {}, content: {}, Skill Path: {}, Prerequisites: {}, level: {}, Topic Size : {}",
+        course.title,
+        course.topic,
+        course.description,
+        course.content,
+        course.skill_path,
+        course.prerequisite_topics.join(", "),
+        course.level,
+        course.ctype
+    )
+}
+
+/// A new row of the `courses` table. Mirrors the app's `CoursesDataWithEmbeddings`, whose
+/// remaining fields default when read back.
+#[derive(Serialize)]
+struct NewCourseRow {
+    title: String,
+    description: String,
+    channel_name: String,
+    published_date: String,
+    skill_path: String,
+    level: String,
+    ctype: String,
+    content: String,
+    topic: String,
+    prerequisite_topics: Vec<String>,
+    embedding: Vec<f32>,
+    url: String,
+    duration_minutes: Option<u32>,
+    deactivated: bool,
+}
+
+/// Embeds every course as it is read and adds it to the store, returning how many were added.
+/// Records that don't match the schema are reported and skipped. Drops the stored taxonomy so
+/// the app rebuilds it from the new corpus on its next start.
+pub async fn ingest(
+    db: &Surreal<Db>,
+    courses: impl IntoIterator<Item = Result<Course, SchemaError>>,
+) -> Result<usize> {
+    let mut model = TextEmbedding::try_new(InitOptions::new(EMBEDDING_MODEL))?;
+    let mut total = 0;
+    for course in courses {
+        let course = match course {
+            Ok(course) => course,
+            Err(e @ SchemaError::InvalidRecord { .. }) => {
+                eprintln!("Skipping a record: {e}");
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        println!("Processing and storing: {total}");
+        let embedding = model
+            .embed(vec![embedding_text(&course)], None)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Empty embedding returned"))?;
+        let duration_minutes = course.duration_minutes();
+        let row = NewCourseRow {
+            title: course.title,
+            description: course.description,
+            channel_name: course.channel_name,
+            published_date: course.published_date,
+            skill_path: course.skill_path,
+            level: course.level,
+            ctype: course.ctype,
+            content: course.content,
+            topic: course.topic,
+            prerequisite_topics: course.prerequisite_topics,
+            embedding,
+            url: course.url.unwrap_or_default(), // Usually added later by the URL enricher
+            duration_minutes,
+            deactivated: false,
+        };
+        db.query(format!("CREATE {COURSES_TABLE} CONTENT $row"))
+            .bind(("row", row))
+            .await?
+            .check()?;
+        total += 1;
+    }
+
+    db.query("DELETE taxonomy:current").await?.check()?;
+    Ok(total)
+}
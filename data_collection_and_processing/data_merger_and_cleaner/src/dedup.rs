@@ -0,0 +1,384 @@
+//! Near-duplicate detection: the same course scraped from two sources, or reuploaded under a
+//! slightly different title, despite having a different ID.
+//!
+//! Records are compared by normalized title similarity and, with `--embedding-similarity`
+//! (needs the `embeddings` feature), by the cosine similarity of their embeddings. Matches are
+//! clustered, each cluster keeps its richest record, and every record merged away is logged
+//! to `<output>.merges.jsonl`.
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use course_schema::Course;
+use serde::Serialize;
+
+use crate::scoring;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Description similarity that backs up a title match. Records from different channels
+/// always need it, since unrelated creators often pick the same generic title; records from
+/// one channel need it, or the same duration, unless their titles are the same.
+const DESCRIPTION_SIMILARITY: f64 = 0.6;
+/// Characters of description compared; enough to tell courses apart, cheap to compare.
+const DESCRIPTION_PREFIX_CHARS: usize = 200;
+
+// Settings for the near-duplicate stage. A plain comment, since clap would use a doc comment
+// as the help text of every command it is in.
+#[derive(Debug, Clone, Args)]
+pub struct DedupArgs {
+    /// Records whose normalized titles are at least this similar (0-1) are duplicates; 1 only
+    /// merges titles that differ in case and punctuation.
+    #[arg(long, default_value_t = 0.85)]
+    pub title_similarity: f64,
+    /// How many neighbouring titles, in sorted order, each title is compared with.
+    #[arg(long, default_value_t = 20)]
+    pub dedup_window: usize,
+    /// Also merge records on the same topic whose embeddings have at least this cosine
+    /// similarity (0-1). Needs the merger built with the `embeddings` feature.
+    #[arg(long, value_name = "COSINE")]
+    pub embedding_similarity: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMethod {
+    Title,
+    #[cfg_attr(not(feature = "embeddings"), allow(dead_code))]
+    Embedding,
+}
+
+/// One record merged away, and why.
+#[derive(Debug, Serialize)]
+pub struct MergeDecision {
+    pub dropped: String,
+    pub kept: String,
+    /// The record it was found similar to; the kept one, or another in the same cluster.
+    pub matched: String,
+    pub method: MatchMethod,
+    pub similarity: f64,
+    pub dropped_title: String,
+    pub kept_title: String,
+}
+
+/// What near-duplicate detection needs of a record, so the records themselves needn't stay in
+/// memory.
+pub struct Candidate {
+    pub id: String,
+    /// Stands in for the full description when the scorer counts repeats.
+    pub description_key: Option<u64>,
+    title: String,
+    normalized_title: String,
+    numbers: Vec<String>,
+    channel: String,
+    duration_minutes: Option<u32>,
+    /// The start of the description.
+    description: String,
+    /// Only embedding matching groups records by topic.
+    #[cfg_attr(not(feature = "embeddings"), allow(dead_code))]
+    topic: String,
+    richness: (usize, usize, usize),
+}
+
+impl Candidate {
+    pub fn new(id: String, video: &Course) -> Self {
+        Candidate {
+            id,
+            description_key: scoring::description_key(&video.description),
+            title: video.title.clone(),
+            normalized_title: normalize(&video.title),
+            numbers: numbers(&video.title),
+            channel: normalize(&video.channel_name),
+            duration_minutes: video.duration_minutes(),
+            description: video
+                .description
+                .chars()
+                .take(DESCRIPTION_PREFIX_CHARS)
+                .collect(),
+            topic: normalize(&video.topic),
+            richness: richness(video),
+        }
+    }
+}
+
+struct Match {
+    a: usize,
+    b: usize,
+    method: MatchMethod,
+    similarity: f64,
+}
+
+/// Finds the records that duplicate another one, keeping the richest of each cluster.
+pub fn find_near_duplicates(videos: &[Candidate], args: &DedupArgs) -> Result<Vec<MergeDecision>> {
+    if !(0.0..=1.0).contains(&args.title_similarity) {
+        bail!("--title-similarity must be between 0 and 1");
+    }
+
+    let mut matches = title_matches(videos, args);
+    if let Some(threshold) = args.embedding_similarity {
+        if !(0.0..=1.0).contains(&threshold) {
+            bail!("--embedding-similarity must be between 0 and 1");
+        }
+        matches.extend(embedding_matches(videos, threshold)?);
+    }
+
+    // Cluster the matches, remembering the first match that brought each record in
+    let mut clusters = UnionFind::new(videos.len());
+    let mut evidence: HashMap<usize, &Match> = HashMap::new();
+    for m in &matches {
+        clusters.union(m.a, m.b);
+        evidence.entry(m.a).or_insert(m);
+        evidence.entry(m.b).or_insert(m);
+    }
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &i in evidence.keys() {
+        members.entry(clusters.find(i)).or_default().push(i);
+    }
+
+    let mut decisions = Vec::new();
+    for mut cluster in members.into_values() {
+        cluster.sort_unstable();
+        // Richest first; the earliest loaded wins a tie
+        let kept = *cluster
+            .iter()
+            .max_by_key(|&&i| (videos[i].richness, std::cmp::Reverse(i)))
+            .expect("clusters aren't empty");
+        for &i in cluster.iter().filter(|&&i| i != kept) {
+            let m = evidence[&i];
+            let matched = if m.a == i { m.b } else { m.a };
+            decisions.push(MergeDecision {
+                dropped: videos[i].id.clone(),
+                kept: videos[kept].id.clone(),
+                matched: videos[matched].id.clone(),
+                method: m.method,
+                similarity: m.similarity,
+                dropped_title: videos[i].title.clone(),
+                kept_title: videos[kept].title.clone(),
+            });
+        }
+    }
+    decisions.sort_by(|a, b| a.kept.cmp(&b.kept).then(a.dropped.cmp(&b.dropped)));
+    Ok(decisions)
+}
+
+/// Rewrites the merge log next to `output`.
+pub fn write_merge_log(path: &Path, decisions: &[MergeDecision]) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("Couldn't create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    for decision in decisions {
+        serde_json::to_writer(&mut writer, decision)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Sorted-neighbourhood comparison: titles are sorted, and also sorted back to front so a
+/// changed first word doesn't hide a match, and each is compared with the next few.
+fn title_matches(videos: &[Candidate], args: &DedupArgs) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for reversed in [false, true] {
+        let mut order: Vec<usize> = (0..videos.len())
+            .filter(|&i| !videos[i].normalized_title.is_empty())
+            .collect();
+        order.sort_by_cached_key(|&i| {
+            let title = &videos[i].normalized_title;
+            if reversed {
+                title.chars().rev().collect()
+            } else {
+                title.clone()
+            }
+        });
+        for (pos, &a) in order.iter().enumerate() {
+            for &b in order[pos + 1..].iter().take(args.dedup_window) {
+                let (va, vb) = (&videos[a], &videos[b]);
+                if va.numbers != vb.numbers {
+                    continue;
+                }
+                let similarity =
+                    strsim::normalized_levenshtein(&va.normalized_title, &vb.normalized_title);
+                if similarity < args.title_similarity {
+                    continue;
+                }
+                // A channel's "JavaScript Tutorial" and "TypeScript Tutorial" are a word apart
+                // but different videos, so a title that changed needs more to go on.
+                let corroborated = if va.channel == vb.channel {
+                    va.normalized_title == vb.normalized_title
+                        || descriptions_agree(va, vb)
+                        || (va.duration_minutes.is_some()
+                            && va.duration_minutes == vb.duration_minutes)
+                } else {
+                    descriptions_agree(va, vb)
+                };
+                if !corroborated {
+                    continue;
+                }
+                matches.push(Match {
+                    a,
+                    b,
+                    method: MatchMethod::Title,
+                    similarity,
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Compares every pair of records on the same topic by the cosine similarity of their
+/// embeddings; comparing across topics would be quadratic in the whole dataset.
+#[cfg(feature = "embeddings")]
+fn embedding_matches(videos: &[Candidate], threshold: f32) -> Result<Vec<Match>> {
+    use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+
+    let mut topics: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, video) in videos.iter().enumerate() {
+        if !video.topic.is_empty() {
+            topics.entry(&video.topic).or_default().push(i);
+        }
+    }
+    let candidates: Vec<usize> = topics
+        .values()
+        .filter(|group| group.len() > 1)
+        .flatten()
+        .copied()
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    println!("Embedding {} records to compare...", candidates.len());
+    // Only compared with each other, so a small model will do.
+    let mut model = TextEmbedding::try_new(InitOptions::new(EmbeddingModel::AllMiniLML6V2))?;
+    let texts: Vec<String> = candidates
+        .iter()
+        .map(|&i| format!("{}. {}", videos[i].title, videos[i].description))
+        .collect();
+    let embeddings: HashMap<usize, Vec<f32>> = candidates
+        .iter()
+        .copied()
+        .zip(model.embed(texts, None)?.into_iter().map(unit))
+        .collect();
+
+    let mut matches = Vec::new();
+    for group in topics.values().filter(|group| group.len() > 1) {
+        for (pos, &a) in group.iter().enumerate() {
+            for &b in &group[pos + 1..] {
+                if videos[a].numbers != videos[b].numbers {
+                    continue;
+                }
+                let cosine: f32 = embeddings[&a]
+                    .iter()
+                    .zip(&embeddings[&b])
+                    .map(|(x, y)| x * y)
+                    .sum();
+                if cosine >= threshold {
+                    matches.push(Match {
+                        a,
+                        b,
+                        method: MatchMethod::Embedding,
+                        similarity: f64::from(cosine),
+                    });
+                }
+            }
+        }
+    }
+    Ok(matches)
+}
+
+#[cfg(not(feature = "embeddings"))]
+fn embedding_matches(_videos: &[Candidate], _threshold: f32) -> Result<Vec<Match>> {
+    bail!(
+        "--embedding-similarity needs the merger built with the `embeddings` feature, e.g. `cargo run -p skillforge_cli --features embeddings -- merge`"
+    )
+}
+
+#[cfg(feature = "embeddings")]
+fn unit(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+    vector
+}
+
+/// Lowercase words without punctuation, so "Rust: The Basics!" equals "rust the basics".
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The numbers in a title. Titles differing only in these, like "Part 1" and "Part 2", are
+/// different videos however similar they look.
+fn numbers(title: &str) -> Vec<String> {
+    title
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Two empty descriptions don't agree on anything.
+fn descriptions_agree(a: &Candidate, b: &Candidate) -> bool {
+    let (a, b) = (normalize(&a.description), normalize(&b.description));
+    !a.is_empty()
+        && !b.is_empty()
+        && strsim::normalized_levenshtein(&a, &b) >= DESCRIPTION_SIMILARITY
+}
+
+/// How much a record tells us: filled-in fields first, then prerequisites, then description
+/// length.
+fn richness(video: &Course) -> (usize, usize, usize) {
+    let filled = [
+        &video.description,
+        &video.channel_name,
+        &video.published_date,
+        &video.skill_path,
+        &video.level,
+        &video.ctype,
+        &video.content,
+        &video.topic,
+    ]
+    .into_iter()
+    .filter(|field| !field.trim().is_empty())
+    .count()
+        + usize::from(video.duration.is_some())
+        + usize::from(video.url.is_some())
+        + usize::from(video.enhanced_with_llm);
+    (
+        filled,
+        video.prerequisite_topics.len(),
+        video.description.chars().count(),
+    )
+}
+
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent[b.max(a)] = a.min(b);
+        }
+    }
+}
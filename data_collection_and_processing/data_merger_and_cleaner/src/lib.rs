@@ -0,0 +1,476 @@
+//! Merges processed course files, drops duplicates and filters out low-quality records;
+//! `skillforge merge`.
+
+mod dedup;
+mod scoring;
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+use course_schema::{Course, CourseReader, CourseWriter, SchemaError};
+use futures::stream::{self, StreamExt};
+use glob::glob;
+use indicatif::{ProgressBar, ProgressStyle};
+use pipeline_common::checkpoint::{
+    Checkpoint, CheckpointEntry, Outcome, RejectsFile, RetryFile, finished_ids, is_sidecar,
+    record_id, sidecar_path,
+};
+use pipeline_common::llm::{LlmArgs, LlmClient};
+use pipeline_common::usage::{self, Sample, UsageReport};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use dedup::Candidate;
+pub use dedup::DedupArgs;
+pub use scoring::ScoringArgs;
+use scoring::{QualityScorer, RuleDecision};
+
+// --- Data Structures ---
+
+#[derive(Deserialize)]
+struct QualityCheck {
+    valid: bool,
+    /// 0 (junk) to 10 (excellent), if the model gave one.
+    score: Option<f32>,
+    reason: Option<String>,
+}
+
+/// What happens to a record when its quality check can't be completed, e.g. during an
+/// OpenRouter outage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailurePolicy {
+    /// Keep the record as if it had passed.
+    Open,
+    /// Reject the record; it is listed in the rejects file.
+    Closed,
+    /// Leave the record out of this run and list it in the retry file for the next one.
+    Defer,
+}
+
+/// The cleaner's decision on one record.
+enum Verdict {
+    Keep,
+    Reject { reason: String, score: Option<f32> },
+}
+
+// --- Configuration ---
+const INPUT_PATTERNS: [&str; 2] = [
+    "data_collection_and_processing/processed_datasets/*.json",
+    "data_collection_and_processing/processed_datasets/*.jsonl",
+]; // Change this to your directory
+const OUTPUT_FILE: &str = "final_data.json";
+const MODEL: &str = "nvidia/nemotron-3-nano-30b-a3b:free";
+const MAX_CONCURRENT_REQUESTS: usize = 750; // Adjust based on rate limits
+/// Name of this stage in usage reports.
+const STAGE: &str = "merge";
+/// Completion tokens assumed per check until a run has measured them; one short verdict.
+const EXPECTED_COMPLETION_TOKENS: u64 = 40;
+
+#[derive(Debug, Clone, Args)]
+pub struct MergeArgs {
+    /// Discard the checkpoint of an earlier run and check every record again.
+    #[arg(long)]
+    pub fresh: bool,
+    /// What to do with a record whose quality check fails.
+    #[arg(long, value_enum, default_value_t = FailurePolicy::Defer)]
+    pub on_check_failure: FailurePolicy,
+    /// Find duplicates and score the records, then estimate what the LLM checks would cost,
+    /// without calling the model or writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Borderline records whose prompts a dry run measures to estimate the cost.
+    #[arg(long, default_value_t = 50)]
+    pub estimate_sample: usize,
+    #[command(flatten)]
+    pub dedup: DedupArgs,
+    #[command(flatten)]
+    pub scoring: ScoringArgs,
+    #[command(flatten)]
+    pub llm: LlmArgs,
+}
+
+pub async fn run(args: MergeArgs) -> Result<()> {
+    println!("🚀 Starting Data Merger...");
+    if args.scoring.reject_below >= args.scoring.accept_above {
+        bail!("--reject-below must be lower than --accept-above");
+    }
+
+    // 1. Read every input once, keeping only what near-duplicate detection needs
+    let output = Path::new(OUTPUT_FILE);
+    let done = if args.fresh {
+        HashSet::new()
+    } else {
+        finished_ids(output)?
+    };
+
+    let paths = input_paths()?;
+    println!("found {} files. Reading...", paths.len());
+    let mut candidates: Vec<Candidate> = Vec::new();
+    for_each_course(&paths, true, |id, video| {
+        candidates.push(Candidate::new(id, &video));
+        Ok(())
+    })?;
+
+    println!("Total unique videos loaded: {}", candidates.len());
+
+    // 2. Near-duplicates: keep the richest record of each cluster
+    let merges = dedup::find_near_duplicates(&candidates, &args.dedup)?;
+    let merge_log = sidecar_path(output, "merges.jsonl");
+    if !args.dry_run {
+        dedup::write_merge_log(&merge_log, &merges)?;
+    }
+    let merged_away: HashSet<String> = merges.into_iter().map(|m| m.dropped).collect();
+    if args.dry_run {
+        println!("Would merge away {} near-duplicates.", merged_away.len());
+    } else if !merged_away.is_empty() {
+        println!(
+            "Merged away {} near-duplicates; see {} for each decision.",
+            merged_away.len(),
+            merge_log.display()
+        );
+    }
+
+    let mut scorer = QualityScorer::new(args.scoring.clone());
+    let (mut already_done, mut to_check) = (0, 0);
+    for candidate in candidates.iter().filter(|c| !merged_away.contains(&c.id)) {
+        scorer.observe(&candidate.id, candidate.description_key);
+        if done.contains(&candidate.id) {
+            already_done += 1;
+        } else {
+            to_check += 1;
+        }
+    }
+    drop(candidates);
+    println!("{} already checked, {} to go", already_done, to_check);
+
+    if args.dry_run {
+        return estimate_run(&args, &paths, &merged_away, &done, &scorer).await;
+    }
+    drop(done);
+    let api_key = env::var("OPENROUTER_API_KEY").context("OPENROUTER_API_KEY not set")?;
+    let mut checkpoint = Checkpoint::open(output, args.fresh)?;
+    let mut retries = RetryFile::create(output)?;
+    let mut rejects = RejectsFile::open(output, args.fresh)?;
+
+    // 3. Read the inputs again for rule-based scoring, skipping what an earlier run
+    // finished; only borderline records go on to the LLM. They are spilled to a file rather
+    // than held in memory, since they can be a large share of the input.
+    let borderline_path = sidecar_path(output, "borderline.jsonl");
+    let mut borderline = CourseWriter::create(&borderline_path)
+        .with_context(|| format!("Couldn't create {}", borderline_path.display()))?;
+    let (mut rule_accepted, mut rule_rejected) = (0, 0);
+    for_each_course(&paths, false, |id, video| {
+        if merged_away.contains(&id) || checkpoint.is_done(&id) {
+            return Ok(());
+        }
+        let verdict = match scorer.decide(&id, &video) {
+            RuleDecision::Accept => {
+                rule_accepted += 1;
+                Verdict::Keep
+            }
+            RuleDecision::Reject { reason, score } => {
+                rule_rejected += 1;
+                Verdict::Reject {
+                    reason,
+                    score: Some(score),
+                }
+            }
+            RuleDecision::Borderline => {
+                borderline.write(&video)?;
+                return Ok(());
+            }
+        };
+        settle(id, video, verdict, &mut checkpoint, &mut rejects)
+    })?;
+    let borderline_count = borderline.len();
+    borderline.finish()?;
+    println!(
+        "Rules kept {} and rejected {} records; {} borderline records go to the LLM.",
+        rule_accepted, rule_rejected, borderline_count
+    );
+
+    // 4. Setup Parallel Processing
+    let client = LlmClient::new(api_key, &args.llm);
+    let outcome = async {
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+        let pb = ProgressBar::new(borderline_count as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}",
+                )?
+                .progress_chars("#>-"),
+        );
+
+        // 5. Process Stream, reading the borderline records back as the checks need them and
+        // checkpointing each verdict as it arrives
+        let reader = CourseReader::open(&borderline_path)
+            .with_context(|| format!("Couldn't read {}", borderline_path.display()))?;
+        let mut processed_results = stream::iter(reader)
+            .map(|video| {
+                let client = client.clone();
+                let permit = Arc::clone(&semaphore);
+                let pb = pb.clone();
+
+                async move {
+                    let video = video?;
+                    let _permit = permit.acquire().await.unwrap(); // Limit concurrency
+                    let result = process_video(&client, &video).await;
+                    pb.set_message(client.stats().to_string());
+                    pb.inc(1);
+                    Ok::<_, SchemaError>((video, result))
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS); // Run in parallel
+
+        while let Some(item) = processed_results.next().await {
+            let (video, result) =
+                item.with_context(|| format!("Couldn't read {}", borderline_path.display()))?;
+            let id = course_id(&video);
+            let verdict = match (result, args.on_check_failure) {
+                (Ok(verdict), _) => verdict,
+                (Err(_), FailurePolicy::Open) => Verdict::Keep,
+                (Err(error), FailurePolicy::Closed) => Verdict::Reject {
+                    reason: format!("Quality check failed: {error}"),
+                    score: None,
+                },
+                (Err(error), FailurePolicy::Defer) => {
+                    pb.println(format!("Quality check failed for {id}: {error}"));
+                    retries.record(&id, &error, &video)?;
+                    continue;
+                }
+            };
+            settle(id, video, verdict, &mut checkpoint, &mut rejects)?;
+        }
+
+        pb.finish_with_message(format!("Processing complete! ({})", client.stats()));
+        drop(processed_results);
+        fs::remove_file(&borderline_path)
+            .with_context(|| format!("Couldn't remove {}", borderline_path.display()))?;
+
+        // 6. Write the valid results, this run's and earlier ones, less records since found to
+        // duplicate another, straight from the checkpoint
+        let mut writer = CourseWriter::create(OUTPUT_FILE)
+            .with_context(|| format!("Couldn't create {OUTPUT_FILE}"))?;
+        checkpoint.write_kept(&mut writer, |id| !merged_away.contains(id))?;
+        println!("Final dataset size after cleaning: {}", writer.len());
+        writer.finish()?;
+
+        println!("✅ Successfully saved to {}", OUTPUT_FILE);
+        println!("OpenRouter: {}", client.stats());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    // Account for what was spent even when the run failed part way
+    UsageReport::record_run(STAGE, &client.usage(), output, args.fresh)?;
+    outcome?;
+    if !rejects.is_empty() {
+        println!(
+            "{} records were rejected this run; see {} for why.",
+            rejects.len(),
+            rejects.path().display()
+        );
+    }
+    if !retries.is_empty() {
+        println!(
+            "⚠️  {} quality checks failed and are listed in {}; run again to retry them.",
+            retries.len(),
+            retries.path().display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The processed datasets to merge, less the files the stages write next to their output.
+fn input_paths() -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for pattern in INPUT_PATTERNS {
+        paths.extend(glob(pattern)?.filter_map(Result::ok));
+    }
+    paths.retain(|path| !is_sidecar(path));
+    paths.sort();
+    Ok(paths)
+}
+
+/// Streams the courses of `paths` to `f` with their record IDs, each ID once. Records that
+/// don't match the schema are reported and skipped.
+fn for_each_course(
+    paths: &[PathBuf],
+    announce: bool,
+    mut f: impl FnMut(String, Course) -> Result<()>,
+) -> Result<()> {
+    let mut seen_ids: HashSet<String> = HashSet::new();
+    for path in paths {
+        let reader = CourseReader::open(path)
+            .with_context(|| format!("Couldn't read {}", path.display()))?;
+        if announce && let Some(layout) = reader.migrated_from() {
+            println!(
+                "Migrated {} from the legacy '{}' layout",
+                path.display(),
+                layout.key()
+            );
+        }
+
+        for video in reader {
+            let video = match video {
+                Ok(video) => video,
+                Err(e @ SchemaError::InvalidRecord { .. }) => {
+                    if announce {
+                        eprintln!("Skipping a record of {}: {e}", path.display());
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Couldn't read {}", path.display()));
+                }
+            };
+            // Deduplication Logic: Check ID immediately
+            let id = course_id(&video);
+            if seen_ids.insert(id.clone()) {
+                f(id, video)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Scores the records a run would check, without recording anything, and estimates what the
+/// LLM checks of the borderline ones would cost.
+async fn estimate_run(
+    args: &MergeArgs,
+    paths: &[PathBuf],
+    merged_away: &HashSet<String>,
+    done: &HashSet<String>,
+    scorer: &QualityScorer,
+) -> Result<()> {
+    let mut sample = Sample::new(args.estimate_sample);
+    let (mut rule_accepted, mut rule_rejected) = (0, 0);
+    for_each_course(paths, false, |id, video| {
+        if merged_away.contains(&id) || done.contains(&id) {
+            return Ok(());
+        }
+        match scorer.decide(&id, &video) {
+            RuleDecision::Accept => rule_accepted += 1,
+            RuleDecision::Reject { .. } => rule_rejected += 1,
+            RuleDecision::Borderline => sample.offer(|| quality_prompt(&video)),
+        }
+        Ok(())
+    })?;
+    println!(
+        "Dry run: rules would keep {} and reject {} records, and send {} borderline records to {}.",
+        rule_accepted,
+        rule_rejected,
+        sample.offered(),
+        MODEL
+    );
+    let estimate = usage::estimate(
+        STAGE,
+        MODEL,
+        sample.offered(),
+        sample.items(),
+        EXPECTED_COMPLETION_TOKENS,
+        Path::new(OUTPUT_FILE),
+    )
+    .await?;
+    println!(
+        "Estimated from {} sampled records: {}",
+        sample.items().len(),
+        estimate
+    );
+    Ok(())
+}
+
+/// The stable ID a course is deduplicated and checkpointed under.
+fn course_id(video: &Course) -> String {
+    record_id(
+        &video.video_id,
+        &[&video.title, &video.channel_name, &video.description],
+    )
+}
+
+/// Checkpoints the verdict on a record, and lists it in the rejects file if it was rejected.
+fn settle(
+    id: String,
+    video: Course,
+    verdict: Verdict,
+    checkpoint: &mut Checkpoint,
+    rejects: &mut RejectsFile,
+) -> Result<()> {
+    let outcome = match verdict {
+        // Transformation (Remove unwanted fields)
+        Verdict::Keep => Outcome::Kept {
+            course: Box::new(Course {
+                views: None,
+                likes: None,
+                ..video
+            }),
+        },
+        Verdict::Reject { reason, score } => {
+            rejects.record(&id, &reason, score, &video)?;
+            Outcome::Dropped { reason }
+        }
+    };
+    checkpoint.record(&CheckpointEntry { id, outcome })
+}
+
+/// The LLM's verdict on a borderline record. `Err` means the check itself failed, and
+/// `--on-check-failure` decides what happens to the record.
+async fn process_video(client: &LlmClient, video: &Course) -> Result<Verdict, String> {
+    let check = check_quality_with_llm(client, video).await?;
+
+    if check.valid {
+        Ok(Verdict::Keep)
+    } else {
+        Ok(Verdict::Reject {
+            reason: check
+                .reason
+                .unwrap_or_else(|| "Rejected by the quality check".to_string()),
+            score: check.score,
+        })
+    }
+}
+
+async fn check_quality_with_llm(
+    client: &LlmClient,
+    video: &Course,
+) -> Result<QualityCheck, String> {
+    let payload = serde_json::json!({
+        "model": MODEL,
+        "messages": [
+            {"role": "user", "content": quality_prompt(video)}
+        ],
+        "response_format": { "type": "json_object" } // Force JSON if supported, otherwise prompt handles it
+    });
+
+    let content = client.chat(&payload).await.map_err(|e| e.to_string())?;
+    // Attempt to parse the boolean verdict
+    if let Ok(verdict) = serde_json::from_str::<QualityCheck>(&content) {
+        return Ok(verdict);
+    }
+    // Fallback cleanup if model outputs markdown code blocks
+    let clean = content.replace("```json", "").replace("```", "");
+    serde_json::from_str::<QualityCheck>(&clean).map_err(|e| format!("Unreadable verdict: {e}"))
+}
+
+/// A lightweight prompt asking whether `video` is worth keeping.
+fn quality_prompt(video: &Course) -> String {
+    format!(
+        "Analyze this video metadata for a dataset. \
+        Title: '{}'. \
+        Description: '{}'. \
+        Topic: '{}'. \
+        Is this entry unambiguous, educational, and of acceptable quality? \
+        Reject if it is spam, placeholder text, or completely ambiguous (e.g., title is just 'Video 1'). \
+        Score its quality from 0 (junk) to 10 (excellent). \
+        Respond ONLY with valid JSON: {{\"valid\": true, \"score\": <0-10>}} or \
+        {{\"valid\": false, \"score\": <0-10>, \"reason\": \"<short reason>\"}}",
+        video.title,
+        video.description.chars().take(200).collect::<String>(), // Truncate desc to save tokens
+        video.topic
+    )
+}
@@ -0,0 +1,246 @@
+//! Deterministic quality scoring, so only borderline records cost an LLM call.
+//!
+//! Every record starts at 10 and loses points per problem found. Records at or below
+//! `--reject-below` are rejected and records at or above `--accept-above` kept without asking
+//! the model; the rest go to the LLM quality check.
+
+use clap::Args;
+use course_schema::Course;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+const MAX_SCORE: f32 = 10.0;
+
+/// Titles that say nothing once a trailing number is stripped, like "Video 1" or "Part 3".
+const PLACEHOLDER_TITLES: &[&str] = &[
+    "video",
+    "new video",
+    "untitled",
+    "lecture",
+    "lesson",
+    "part",
+    "episode",
+    "clip",
+    "class",
+    "test",
+    "intro",
+    "introduction",
+];
+
+/// Words that mark entertainment or promotion rather than teaching, matched as whole words.
+/// Kept to phrases a lesson wouldn't use: "reaction" alone is half of chemistry.
+const NON_EDUCATIONAL_KEYWORDS: &[&str] = &[
+    "giveaway",
+    "prank",
+    "unboxing",
+    "reaction video",
+    "vlog",
+    "asmr",
+    "music video",
+    "official trailer",
+    "gameplay",
+    "funny moments",
+    "free money",
+    "like and subscribe",
+];
+
+// Thresholds for the rule-based scorer. A plain comment, since clap would use a doc comment
+// as the help text of every command it is in.
+#[derive(Debug, Clone, Args)]
+pub struct ScoringArgs {
+    /// Reject records scoring at or below this (0-10) without asking the LLM.
+    #[arg(long, default_value_t = 3.0)]
+    pub reject_below: f32,
+    /// Keep records scoring at or above this (0-10) without asking the LLM.
+    #[arg(long, default_value_t = 8.0)]
+    pub accept_above: f32,
+    /// Descriptions shorter than this many characters lose points.
+    #[arg(long, default_value_t = 40)]
+    pub min_description_chars: usize,
+    /// Descriptions with less character entropy than this, in bits, lose points; repeated
+    /// characters and keyboard mashing score low.
+    #[arg(long, default_value_t = 3.0)]
+    pub min_description_entropy: f64,
+    /// Text with a smaller share of ASCII letters than this (0-1) is treated as non-English.
+    #[arg(long, default_value_t = 0.7)]
+    pub min_english_ratio: f64,
+}
+
+/// What the rules make of one record.
+pub enum RuleDecision {
+    Accept,
+    Reject {
+        reason: String,
+        score: f32,
+    },
+    /// Too close to call; ask the LLM.
+    Borderline,
+}
+
+pub struct QualityScorer {
+    args: ScoringArgs,
+    /// The first loaded record with each [`description_key`]; only the records after it are
+    /// copies.
+    descriptions: HashMap<u64, String>,
+}
+
+impl QualityScorer {
+    pub fn new(args: ScoringArgs) -> Self {
+        QualityScorer {
+            args,
+            descriptions: HashMap::new(),
+        }
+    }
+
+    /// Notes a record's description, so later records copying it are penalized. Call for
+    /// every loaded record, in load order, before scoring any.
+    pub fn observe(&mut self, id: &str, description_key: Option<u64>) {
+        if let Some(key) = description_key {
+            self.descriptions
+                .entry(key)
+                .or_insert_with(|| id.to_string());
+        }
+    }
+
+    pub fn decide(&self, id: &str, video: &Course) -> RuleDecision {
+        let (score, problems) = self.score(id, video);
+        if score <= self.args.reject_below {
+            RuleDecision::Reject {
+                reason: problems.join("; "),
+                score,
+            }
+        } else if score >= self.args.accept_above {
+            RuleDecision::Accept
+        } else {
+            RuleDecision::Borderline
+        }
+    }
+
+    /// The score from 0 to 10, and the problems that lowered it.
+    fn score(&self, id: &str, video: &Course) -> (f32, Vec<String>) {
+        let title = video.title.trim();
+        let description = video.description.trim();
+        if title.is_empty() || description.chars().count() < 10 {
+            return (0.0, vec!["Missing title or description".to_string()]);
+        }
+
+        let mut score = MAX_SCORE;
+        let mut problems = Vec::new();
+        let mut penalize = |points: f32, problem: String| {
+            score -= points;
+            problems.push(problem);
+        };
+
+        if is_placeholder_title(title) {
+            penalize(5.0, format!("Placeholder title '{title}'"));
+        }
+        let description_chars = description.chars().count();
+        if description_chars < self.args.min_description_chars {
+            penalize(
+                2.0,
+                format!("Description is only {description_chars} characters"),
+            );
+        }
+        let entropy = entropy(description);
+        if entropy < self.args.min_description_entropy {
+            penalize(3.0, format!("Repetitive description ({entropy:.1} bits)"));
+        }
+
+        let text = format!("{title} {description}").to_lowercase();
+        let words: Vec<&str> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let keywords: Vec<&str> = NON_EDUCATIONAL_KEYWORDS
+            .iter()
+            .copied()
+            .filter(|k| contains_phrase(&words, k))
+            .collect();
+        if !keywords.is_empty() {
+            penalize(
+                (2.0 * keywords.len() as f32).min(4.0),
+                format!("Non-educational keywords: {}", keywords.join(", ")),
+            );
+        }
+
+        if video.skill_path.trim().is_empty() {
+            penalize(1.0, "No skill path".to_string());
+        }
+        if video.topic.trim().is_empty() {
+            penalize(1.0, "No topic".to_string());
+        }
+        let ratio = english_ratio(&text);
+        if ratio < self.args.min_english_ratio {
+            penalize(
+                3.0,
+                format!("Likely not English ({:.0}% ASCII letters)", ratio * 100.0),
+            );
+        }
+        if let Some(first) = self
+            .descriptions
+            .get(&description_key(description).unwrap_or_default())
+            .filter(|&first| first != id)
+        {
+            penalize(2.0, format!("Description copied from {first}"));
+        }
+
+        (score.max(0.0), problems)
+    }
+}
+
+/// A hash of the description with case and spacing ignored, standing in for it when counting
+/// duplicates; `None` for an empty one.
+pub fn description_key(description: &str) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    let mut words = description.split_whitespace().peekable();
+    words.peek()?;
+    for word in words {
+        word.to_lowercase().hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+/// Whether the words of `phrase` appear in `words` one after another.
+fn contains_phrase(words: &[&str], phrase: &str) -> bool {
+    let phrase: Vec<&str> = phrase.split_whitespace().collect();
+    words.windows(phrase.len()).any(|w| w == phrase.as_slice())
+}
+
+fn is_placeholder_title(title: &str) -> bool {
+    let stripped = title
+        .to_lowercase()
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c.is_whitespace() || "#:-.".contains(c))
+        .to_string();
+    stripped.is_empty() || PLACEHOLDER_TITLES.contains(&stripped.as_str())
+}
+
+/// Shannon entropy of the characters of `text`, in bits per character.
+fn entropy(text: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        *counts.entry(c).or_default() += 1;
+    }
+    let total: usize = counts.values().sum();
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+/// Share of the letters in `text` that are ASCII; 1 when there are none.
+fn english_ratio(text: &str) -> f64 {
+    let (ascii, letters) = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .fold((0, 0), |(ascii, letters), c| {
+            (ascii + usize::from(c.is_ascii()), letters + 1)
+        });
+    if letters == 0 {
+        1.0
+    } else {
+        ascii as f64 / letters as f64
+    }
+}
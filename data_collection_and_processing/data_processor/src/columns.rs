@@ -0,0 +1,161 @@
+//! Which CSV header each input field is read from, so exports from other providers can be
+//! processed without code changes.
+
+use anyhow::{Result, bail};
+use csv::StringRecord;
+use pipeline_common::checkpoint::record_id;
+use serde::Serialize;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnField {
+    Name,
+    Id,
+    Type,
+    Category,
+    Summary,
+}
+
+impl ColumnField {
+    const ALL: [ColumnField; 5] = [
+        ColumnField::Name,
+        ColumnField::Id,
+        ColumnField::Type,
+        ColumnField::Category,
+        ColumnField::Summary,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            ColumnField::Name => "name",
+            ColumnField::Id => "id",
+            ColumnField::Type => "type",
+            ColumnField::Category => "category",
+            ColumnField::Summary => "summary",
+        }
+    }
+
+    /// Header in the Coursera-style export the processor was written for.
+    fn default_header(self) -> &'static str {
+        match self {
+            ColumnField::Name => "Course Name",
+            ColumnField::Id => "Course ID",
+            ColumnField::Type => "Course Type",
+            ColumnField::Category => "Course Category",
+            ColumnField::Summary => "Course Name and Summary",
+        }
+    }
+}
+
+impl FromStr for ColumnField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ColumnField::ALL
+            .into_iter()
+            .find(|f| f.key().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let keys: Vec<&str> = ColumnField::ALL.iter().map(|f| f.key()).collect();
+                format!("unknown field '{s}', expected one of: {}", keys.join(", "))
+            })
+    }
+}
+
+/// Parses a `--column FIELD=HEADER` argument.
+pub fn parse_column(arg: &str) -> Result<(ColumnField, String), String> {
+    let (field, header) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected FIELD=HEADER, got '{arg}'"))?;
+    Ok((field.parse()?, header.trim().to_string()))
+}
+
+/// One raw input row, read through a [`ColumnMapping`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CourseRecord {
+    pub name: String,
+    pub course_id: String,
+    pub course_type: String,
+    pub category: String,
+    pub summary: String,
+}
+
+impl CourseRecord {
+    /// Stable across runs, so a resumed run can tell which records it already has.
+    pub fn id(&self) -> String {
+        record_id(&self.course_id, &[&self.name, &self.summary])
+    }
+}
+
+/// Header per field. An empty header means the input has no such column and the field is
+/// left blank.
+#[derive(Debug, Clone)]
+pub struct ColumnMapping {
+    headers: Vec<(ColumnField, String)>,
+}
+
+impl ColumnMapping {
+    /// The default headers with `overrides` applied.
+    pub fn new(overrides: &[(ColumnField, String)]) -> Self {
+        let headers = ColumnField::ALL
+            .into_iter()
+            .map(|field| {
+                let header = overrides
+                    .iter()
+                    .rev()
+                    .find(|(f, _)| *f == field)
+                    .map(|(_, h)| h.clone())
+                    .unwrap_or_else(|| field.default_header().to_string());
+                (field, header)
+            })
+            .collect();
+        ColumnMapping { headers }
+    }
+
+    /// Finds each mapped header in a file's header row.
+    pub fn resolve(&self, file_headers: &StringRecord) -> Result<ResolvedColumns> {
+        let mut indices = Vec::new();
+        for (field, header) in &self.headers {
+            if header.is_empty() {
+                if *field == ColumnField::Name {
+                    bail!("the name column can't be left unmapped");
+                }
+                continue;
+            }
+            let Some(index) = file_headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(header))
+            else {
+                bail!(
+                    "column '{header}' for {} not found (headers: {}); map it with --column {}=<header>",
+                    field.key(),
+                    file_headers.iter().collect::<Vec<_>>().join(", "),
+                    field.key()
+                );
+            };
+            indices.push((*field, index));
+        }
+        Ok(ResolvedColumns { indices })
+    }
+}
+
+/// A [`ColumnMapping`] bound to one file's column positions.
+pub struct ResolvedColumns {
+    indices: Vec<(ColumnField, usize)>,
+}
+
+impl ResolvedColumns {
+    pub fn read(&self, row: &StringRecord) -> CourseRecord {
+        let mut record = CourseRecord::default();
+        for (field, index) in &self.indices {
+            let value = row.get(*index).unwrap_or_default().trim().to_string();
+            match field {
+                ColumnField::Name => record.name = value,
+                ColumnField::Id => record.course_id = value,
+                ColumnField::Type => record.course_type = value,
+                ColumnField::Category => record.category = value,
+                ColumnField::Summary => record.summary = value,
+            }
+        }
+        record
+    }
+}
@@ -0,0 +1,401 @@
+//! Turns the raw course CSV into LLM-enhanced course records; `skillforge process`.
+
+mod columns;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use course_schema::{Course, CourseWriter, RawCourse};
+use csv::{ReaderBuilder, StringRecordsIntoIter};
+use futures::stream::{self, StreamExt};
+use glob::glob;
+use indicatif::{ProgressBar, ProgressStyle};
+use pipeline_common::checkpoint::{self, Checkpoint, CheckpointEntry, Outcome, RetryFile};
+use pipeline_common::llm::{LlmArgs, LlmClient};
+use pipeline_common::usage::{self, Sample, UsageReport};
+use serde_json::json;
+use std::collections::HashSet;
+use std::env;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+pub use columns::ColumnField;
+use columns::{ColumnMapping, CourseRecord, ResolvedColumns, parse_column};
+
+const DEFAULT_INPUT: &str = "data_collection_and_processing/datasets/Courses_w_Clean_Summaries.csv";
+const DEFAULT_OUTPUT: &str =
+    "data_collection_and_processing/processed_datasets/courses_processed.json";
+const DEFAULT_MODEL: &str = "nvidia/nemotron-3-nano-30b-a3b:free";
+/// Name of this stage in usage reports.
+const STAGE: &str = "process";
+/// Completion tokens assumed per record until a run has measured them; about one course.
+const EXPECTED_COMPLETION_TOKENS: u64 = 300;
+
+#[derive(Debug, Clone, Args)]
+pub struct ProcessArgs {
+    /// CSV file to read; glob patterns are expanded. Repeat for several.
+    #[arg(long = "input", short, value_name = "GLOB", default_value = DEFAULT_INPUT)]
+    pub inputs: Vec<String>,
+    /// Where the processed course collection is written; one course per line if it ends in
+    /// `.jsonl`.
+    #[arg(long, short, default_value = DEFAULT_OUTPUT)]
+    pub output: PathBuf,
+    /// OpenRouter model that structures each record.
+    #[arg(long, default_value = DEFAULT_MODEL)]
+    pub model: String,
+    /// Requests in flight at once.
+    #[arg(long, default_value_t = 250)]
+    pub concurrency: usize,
+    #[command(flatten)]
+    pub llm: LlmArgs,
+    /// Process at most this many records.
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Read and map the input, print the first prompt and estimate the run's cost, without
+    /// calling the model or writing output.
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Records whose prompts a dry run measures to estimate the cost.
+    #[arg(long, default_value_t = 50)]
+    pub estimate_sample: usize,
+    /// Discard the checkpoint of an earlier run and process every record again.
+    #[arg(long)]
+    pub fresh: bool,
+    /// Read a field from another CSV header, e.g. `--column summary=Description`. Fields are
+    /// name, id, type, category and summary; an empty header leaves the field blank.
+    #[arg(long = "column", value_name = "FIELD=HEADER", value_parser = parse_column)]
+    pub columns: Vec<(ColumnField, String)>,
+}
+
+/// Reads the input files one row at a time through a [`ColumnMapping`], stopping after
+/// `limit` rows, and yields the records not finished by an earlier run. Only the IDs seen so
+/// far are held in memory.
+struct PendingRecords<'a> {
+    paths: std::slice::Iter<'a, PathBuf>,
+    mapping: &'a ColumnMapping,
+    current: Option<(StringRecordsIntoIter<File>, ResolvedColumns)>,
+    done: &'a HashSet<String>,
+    seen: HashSet<String>,
+    remaining: usize,
+    /// Rows read so far.
+    read: usize,
+    /// Rows that were unreadable or had no name.
+    skipped: usize,
+    /// Records repeated in the input or finished by an earlier run.
+    finished: usize,
+}
+
+impl<'a> PendingRecords<'a> {
+    fn new(
+        paths: &'a [PathBuf],
+        mapping: &'a ColumnMapping,
+        done: &'a HashSet<String>,
+        limit: Option<usize>,
+    ) -> Self {
+        PendingRecords {
+            paths: paths.iter(),
+            mapping,
+            current: None,
+            done,
+            seen: HashSet::new(),
+            remaining: limit.unwrap_or(usize::MAX),
+            read: 0,
+            skipped: 0,
+            finished: 0,
+        }
+    }
+
+    fn open(&self, path: &Path) -> Result<(StringRecordsIntoIter<File>, ResolvedColumns)> {
+        let mut rdr = ReaderBuilder::new()
+            .from_path(path)
+            .with_context(|| format!("Couldn't open {}", path.display()))?;
+        let columns = self
+            .mapping
+            .resolve(rdr.headers()?)
+            .with_context(|| format!("Couldn't map the columns of {}", path.display()))?;
+        Ok((rdr.into_records(), columns))
+    }
+}
+
+impl Iterator for PendingRecords<'_> {
+    type Item = Result<(String, CourseRecord)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            let Some((rows, columns)) = &mut self.current else {
+                let path = self.paths.next()?;
+                match self.open(path) {
+                    Ok(current) => self.current = Some(current),
+                    Err(e) => {
+                        self.remaining = 0;
+                        return Some(Err(e));
+                    }
+                }
+                continue;
+            };
+            let Some(row) = rows.next() else {
+                self.current = None;
+                continue;
+            };
+            self.remaining -= 1;
+            self.read += 1;
+            match row.map(|row| columns.read(&row)) {
+                Ok(record) if !record.name.is_empty() => {
+                    let id = record.id();
+                    if self.done.contains(&id) || !self.seen.insert(id.clone()) {
+                        self.finished += 1;
+                    } else {
+                        return Some(Ok((id, record)));
+                    }
+                }
+                _ => self.skipped += 1,
+            }
+        }
+        None
+    }
+}
+
+pub async fn run(args: ProcessArgs) -> Result<()> {
+    if args.concurrency == 0 {
+        bail!("--concurrency must be at least 1");
+    }
+
+    let mut paths = Vec::new();
+    for pattern in &args.inputs {
+        let matched: Vec<PathBuf> = glob(pattern)
+            .with_context(|| format!("Invalid input pattern '{pattern}'"))?
+            .filter_map(Result::ok)
+            .collect();
+        if matched.is_empty() {
+            bail!("No input files match '{pattern}'");
+        }
+        paths.extend(matched);
+    }
+
+    // Skip what an earlier run finished, and rows repeated in the input
+    let done = if args.fresh {
+        HashSet::new()
+    } else {
+        checkpoint::finished_ids(&args.output)?
+    };
+
+    // Count the pending records first, so the progress bar has a length
+    let mapping = ColumnMapping::new(&args.columns);
+    let mut counter = PendingRecords::new(&paths, &mapping, &done, args.limit);
+    let mut first = None;
+    let mut sample = Sample::new(if args.dry_run {
+        args.estimate_sample
+    } else {
+        0
+    });
+    let mut pending = 0;
+    for item in counter.by_ref() {
+        let (_, record) = item?;
+        sample.offer(|| format!("{SYSTEM_PROMPT}{}", user_prompt(&record)));
+        first.get_or_insert(record);
+        pending += 1;
+    }
+
+    println!(
+        "Found {} records in {} files ({} unreadable or untitled rows skipped).",
+        counter.read - counter.skipped,
+        paths.len(),
+        counter.skipped
+    );
+    if counter.finished > 0 {
+        println!(
+            "{} records already processed or repeated, {} to go.",
+            counter.finished, pending
+        );
+    }
+
+    if args.dry_run {
+        println!(
+            "Dry run: would send {} records to {} and write {}.",
+            pending,
+            args.model,
+            args.output.display()
+        );
+        if let Some(first) = &first {
+            println!("First prompt:\n{}", user_prompt(first));
+        }
+        let estimate = usage::estimate(
+            STAGE,
+            &args.model,
+            pending,
+            sample.items(),
+            EXPECTED_COMPLETION_TOKENS,
+            &args.output,
+        )
+        .await?;
+        println!(
+            "Estimated from {} sampled records: {}",
+            sample.items().len(),
+            estimate
+        );
+        return Ok(());
+    }
+
+    let api_key = env::var("OPENROUTER_API_KEY").context("OPENROUTER_API_KEY must be set")?;
+    let mut checkpoint = Checkpoint::open(&args.output, args.fresh)?;
+    let mut retries = RetryFile::create(&args.output)?;
+    println!("Processing...");
+
+    // Initialize the OpenRouter client; it paces and retries requests itself
+    let client = LlmClient::new(api_key, &args.llm);
+
+    let outcome = async {
+        // Setup Progress Bar
+        let pb = ProgressBar::new(pending);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}",
+                )?
+                .progress_chars("#>-"),
+        );
+
+        // Semaphore for Concurrency Control
+        let semaphore = Arc::new(Semaphore::new(args.concurrency));
+        let model_arc = Arc::new(args.model.clone());
+
+        // Process in Parallel, checkpointing each result as it arrives
+        let records = PendingRecords::new(&paths, &mapping, &done, args.limit);
+        let mut results = stream::iter(records)
+            .map(|item| {
+                let client = client.clone();
+                let model = Arc::clone(&model_arc);
+                let permit = Arc::clone(&semaphore);
+                let pb = pb.clone();
+
+                async move {
+                    let (id, record) = item?;
+                    let _permit = permit.acquire().await.unwrap();
+                    let result = process_row(&client, &model, &record).await;
+                    pb.set_message(client.stats().to_string());
+                    pb.inc(1);
+                    Ok::<_, anyhow::Error>((id, record, result))
+                }
+            })
+            .buffer_unordered(args.concurrency); // Run N tasks in parallel
+
+        let mut processed = 0;
+        while let Some(item) = results.next().await {
+            let (id, record, result) = item?;
+            match result {
+                Ok(mut course) => {
+                    // The stable ID is what the merger deduplicates on.
+                    course.video_id = id.clone();
+                    checkpoint.record(&CheckpointEntry {
+                        id,
+                        outcome: Outcome::Kept {
+                            course: Box::new(course),
+                        },
+                    })?;
+                    processed += 1;
+                }
+                Err(error) => {
+                    pb.println(format!("{}: {}", record.name, error));
+                    retries.record(&id, &error, &record)?;
+                }
+            }
+        }
+
+        pb.finish_with_message(format!("Processing complete ({})", client.stats()));
+
+        // Write everything checkpointed so far, this run and earlier ones
+        let mut writer = CourseWriter::create(&args.output)
+            .with_context(|| format!("Couldn't create {}", args.output.display()))?;
+        let written = checkpoint.write_kept(&mut writer, |_| true)?;
+        writer.finish()?;
+
+        println!(
+            "Successfully wrote {} courses ({} new) to {}",
+            written,
+            processed,
+            args.output.display()
+        );
+        println!("OpenRouter: {}", client.stats());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    // Account for what was spent even when the run failed part way
+    UsageReport::record_run(STAGE, &client.usage(), &args.output, args.fresh)?;
+    outcome?;
+    if !retries.is_empty() {
+        println!(
+            "{} records failed and are listed in {}; run again to retry them.",
+            retries.len(),
+            retries.path().display()
+        );
+    }
+
+    Ok(())
+}
+
+const SYSTEM_PROMPT: &str = r#"
+    You are a data processing API. You will receive raw Course data.
+    Your job is to transform this into a specific JSON schema representing a Video/Course object.
+
+    Rules:
+    1. Output strictly valid JSON. No markdown, no conversational text.
+    2. Infer missing fields based on context.
+    3. Generate 'topic' (snake_case), 'prerequisite_topics' (array), 'skill_path', and 'duration' (ISO 8601 format like PT1H).
+    4. For 'views' and 'likes', you can use the provided 'User_vote' and 'Rating' to estimate realistic numbers if needed, or stick to the input.
+    5. 'enhanced_with_llm' must be true.
+
+    Schema required:
+    {
+      "title": "string",
+      "description": "string",
+      "channel_name": "string",
+      "published_date": "YYYY-MM-DDTHH:MM:SSZ",
+      "views": number,
+      "likes": number,
+      "duration": "string",
+      "skill_path": "string",
+      "level": "string",
+      "type": "string (macro/micro)",
+      "content": "",
+      "topic": "string",
+      "prerequisite_topics": ["string"],
+      "enhanced_with_llm": true
+    }
+    "#;
+
+fn user_prompt(record: &CourseRecord) -> String {
+    format!(
+        "Convert this record:\nTitle: {}\nID: {}\nType: {}\nCategory: {}\nFull Description: {}",
+        record.name, record.course_id, record.course_type, record.category, record.summary
+    )
+}
+
+async fn process_row(
+    client: &LlmClient,
+    model: &str,
+    record: &CourseRecord,
+) -> Result<Course, String> {
+    let user_prompt = user_prompt(record);
+
+    let payload = json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": SYSTEM_PROMPT},
+            {"role": "user", "content": user_prompt}
+        ]
+    });
+
+    let content = client.chat(&payload).await.map_err(|e| e.to_string())?;
+
+    let clean_json = content
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```");
+
+    // Parsed leniently: the model may leave optional fields out or null.
+    serde_json::from_str::<RawCourse>(clean_json)
+        .map(Course::from)
+        .map_err(|e| format!("JSON Parse Error: {e}"))
+}
@@ -0,0 +1,334 @@
+//! Append-only progress files, so a stage that dies halfway resumes instead of paying for
+//! the same LLM calls again.
+//!
+//! A stage appends one line per finished record to `<output>.checkpoint.jsonl` as it goes and
+//! builds its output from that file at the end. Records that failed go to
+//! `<output>.retry.jsonl` instead and are not checkpointed, so running the stage again
+//! retries exactly those. Records a stage removes on purpose can be listed, with the reason,
+//! in `<output>.rejects.jsonl`.
+
+use anyhow::{Context, Result};
+use course_schema::{Course, CourseWriter};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// A record ID that stays the same between runs: the source ID when there is one, otherwise a
+/// hash of `fallback`.
+pub fn record_id(source_id: &str, fallback: &[&str]) -> String {
+    let source_id = source_id.trim();
+    if !source_id.is_empty() {
+        return source_id.to_string();
+    }
+    // FNV-1a, which unlike `DefaultHasher` is fixed across Rust releases.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in fallback {
+        for byte in part.trim().bytes().chain([0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("h{hash:016x}")
+}
+
+/// `dir/name.json` becomes `dir/name.<suffix>`.
+pub fn sidecar_path(output: &Path, suffix: &str) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    output.with_file_name(format!("{stem}.{suffix}"))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Outcome {
+    Kept {
+        course: Box<Course>,
+    },
+    /// Finished but left out of the output, e.g. rejected by the quality check.
+    Dropped {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckpointEntry {
+    pub id: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+pub struct Checkpoint {
+    path: PathBuf,
+    file: File,
+    done: HashSet<String>,
+}
+
+impl Checkpoint {
+    /// Opens the checkpoint for `output`, remembering the records it already holds. With
+    /// `fresh`, starts over from an empty file.
+    pub fn open(output: &Path, fresh: bool) -> Result<Self> {
+        let path = sidecar_path(output, "checkpoint.jsonl");
+        let file = open_log(&path, fresh)?;
+        let done = finished_ids(output)?;
+        Ok(Checkpoint { path, file, done })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    pub fn is_done(&self, id: &str) -> bool {
+        self.done.contains(id)
+    }
+
+    /// Appends one finished record.
+    pub fn record(&mut self, entry: &CheckpointEntry) -> Result<()> {
+        append_line(&mut self.file, &self.path, entry)?;
+        self.done.insert(entry.id.clone());
+        Ok(())
+    }
+
+    /// Writes every kept course whose ID passes `include`, in the order they were last
+    /// finished, and returns how many. The checkpoint is read twice rather than held in
+    /// memory.
+    pub fn write_kept<W: Write>(
+        &self,
+        writer: &mut CourseWriter<W>,
+        include: impl Fn(&str) -> bool,
+    ) -> Result<usize> {
+        let latest = latest_lines(&self.path)?;
+        let mut written = 0;
+        for (line_number, line) in lines(&self.path)?.enumerate() {
+            let line = line?;
+            let Ok(head) = serde_json::from_str::<EntryHead>(&line) else {
+                continue;
+            };
+            if latest.get(&head.id) != Some(&line_number) || !include(&head.id) {
+                continue;
+            }
+            if let Outcome::Kept { course } =
+                serde_json::from_str::<CheckpointEntry>(&line)?.outcome
+            {
+                writer.write(&course)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// IDs already in the checkpoint for `output`, without opening it for writing.
+pub fn finished_ids(output: &Path) -> Result<HashSet<String>> {
+    let path = sidecar_path(output, "checkpoint.jsonl");
+    Ok(latest_lines(&path)?.into_keys().collect())
+}
+
+/// Whether `path` is one of the files the stages write next to their output, so it isn't
+/// mistaken for an input.
+pub fn is_sidecar(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    SIDECAR_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(&format!(".{suffix}")))
+}
+
+const SIDECAR_SUFFIXES: [&str; 6] = [
+    "checkpoint.jsonl",
+    "retry.jsonl",
+    "rejects.jsonl",
+    "merges.jsonl",
+    "borderline.jsonl",
+    "usage.json",
+];
+
+/// The start of a checkpoint line, read without parsing the course in it.
+#[derive(Deserialize)]
+struct EntryHead {
+    id: String,
+}
+
+/// The line each ID was last finished on. A torn last line from a crash is skipped; that
+/// record is simply processed again.
+fn latest_lines(path: &Path) -> Result<HashMap<String, usize>> {
+    let mut latest = HashMap::new();
+    if !path.exists() {
+        return Ok(latest);
+    }
+    for (line_number, line) in lines(path)?.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<EntryHead>(&line) {
+            Ok(head) => {
+                latest.insert(head.id, line_number);
+            }
+            Err(e) => eprintln!(
+                "Skipping unreadable line {} of {}: {e}",
+                line_number + 1,
+                path.display()
+            ),
+        }
+    }
+    Ok(latest)
+}
+
+fn lines(path: &Path) -> Result<std::io::Lines<BufReader<File>>> {
+    let file = File::open(path).with_context(|| format!("Couldn't open {}", path.display()))?;
+    Ok(BufReader::new(file).lines())
+}
+
+#[derive(Serialize)]
+struct RetryEntry<'a, T> {
+    id: &'a str,
+    error: &'a str,
+    record: &'a T,
+}
+
+/// Records that failed this run, with the error and the input record. Rewritten on every
+/// run, since the failures are retried anyway.
+pub struct RetryFile {
+    path: PathBuf,
+    file: File,
+    count: usize,
+}
+
+impl RetryFile {
+    pub fn create(output: &Path) -> Result<Self> {
+        let path = sidecar_path(output, "retry.jsonl");
+        let file =
+            File::create(&path).with_context(|| format!("Couldn't create {}", path.display()))?;
+        Ok(RetryFile {
+            path,
+            file,
+            count: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn record<T: Serialize>(&mut self, id: &str, error: &str, record: &T) -> Result<()> {
+        append_line(
+            &mut self.file,
+            &self.path,
+            &RetryEntry { id, error, record },
+        )?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct RejectEntry<'a, T> {
+    id: &'a str,
+    reason: &'a str,
+    score: Option<f32>,
+    record: &'a T,
+}
+
+/// Records a stage removed on purpose, with why, for curators to audit. Kept across resumed
+/// runs like the checkpoint, since the records rejected earlier aren't checked again.
+pub struct RejectsFile {
+    path: PathBuf,
+    file: File,
+    count: usize,
+}
+
+impl RejectsFile {
+    pub fn open(output: &Path, fresh: bool) -> Result<Self> {
+        let path = sidecar_path(output, "rejects.jsonl");
+        let file = open_log(&path, fresh)?;
+        Ok(RejectsFile {
+            path,
+            file,
+            count: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Rejections written this run.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn record<T: Serialize>(
+        &mut self,
+        id: &str,
+        reason: &str,
+        score: Option<f32>,
+        record: &T,
+    ) -> Result<()> {
+        let entry = RejectEntry {
+            id,
+            reason,
+            score,
+            record,
+        };
+        append_line(&mut self.file, &self.path, &entry)?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Opens a JSONL file for appending, emptied first with `fresh`.
+fn open_log(path: &Path, fresh: bool) -> Result<File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    if fresh && path.exists() {
+        fs::remove_file(path).with_context(|| format!("Couldn't remove {}", path.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Couldn't open {}", path.display()))?;
+    // Close off a torn last line so the next entry starts on a line of its own.
+    if file.metadata()?.len() > 0 {
+        let mut last = [0u8];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            file.write_all(b"\n")?;
+        }
+    }
+    Ok(file)
+}
+
+/// Appends `entry` as one line in a single write, so a crash loses at most that line.
+fn append_line<T: Serialize>(file: &mut File, path: &Path, entry: &T) -> Result<()> {
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    file.write_all(&line)
+        .with_context(|| format!("Couldn't append to {}", path.display()))
+}
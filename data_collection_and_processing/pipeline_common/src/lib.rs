@@ -0,0 +1,5 @@
+//! Code shared by the pipeline stages behind `skillforge process` and `skillforge merge`.
+
+pub mod checkpoint;
+pub mod llm;
+pub mod usage;
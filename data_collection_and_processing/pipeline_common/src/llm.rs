@@ -0,0 +1,307 @@
+//! The OpenRouter client every pipeline stage sends its prompts through.
+//!
+//! Requests are paced by a token bucket and retried with exponential backoff and jitter when
+//! OpenRouter is rate limiting or failing (429, 5xx, dropped connections), waiting at least as
+//! long as its `Retry-After` header asks. Retries and failures are counted per run so a stage
+//! can show them next to its progress bar, and the tokens and cost of every completion per
+//! model, for the stage's [usage report](crate::usage::UsageReport).
+
+use clap::Args;
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::Mutex;
+
+use crate::usage::{CompletionUsage, ModelUsage};
+
+const CHAT_COMPLETIONS_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+/// First backoff delay; doubled on every further attempt.
+const BASE_DELAY: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+// Flags for how a stage talks to OpenRouter, shared by every stage that calls it. A plain
+// comment, since clap would use a doc comment as the help text of every command it is in.
+#[derive(Debug, Clone, Args)]
+pub struct LlmArgs {
+    /// Most requests started per second; unlimited when unset.
+    #[arg(long, value_name = "PER_SECOND", value_parser = parse_rate)]
+    pub rate_limit: Option<f64>,
+    /// Times a rate-limited or failed request is retried before the record counts as failed.
+    #[arg(long, default_value_t = 5)]
+    pub max_retries: u32,
+}
+
+fn parse_rate(arg: &str) -> Result<f64, String> {
+    match arg.parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!(
+            "expected a number of requests per second above 0, got '{arg}'"
+        )),
+    }
+}
+
+// OpenRouter Response Structure
+#[derive(Deserialize)]
+struct OpenRouterResponse {
+    #[serde(default)]
+    choices: Vec<Choice>,
+    usage: Option<CompletionUsage>,
+}
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+#[derive(Deserialize)]
+struct Message {
+    content: String,
+}
+
+#[derive(Debug)]
+pub enum LlmError {
+    /// The request didn't get a response, even after retrying.
+    Request(reqwest::Error),
+    /// OpenRouter answered with an error status, after retrying if it was a transient one.
+    Status { status: StatusCode, body: String },
+    /// The response wasn't a chat completion.
+    Response(String),
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmError::Request(e) => write!(f, "Request Error: {e}"),
+            LlmError::Status { status, body } => write!(f, "HTTP {status}: {body}"),
+            LlmError::Response(e) => write!(f, "Unexpected response: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LlmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LlmError::Request(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Counts for one run, read with [`LlmClient::stats`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LlmStats {
+    pub requests: u64,
+    pub retries: u64,
+    pub failures: u64,
+}
+
+impl fmt::Display for LlmStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests, {} retries, {} failed",
+            self.requests, self.retries, self.failures
+        )
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
+    /// Per requested model.
+    usage: StdMutex<BTreeMap<String, ModelUsage>>,
+}
+
+/// Cheap to clone; clones share the rate limit and the counters.
+#[derive(Clone)]
+pub struct LlmClient {
+    http: Client,
+    api_key: Arc<str>,
+    max_retries: u32,
+    limiter: Option<Arc<TokenBucket>>,
+    counters: Arc<Counters>,
+}
+
+impl LlmClient {
+    pub fn new(api_key: String, args: &LlmArgs) -> Self {
+        LlmClient {
+            http: Client::new(),
+            api_key: api_key.into(),
+            max_retries: args.max_retries,
+            limiter: args.rate_limit.map(|r| Arc::new(TokenBucket::new(r))),
+            counters: Arc::default(),
+        }
+    }
+
+    pub fn stats(&self) -> LlmStats {
+        LlmStats {
+            requests: self.counters.requests.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+            failures: self.counters.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Tokens and cost of this client's completions so far, per model.
+    pub fn usage(&self) -> BTreeMap<String, ModelUsage> {
+        self.counters.usage.lock().unwrap().clone()
+    }
+
+    /// Sends a chat completion request and returns the first choice's message content. Asks
+    /// OpenRouter to report the cost along with the tokens, unless `payload` says otherwise.
+    pub async fn chat(&self, payload: &Value) -> Result<String, LlmError> {
+        let mut payload = payload.clone();
+        if let Some(fields) = payload.as_object_mut() {
+            fields
+                .entry("usage")
+                .or_insert_with(|| json!({"include": true}));
+        }
+        let result = self
+            .send_with_retries(&payload)
+            .await
+            .and_then(|body| self.first_choice(&payload, &body));
+        if result.is_err() {
+            self.counters.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// The raw body of the first successful response.
+    async fn send_with_retries(&self, payload: &Value) -> Result<String, LlmError> {
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire().await;
+            }
+            self.counters.requests.fetch_add(1, Ordering::Relaxed);
+
+            let (error, retry_after) = match self
+                .http
+                .post(CHAT_COMPLETIONS_URL)
+                .bearer_auth(&self.api_key)
+                .json(payload)
+                .send()
+                .await
+            {
+                Ok(resp) if resp.status().is_success() => {
+                    match resp.text().await {
+                        Ok(body) => return Ok(body),
+                        // The connection dropped mid-body; worth another try.
+                        Err(e) => (LlmError::Request(e), None),
+                    }
+                }
+                Ok(resp) => {
+                    let status = resp.status();
+                    let retry_after = retry_after(resp.headers());
+                    let body = resp.text().await.unwrap_or_default();
+                    let error = LlmError::Status { status, body };
+                    if !is_transient(status) {
+                        return Err(error);
+                    }
+                    (error, retry_after)
+                }
+                Err(e) => (LlmError::Request(e), None),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error);
+            }
+            attempt += 1;
+            self.counters.retries.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(backoff(attempt).max(retry_after.unwrap_or_default())).await;
+        }
+    }
+
+    /// The first choice's message content, counting the response's usage under the model
+    /// `payload` asked for; a completion is paid for even if it's unusable.
+    fn first_choice(&self, payload: &Value, body: &str) -> Result<String, LlmError> {
+        let response: OpenRouterResponse =
+            serde_json::from_str(body).map_err(|e| LlmError::Response(e.to_string()))?;
+        if let Some(usage) = &response.usage {
+            let model = payload["model"].as_str().unwrap_or("unknown").to_string();
+            self.counters
+                .usage
+                .lock()
+                .unwrap()
+                .entry(model)
+                .or_default()
+                .add(&ModelUsage::from(usage));
+        }
+        response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| LlmError::Response("no choices".to_string()))
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Exponential backoff with full jitter: a random delay up to `BASE_DELAY * 2^(attempt - 1)`.
+fn backoff(attempt: u32) -> Duration {
+    let ceiling = BASE_DELAY
+        .saturating_mul(1 << (attempt - 1).min(16))
+        .min(MAX_DELAY);
+    ceiling.mul_f64(rand::rng().random_range(0.0..=1.0))
+}
+
+/// `Retry-After` as either a number of seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()?
+            .duration_since(SystemTime::now())
+            .unwrap_or_default(),
+    };
+    Some(delay.min(MAX_DELAY))
+}
+
+/// Allows `rate` requests per second on average, in bursts of up to one second's worth.
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(rate: f64) -> Self {
+        let capacity = rate.max(1.0);
+        TokenBucket {
+            rate,
+            capacity,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let (tokens, last) = &mut *state;
+                let now = Instant::now();
+                *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * self.rate)
+                    .min(self.capacity);
+                *last = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - *tokens) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
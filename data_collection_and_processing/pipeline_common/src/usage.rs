@@ -0,0 +1,297 @@
+//! What the LLM calls of a stage cost, and what a run is likely to cost before it starts.
+//!
+//! The client adds up the `usage` OpenRouter returns with every completion per model, and a
+//! stage adds that to `<output>.usage.json` at the end of a run, so the report covers every
+//! run towards that output since the last `--fresh`. Estimates count about four characters per
+//! prompt token and price tokens from OpenRouter's model list.
+
+use anyhow::{Context, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use crate::checkpoint::sidecar_path;
+
+const MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
+/// Rough characters per token of English text, for prompts not yet sent.
+const CHARS_PER_TOKEN: u64 = 4;
+
+/// Tokens and cost of the completions one model returned.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ModelUsage {
+    /// Completions that reported their usage.
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// In USD, as reported by OpenRouter.
+    pub cost: f64,
+}
+
+impl ModelUsage {
+    pub(crate) fn add(&mut self, other: &ModelUsage) {
+        self.requests += other.requests;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.cost += other.cost;
+    }
+}
+
+impl fmt::Display for ModelUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} prompt + {} completion tokens, ${:.4}",
+            self.prompt_tokens, self.completion_tokens, self.cost
+        )
+    }
+}
+
+/// The `usage` object of a chat completion.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CompletionUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+    cost: Option<f64>,
+}
+
+impl From<&CompletionUsage> for ModelUsage {
+    fn from(usage: &CompletionUsage) -> Self {
+        ModelUsage {
+            requests: 1,
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cost: usage.cost.unwrap_or_default(),
+        }
+    }
+}
+
+/// Usage per stage and model, written next to a stage's output.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// Per stage, then per model.
+    pub stages: BTreeMap<String, BTreeMap<String, ModelUsage>>,
+    pub total: ModelUsage,
+}
+
+impl UsageReport {
+    pub fn path(output: &Path) -> PathBuf {
+        sidecar_path(output, "usage.json")
+    }
+
+    /// The report of earlier runs towards `output`; empty with `fresh` or when there is none.
+    pub fn load(output: &Path, fresh: bool) -> Result<Self> {
+        let path = UsageReport::path(output);
+        if fresh || !path.exists() {
+            return Ok(UsageReport::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Couldn't read {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    /// Adds one run of `stage`, as returned by [`LlmClient::usage`](crate::llm::LlmClient::usage).
+    pub fn add(&mut self, stage: &str, usage: &BTreeMap<String, ModelUsage>) {
+        let models = self.stages.entry(stage.to_string()).or_default();
+        for (model, usage) in usage {
+            models.entry(model.clone()).or_default().add(usage);
+            self.total.add(usage);
+        }
+    }
+
+    /// Writes the report next to `output` and returns where.
+    pub fn write(&self, output: &Path) -> Result<PathBuf> {
+        let path = UsageReport::path(output);
+        let file =
+            File::create(&path).with_context(|| format!("Couldn't create {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(path)
+    }
+
+    /// Prints one run of `stage` per model and adds it to the report next to `output`, which
+    /// `fresh` starts over. Stages call this however the run ends, so a failed run's spend is
+    /// still on record.
+    pub fn record_run(
+        stage: &str,
+        usage: &BTreeMap<String, ModelUsage>,
+        output: &Path,
+        fresh: bool,
+    ) -> Result<()> {
+        for (model, usage) in usage {
+            println!("{model}: {usage}");
+        }
+        let mut report = UsageReport::load(output, fresh)?;
+        report.add(stage, usage);
+        println!(
+            "Usage so far ({}) written to {}",
+            report.total,
+            report.write(output)?.display()
+        );
+        Ok(())
+    }
+
+    /// Average completion tokens of the requests `stage` has sent to `model` so far.
+    fn completion_tokens_per_request(&self, stage: &str, model: &str) -> Option<f64> {
+        let usage = self.stages.get(stage)?.get(model)?;
+        (usage.requests > 0).then(|| usage.completion_tokens as f64 / usage.requests as f64)
+    }
+}
+
+/// A uniform random sample of at most `capacity` items from a stream of unknown length.
+pub struct Sample<T> {
+    capacity: usize,
+    offered: u64,
+    items: Vec<T>,
+}
+
+impl<T> Sample<T> {
+    pub fn new(capacity: usize) -> Self {
+        Sample {
+            capacity,
+            offered: 0,
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Offers the next item of the stream; `item` is only called when it is taken.
+    pub fn offer(&mut self, item: impl FnOnce() -> T) {
+        self.offered += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(item());
+        } else if self.capacity > 0 {
+            let slot = rand::rng().random_range(0..self.offered);
+            if let Some(kept) = self.items.get_mut(slot as usize) {
+                *kept = item();
+            }
+        }
+    }
+
+    /// Items offered so far, taken or not.
+    pub fn offered(&self) -> u64 {
+        self.offered
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+/// USD per token.
+#[derive(Debug, Clone, Copy)]
+pub struct Pricing {
+    pub prompt: f64,
+    pub completion: f64,
+}
+
+#[derive(Deserialize)]
+struct ModelList {
+    data: Vec<ModelInfo>,
+}
+#[derive(Deserialize)]
+struct ModelInfo {
+    id: String,
+    pricing: ModelPricing,
+}
+// OpenRouter sends prices as decimal strings
+#[derive(Deserialize)]
+struct ModelPricing {
+    prompt: String,
+    completion: String,
+}
+
+/// `model`'s prices from OpenRouter's model list, or `None` if it isn't listed.
+pub async fn fetch_pricing(model: &str) -> Result<Option<Pricing>> {
+    let models: ModelList = reqwest::get(MODELS_URL)
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let Some(info) = models.data.into_iter().find(|info| info.id == model) else {
+        return Ok(None);
+    };
+    Ok(Some(Pricing {
+        prompt: info.pricing.prompt.parse()?,
+        completion: info.pricing.completion.parse()?,
+    }))
+}
+
+/// What a run is projected to send and cost.
+#[derive(Debug)]
+pub struct Estimate {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// `None` when the model's prices couldn't be found.
+    pub cost: Option<f64>,
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests, about {} prompt + {} completion tokens",
+            self.requests, self.prompt_tokens, self.completion_tokens
+        )?;
+        match self.cost {
+            Some(cost) => write!(f, ", about ${cost:.2}"),
+            None => write!(f, ", cost unknown"),
+        }
+    }
+}
+
+/// Projects the cost of sending `requests` prompts to `model` from `sample`, prompts drawn
+/// from them. Completions are assumed as long as `stage`'s earlier runs towards `output` got
+/// on average, or `default_completion_tokens` without any.
+pub async fn estimate(
+    stage: &str,
+    model: &str,
+    requests: u64,
+    sample: &[String],
+    default_completion_tokens: u64,
+    output: &Path,
+) -> Result<Estimate> {
+    let prompt_tokens_per_request = if sample.is_empty() {
+        0.0
+    } else {
+        sample
+            .iter()
+            .map(|prompt| estimate_tokens(prompt))
+            .sum::<u64>() as f64
+            / sample.len() as f64
+    };
+    let completion_tokens_per_request = UsageReport::load(output, false)?
+        .completion_tokens_per_request(stage, model)
+        .unwrap_or(default_completion_tokens as f64);
+
+    let prompt_tokens = (prompt_tokens_per_request * requests as f64).round() as u64;
+    let completion_tokens = (completion_tokens_per_request * requests as f64).round() as u64;
+    let cost = match fetch_pricing(model).await {
+        Ok(Some(pricing)) => Some(
+            prompt_tokens as f64 * pricing.prompt + completion_tokens as f64 * pricing.completion,
+        ),
+        Ok(None) => {
+            eprintln!("{model} isn't in OpenRouter's model list; can't price it.");
+            None
+        }
+        Err(e) => {
+            eprintln!("Couldn't fetch OpenRouter's prices: {e}");
+            None
+        }
+    };
+    Ok(Estimate {
+        requests,
+        prompt_tokens,
+        completion_tokens,
+        cost,
+    })
+}
+
+/// Tokens `text` is likely to take, without a tokenizer.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(CHARS_PER_TOKEN)
+}
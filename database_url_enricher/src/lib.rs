@@ -1,4 +1,7 @@
+//! Finds URLs for stored courses that are missing one; `skillforge enrich`.
+
 use anyhow::Result; // Removed unused 'Context'
+use course_schema::StoredCourseRef;
 use dashmap::DashMap;
 use flate2::read::GzDecoder;
 use futures::stream::{self, StreamExt};
@@ -7,26 +10,17 @@ use quick_xml::reader::Reader;
 use rand::Rng;
 use reqwest::Client;
 use scraper::{Html, Selector};
-use serde::{Deserialize, Serialize};
 use std::io::{BufReader, Read};
 use std::sync::Arc;
 use std::time::Duration;
 use surrealdb::Surreal;
-use surrealdb::engine::local::{Db, RocksDb};
+use surrealdb::engine::local::Db;
 use surrealdb::sql::Thing as RecordId;
 use tokio::sync::Semaphore;
 
 // --- DATA STRUCTURES ---
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Courses {
-    pub id: Option<RecordId>,
-    pub title: String,
-    pub channel_name: String,
-    pub ctype: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub url: Option<String>,
-}
+pub type Courses = StoredCourseRef<RecordId>;
 
 struct SitemapKnowledgeBase {
     catalog: DashMap<(String, String), String>,
@@ -198,8 +192,7 @@ struct AutoEnricher {
 
 impl AutoEnricher {
     async fn new(db_path: &str, max_concurrent: usize) -> Result<Self> {
-        let db = Surreal::new::<RocksDb>(db_path).await?;
-        db.use_ns("main").use_db("main").await?;
+        let db = course_store::open(db_path).await?;
 
         let client = Client::builder()
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36")
@@ -248,7 +241,7 @@ impl AutoEnricher {
 
                     // 3. Stealth Scraper (Fallback)
                     if found_url.is_none() {
-                        let jitter = rand::thread_rng().gen_range(500..2000);
+                        let jitter = rand::rng().random_range(500..2000);
                         tokio::time::sleep(Duration::from_millis(jitter)).await;
                         found_url = engine.stealth_scrape(&course).await;
                     }
@@ -399,9 +392,8 @@ impl AutoEnricher {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let enricher = AutoEnricher::new("skillforge", 50).await?;
+pub async fn run(db_path: &str, max_concurrent: usize) -> Result<()> {
+    let enricher = AutoEnricher::new(db_path, max_concurrent).await?;
     enricher.run().await?;
     Ok(())
 }